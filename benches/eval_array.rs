@@ -85,3 +85,35 @@ fn bench_eval_array_loop(bench: &mut Bencher) {
 
     bench.iter(|| engine.consume_ast(&ast).unwrap());
 }
+
+#[bench]
+fn bench_eval_array_homogeneous_loop(bench: &mut Bencher) {
+    let script = r#"let x = [];
+                            for i in range(0, 1000) { x.push(i); }
+                            let sum = 0;
+                            for v in x { sum += v; }
+    "#;
+
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::None);
+
+    let ast = engine.compile(script).unwrap();
+
+    bench.iter(|| engine.consume_ast(&ast).unwrap());
+}
+
+#[bench]
+fn bench_eval_array_mixed_loop(bench: &mut Bencher) {
+    let script = r#"let x = [];
+                            for i in range(0, 1000) { if i % 2 == 0 { x.push(i); } else { x.push("x"); } }
+                            let sum = 0;
+                            for v in x { if type_of(v) != "string" { sum += v; } }
+    "#;
+
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::None);
+
+    let ast = engine.compile(script).unwrap();
+
+    bench.iter(|| engine.consume_ast(&ast).unwrap());
+}
@@ -75,3 +75,54 @@ fn bench_eval_scope_complex(bench: &mut Bencher) {
 
     bench.iter(|| engine.consume_ast_with_scope(&mut scope, &ast).unwrap());
 }
+
+#[bench]
+fn bench_eval_scope_loop_locals(bench: &mut Bencher) {
+    let script = r#"
+            let a = 1; let b = 2; let c = 3; let d = 4; let e = 5;
+            let sum = 0;
+            for i in range(0, 100) {
+                let f = i;
+                sum += a + b + c + d + e + f;
+            }
+            sum
+        "#;
+
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::None);
+
+    let ast = engine.compile(script).unwrap();
+
+    bench.iter(|| engine.consume_ast(&ast).unwrap());
+}
+
+#[bench]
+fn bench_eval_scope_loop_external(bench: &mut Bencher) {
+    // Variables pushed into an external scope have no pre-calculated offset,
+    // so every access inside the loop goes through a look-up by name hash
+    let script = r#"
+            let sum = 0;
+            for i in range(0, 100) {
+                sum += a + b + c + d + e + i;
+            }
+            sum
+        "#;
+
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::None);
+
+    let mut scope = Scope::new();
+    scope.push("a", 1 as INT);
+    scope.push("b", 2 as INT);
+    scope.push("c", 3 as INT);
+    scope.push("d", 4 as INT);
+    scope.push("e", 5 as INT);
+
+    let ast = engine.compile_with_scope(&scope, script).unwrap();
+
+    bench.iter(|| {
+        let sum = engine.eval_ast_with_scope::<INT>(&mut scope, &ast).unwrap();
+        scope.rewind(5);
+        sum
+    });
+}
@@ -24,6 +24,24 @@ fn bench_iterations_1000(bench: &mut Bencher) {
     bench.iter(|| engine.consume_ast(&ast).unwrap());
 }
 
+#[bench]
+fn bench_iterations_for_range(bench: &mut Bencher) {
+    let script = r#"
+            let sum = 0;
+
+            for i in range(0, 1_000) {
+                sum += i;
+            }
+        "#;
+
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::None);
+
+    let ast = engine.compile(script).unwrap();
+
+    bench.iter(|| engine.consume_ast(&ast).unwrap());
+}
+
 #[bench]
 fn bench_iterations_fibonacci(bench: &mut Bencher) {
     let script = r#"
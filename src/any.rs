@@ -7,6 +7,9 @@ use crate::r#unsafe::{unsafe_cast_box, unsafe_try_cast};
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
 
@@ -22,6 +25,9 @@ use crate::stdlib::{
     vec::Vec,
 };
 
+#[cfg(not(feature = "no_index"))]
+use crate::stdlib::iter::FromIterator;
+
 #[cfg(not(feature = "no_std"))]
 #[cfg(not(target_arch = "wasm32"))]
 use crate::stdlib::time::Instant;
@@ -119,8 +125,29 @@ impl dyn Variant {
     }
 }
 
+/// A user-defined tag value that can be attached to a `Dynamic`.
+#[cfg(feature = "tags")]
+pub type Tag = i32;
+
+/// Storage for the tag of a `Dynamic`.
+#[cfg(feature = "tags")]
+pub(crate) type TagValue = Tag;
+
+/// Storage for the tag of a `Dynamic` - empty without the `tags` feature so that `Dynamic` stays small.
+#[cfg(not(feature = "tags"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TagValue;
+
+/// Default tag value of a `Dynamic`.
+#[cfg(feature = "tags")]
+pub(crate) const DEFAULT_TAG: TagValue = 0;
+
+/// Default tag value of a `Dynamic`.
+#[cfg(not(feature = "tags"))]
+pub(crate) const DEFAULT_TAG: TagValue = TagValue;
+
 /// Dynamic type containing any value.
-pub struct Dynamic(pub(crate) Union);
+pub struct Dynamic(pub(crate) Union, pub(crate) TagValue);
 
 /// Internal `Dynamic` representation.
 ///
@@ -133,6 +160,8 @@ pub enum Union {
     Int(INT),
     #[cfg(not(feature = "no_float"))]
     Float(FLOAT),
+    #[cfg(feature = "decimal")]
+    Decimal(Box<Decimal>),
     #[cfg(not(feature = "no_index"))]
     Array(Box<Array>),
     #[cfg(not(feature = "no_object"))]
@@ -170,6 +199,8 @@ impl Dynamic {
             Union::Int(_) => TypeId::of::<INT>(),
             #[cfg(not(feature = "no_float"))]
             Union::Float(_) => TypeId::of::<FLOAT>(),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(_) => TypeId::of::<Decimal>(),
             #[cfg(not(feature = "no_index"))]
             Union::Array(_) => TypeId::of::<Array>(),
             #[cfg(not(feature = "no_object"))]
@@ -189,6 +220,8 @@ impl Dynamic {
             Union::Int(_) => type_name::<INT>(),
             #[cfg(not(feature = "no_float"))]
             Union::Float(_) => type_name::<FLOAT>(),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(_) => "decimal",
             #[cfg(not(feature = "no_index"))]
             Union::Array(_) => "array",
             #[cfg(not(feature = "no_object"))]
@@ -203,6 +236,75 @@ impl Dynamic {
     }
 }
 
+/// Map the name of a standard type into a friendly name (e.g. `ImmutableString` into `string`).
+///
+/// Names of other types are returned unchanged.
+pub(crate) fn map_std_type_name(name: &str) -> &str {
+    if name == type_name::<String>()
+        || name == type_name::<ImmutableString>()
+        || name == type_name::<&str>()
+    {
+        return "string";
+    }
+    if name == type_name::<Dynamic>() {
+        return "Dynamic";
+    }
+    if name == type_name::<FnPtr>() {
+        return "Fn";
+    }
+    #[cfg(feature = "decimal")]
+    if name == type_name::<Decimal>() {
+        return "decimal";
+    }
+    #[cfg(not(feature = "no_index"))]
+    if name == type_name::<Array>() {
+        return "array";
+    }
+    #[cfg(not(feature = "no_object"))]
+    if name == type_name::<Map>() {
+        return "map";
+    }
+    #[cfg(not(feature = "no_std"))]
+    if name == type_name::<Instant>() {
+        return "timestamp";
+    }
+
+    name
+}
+
+/// Get the name of a standard type from its `TypeId`, or `?` if it is not a standard type.
+pub(crate) fn type_name_of_id(id: TypeId) -> &'static str {
+    macro_rules! check_types {
+        ($($t:ty),*) => {
+            $(
+                if id == TypeId::of::<$t>() {
+                    return type_name::<$t>();
+                }
+            )*
+        };
+    }
+
+    check_types!(INT, bool, char, ImmutableString, FnPtr, Dynamic, ());
+
+    #[cfg(not(feature = "only_i32"))]
+    #[cfg(not(feature = "only_i64"))]
+    check_types!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128);
+
+    #[cfg(not(feature = "no_float"))]
+    check_types!(f32, f64);
+
+    #[cfg(feature = "decimal")]
+    check_types!(Decimal);
+
+    #[cfg(not(feature = "no_index"))]
+    check_types!(Array);
+
+    #[cfg(not(feature = "no_object"))]
+    check_types!(Map);
+
+    "?"
+}
+
 impl fmt::Display for Dynamic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.0 {
@@ -213,6 +315,8 @@ impl fmt::Display for Dynamic {
             Union::Int(value) => fmt::Display::fmt(value, f),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => fmt::Display::fmt(value, f),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => fmt::Display::fmt(value, f),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_object"))]
@@ -237,6 +341,8 @@ impl fmt::Debug for Dynamic {
             Union::Int(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => fmt::Debug::fmt(value, f),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_object"))]
@@ -254,26 +360,32 @@ impl fmt::Debug for Dynamic {
 impl Clone for Dynamic {
     fn clone(&self) -> Self {
         match self.0 {
-            Union::Unit(value) => Self(Union::Unit(value)),
-            Union::Bool(value) => Self(Union::Bool(value)),
-            Union::Str(ref value) => Self(Union::Str(value.clone())),
-            Union::Char(value) => Self(Union::Char(value)),
-            Union::Int(value) => Self(Union::Int(value)),
+            Union::Unit(value) => Self(Union::Unit(value), self.1),
+            Union::Bool(value) => Self(Union::Bool(value), self.1),
+            Union::Str(ref value) => Self(Union::Str(value.clone()), self.1),
+            Union::Char(value) => Self(Union::Char(value), self.1),
+            Union::Int(value) => Self(Union::Int(value), self.1),
             #[cfg(not(feature = "no_float"))]
-            Union::Float(value) => Self(Union::Float(value)),
+            Union::Float(value) => Self(Union::Float(value), self.1),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(ref value) => Self(Union::Decimal(value.clone()), self.1),
             #[cfg(not(feature = "no_index"))]
-            Union::Array(ref value) => Self(Union::Array(value.clone())),
+            Union::Array(ref value) => Self(Union::Array(value.clone()), self.1),
             #[cfg(not(feature = "no_object"))]
-            Union::Map(ref value) => Self(Union::Map(value.clone())),
-            Union::FnPtr(ref value) => Self(Union::FnPtr(value.clone())),
-            Union::Variant(ref value) => (***value).clone_into_dynamic(),
+            Union::Map(ref value) => Self(Union::Map(value.clone()), self.1),
+            Union::FnPtr(ref value) => Self(Union::FnPtr(value.clone()), self.1),
+            Union::Variant(ref value) => {
+                let mut result = (***value).clone_into_dynamic();
+                result.1 = self.1;
+                result
+            }
         }
     }
 }
 
 impl Default for Dynamic {
     fn default() -> Self {
-        Self(Union::Unit(()))
+        Self(Union::Unit(()), DEFAULT_TAG)
     }
 }
 
@@ -329,6 +441,13 @@ impl Dynamic {
             }
         }
 
+        #[cfg(feature = "decimal")]
+        {
+            if let Some(result) = <dyn Any>::downcast_ref::<Decimal>(&value) {
+                return result.clone().into();
+            }
+        }
+
         let mut boxed = Box::new(value);
 
         boxed = match unsafe_cast_box::<_, Dynamic>(boxed) {
@@ -350,12 +469,116 @@ impl Dynamic {
         #[cfg(not(feature = "no_object"))]
         {
             boxed = match unsafe_cast_box::<_, Map>(boxed) {
-                Ok(map) => return (*map).into(),
+                Ok(map) => return Self::from_map(*map),
                 Err(val) => val,
             }
         }
 
-        Self(Union::Variant(Box::new(boxed)))
+        Self(Union::Variant(Box::new(boxed)), DEFAULT_TAG)
+    }
+
+    /// Create a `Dynamic` holding an `Array`.
+    ///
+    /// The result is the same as an array created in a script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Dynamic;
+    ///
+    /// let result = Dynamic::from_array(vec![Dynamic::from(1_i64), Dynamic::from(true)]);
+    /// assert_eq!(result.type_name(), "array");
+    /// assert_eq!(result.to_string(), "[1, true]");
+    /// ```
+    #[cfg(not(feature = "no_index"))]
+    pub fn from_array(array: Array) -> Self {
+        Self(Union::Array(Box::new(array)), DEFAULT_TAG)
+    }
+
+    /// Create a `Dynamic` holding an object `Map`.
+    ///
+    /// The result is the same as an object map created in a script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Dynamic, Map};
+    ///
+    /// let mut map = Map::new();
+    /// map.insert("answer".into(), Dynamic::from(42_i64));
+    ///
+    /// let result = Dynamic::from_map(map);
+    /// assert_eq!(result.type_name(), "map");
+    /// ```
+    #[cfg(not(feature = "no_object"))]
+    pub fn from_map(map: Map) -> Self {
+        Self(Union::Map(Box::new(map)), DEFAULT_TAG)
+    }
+
+    /// Create a fully independent copy of the `Dynamic` value.
+    ///
+    /// Unlike `clone`, which may share underlying storage (e.g. the contents of strings)
+    /// with the original, this recursively copies all arrays, object maps and strings
+    /// so that nothing is shared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Dynamic;
+    ///
+    /// let x = Dynamic::from("hello".to_string());
+    /// let y = x.clone_deep();
+    ///
+    /// assert_eq!(y.as_str().unwrap(), "hello");
+    /// assert_ne!(x.as_str().unwrap().as_ptr(), y.as_str().unwrap().as_ptr());
+    /// ```
+    pub fn clone_deep(&self) -> Self {
+        match &self.0 {
+            Union::Str(value) => Self(Union::Str(value.as_str().to_string().into()), self.1),
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(value) => Self(
+                Union::Array(Box::new(value.iter().map(Dynamic::clone_deep).collect())),
+                self.1,
+            ),
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(value) => Self(
+                Union::Map(Box::new(
+                    value
+                        .iter()
+                        .map(|(k, v)| (k.as_str().to_string().into(), v.clone_deep()))
+                        .collect(),
+                )),
+                self.1,
+            ),
+            _ => self.clone(),
+        }
+    }
+
+    /// Get the tag attached to the `Dynamic` value. A newly created value has a tag of zero.
+    ///
+    /// Tags are preserved when the value is cloned, and therefore through variable assignments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::Dynamic;
+    ///
+    /// let mut x = Dynamic::from(42_i64);
+    /// assert_eq!(x.tag(), 0);
+    ///
+    /// x.set_tag(123);
+    /// assert_eq!(x.clone().tag(), 123);
+    /// ```
+    #[cfg(feature = "tags")]
+    pub fn tag(&self) -> Tag {
+        self.1
+    }
+
+    /// Attach a tag to the `Dynamic` value.
+    #[cfg(feature = "tags")]
+    pub fn set_tag(&mut self, value: Tag) -> &mut Self {
+        self.1 = value;
+        self
     }
 
     /// Get a copy of the `Dynamic` value as a specific type.
@@ -390,6 +613,8 @@ impl Dynamic {
             Union::Int(value) => unsafe_try_cast(value),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => unsafe_try_cast(value),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => unsafe_cast_box::<_, T>(value).ok().map(|v| *v),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => unsafe_cast_box::<_, T>(value).ok().map(|v| *v),
             #[cfg(not(feature = "no_object"))]
@@ -433,6 +658,8 @@ impl Dynamic {
             Union::Int(value) => unsafe_try_cast(value).unwrap(),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => unsafe_try_cast(value).unwrap(),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => *unsafe_cast_box::<_, T>(value).unwrap(),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => *unsafe_cast_box::<_, T>(value).unwrap(),
             #[cfg(not(feature = "no_object"))]
@@ -459,6 +686,8 @@ impl Dynamic {
             Union::Int(value) => <dyn Any>::downcast_ref::<T>(value),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => <dyn Any>::downcast_ref::<T>(value),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => <dyn Any>::downcast_ref::<T>(value.as_ref()),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => <dyn Any>::downcast_ref::<T>(value.as_ref()),
             #[cfg(not(feature = "no_object"))]
@@ -484,6 +713,8 @@ impl Dynamic {
             Union::Int(value) => <dyn Any>::downcast_mut::<T>(value),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => <dyn Any>::downcast_mut::<T>(value),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => <dyn Any>::downcast_mut::<T>(value.as_mut()),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => <dyn Any>::downcast_mut::<T>(value.as_mut()),
             #[cfg(not(feature = "no_object"))]
@@ -560,65 +791,91 @@ impl Dynamic {
 
 impl From<()> for Dynamic {
     fn from(value: ()) -> Self {
-        Self(Union::Unit(value))
+        Self(Union::Unit(value), DEFAULT_TAG)
     }
 }
 impl From<bool> for Dynamic {
     fn from(value: bool) -> Self {
-        Self(Union::Bool(value))
+        Self(Union::Bool(value), DEFAULT_TAG)
     }
 }
 impl From<INT> for Dynamic {
     fn from(value: INT) -> Self {
-        Self(Union::Int(value))
+        Self(Union::Int(value), DEFAULT_TAG)
     }
 }
 #[cfg(not(feature = "no_float"))]
 impl From<FLOAT> for Dynamic {
     fn from(value: FLOAT) -> Self {
-        Self(Union::Float(value))
+        Self(Union::Float(value), DEFAULT_TAG)
+    }
+}
+#[cfg(feature = "decimal")]
+impl From<Decimal> for Dynamic {
+    fn from(value: Decimal) -> Self {
+        Self(Union::Decimal(Box::new(value)), DEFAULT_TAG)
     }
 }
 impl From<char> for Dynamic {
     fn from(value: char) -> Self {
-        Self(Union::Char(value))
+        Self(Union::Char(value), DEFAULT_TAG)
     }
 }
 impl<S: Into<ImmutableString>> From<S> for Dynamic {
     fn from(value: S) -> Self {
-        Self(Union::Str(value.into()))
+        Self(Union::Str(value.into()), DEFAULT_TAG)
     }
 }
 #[cfg(not(feature = "no_index"))]
 impl<T: Variant + Clone> From<Vec<T>> for Dynamic {
     fn from(value: Vec<T>) -> Self {
-        Self(Union::Array(Box::new(
-            value.into_iter().map(Dynamic::from).collect(),
-        )))
+        Self(
+            Union::Array(Box::new(value.into_iter().map(Dynamic::from).collect())),
+            DEFAULT_TAG,
+        )
     }
 }
 #[cfg(not(feature = "no_index"))]
 impl<T: Variant + Clone> From<&[T]> for Dynamic {
     fn from(value: &[T]) -> Self {
-        Self(Union::Array(Box::new(
-            value.iter().cloned().map(Dynamic::from).collect(),
-        )))
+        Self(
+            Union::Array(Box::new(value.iter().cloned().map(Dynamic::from).collect())),
+            DEFAULT_TAG,
+        )
+    }
+}
+#[cfg(not(feature = "no_index"))]
+impl<T: Variant + Clone> FromIterator<T> for Dynamic {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(
+            Union::Array(Box::new(iter.into_iter().map(Dynamic::from).collect())),
+            DEFAULT_TAG,
+        )
     }
 }
 #[cfg(not(feature = "no_object"))]
 impl<K: Into<ImmutableString>, T: Variant + Clone> From<HashMap<K, T>> for Dynamic {
     fn from(value: HashMap<K, T>) -> Self {
-        Self(Union::Map(Box::new(
-            value
-                .into_iter()
-                .map(|(k, v)| (k.into(), Dynamic::from(v)))
-                .collect(),
-        )))
+        Self(
+            Union::Map(Box::new(
+                value
+                    .into_iter()
+                    .map(|(k, v)| (k.into(), Dynamic::from(v)))
+                    .collect(),
+            )),
+            DEFAULT_TAG,
+        )
+    }
+}
+#[cfg(not(feature = "no_object"))]
+impl From<Map> for Dynamic {
+    fn from(value: Map) -> Self {
+        Self::from_map(value)
     }
 }
 impl From<FnPtr> for Dynamic {
     fn from(value: FnPtr) -> Self {
-        Self(Union::FnPtr(value))
+        Self(Union::FnPtr(value), DEFAULT_TAG)
     }
 }
 
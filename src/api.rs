@@ -1,29 +1,37 @@
 //! Module that defines the extern API of `Engine`.
 
-use crate::any::{Dynamic, Variant};
+use crate::any::{map_std_type_name, Dynamic, Variant};
 use crate::engine::{
     get_script_function_by_signature, make_getter, make_setter, Engine, Imports, State, FN_IDX_GET,
     FN_IDX_SET,
 };
-use crate::error::ParseError;
+use crate::error::{ParseError, ParseErrorType as PERR};
 use crate::fn_call::FuncArgs;
-use crate::fn_native::{IteratorFn, SendSync};
+use crate::fn_native::{IteratorFn, NativeCallContext, SendSync};
 use crate::fn_register::RegisterFn;
 use crate::optimize::{optimize_into_ast, OptimizationLevel};
-use crate::parser::AST;
+use crate::parser::{FnAccess, AST};
 use crate::result::EvalAltResult;
 use crate::scope::Scope;
-use crate::token::{lex, Position};
+use crate::token::{lex_with_engine, symbol_text, Position, Token};
 use crate::utils::StaticVec;
 
 #[cfg(not(feature = "no_object"))]
 use crate::engine::Map;
 
+#[cfg(not(feature = "no_module"))]
+use crate::{
+    fn_native::Shared,
+    module::{resolvers::StaticModuleResolver, Module},
+    parser::{ASTNode, Expr, ImmutableString, Stmt},
+};
+
 use crate::stdlib::{
     any::{type_name, TypeId},
     boxed::Box,
-    mem,
+    format, mem,
     string::{String, ToString},
+    vec::Vec,
 };
 
 #[cfg(not(feature = "no_std"))]
@@ -129,6 +137,290 @@ impl Engine {
         self.global_module.set_iter(TypeId::of::<T>(), f);
     }
 
+    /// Register a `Module` with the `Engine` under a namespace.
+    ///
+    /// Functions and variables in the module can then be accessed by scripts via qualified paths
+    /// (e.g. `name::func(...)` or `name::var`) without an `import` statement.
+    /// Modules imported by the script under the same name take precedence.
+    ///
+    /// If there is an existing module registered under the same name, it is replaced.
+    ///
+    /// Not available under the `no_module` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Module};
+    ///
+    /// let mut module = Module::new();
+    /// module.set_fn_1("inc", |x: i64| Ok(x + 1));
+    /// module.set_var("answer", 41_i64);
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_static_module("calc", module);
+    ///
+    /// assert_eq!(engine.eval::<i64>("calc::inc(calc::answer)")?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_module"))]
+    pub fn register_static_module(&mut self, name: &str, mut module: Module) {
+        module.index_all_sub_modules();
+        self.static_modules.insert(name.into(), module);
+    }
+
+    /// Register a constant with the `Engine` that is visible to all scripts.
+    ///
+    /// The constant is used whenever a variable of the same name is not found in the scope,
+    /// so variables in the scope or declared by the script take precedence.
+    /// Scripts cannot assign to it.
+    ///
+    /// The constant is looked up when the script runs, so it is never folded into a compiled `AST` -
+    /// replacing it affects scripts already compiled, and a variable of the same name in the scope
+    /// passed to `eval_ast_with_scope` takes precedence.
+    ///
+    /// If there is an existing global constant with the same name, it is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_global_constant("MAX_HP", 100_i64);
+    ///
+    /// assert_eq!(engine.eval::<i64>("MAX_HP - 58")?, 42);
+    /// assert!(engine.eval::<()>("MAX_HP = 1").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_global_constant<T: Variant + Clone>(&mut self, name: &str, value: T) {
+        self.global_constants
+            .insert(name.into(), Dynamic::from(value));
+    }
+
+    /// Register a native Rust function that receives the context of the call,
+    /// plus a list of mutable `Dynamic` references to the arguments.
+    /// A list of `TypeId`'s is taken as the argument types.
+    ///
+    /// The call context gives access to the scripting `Engine`, the `Scope` of the calling script
+    /// and the position of the call, and can call back into script, so this is useful for
+    /// higher-order functions taking function pointers.
+    ///
+    /// The first argument is passed by reference; all other arguments are passed by value
+    /// and may be consumed.
+    ///
+    /// A parameter type of `TypeId::of::<Dynamic>()` accepts arguments of any type.  The function
+    /// is then only called when no other function matches the argument types exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use std::any::TypeId;
+    /// use rhai::{Engine, FnPtr, NativeCallContext};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_raw_fn(
+    ///     "twice",
+    ///     &[TypeId::of::<FnPtr>(), TypeId::of::<i64>()],
+    ///     |context: NativeCallContext, args| {
+    ///         let fp = args[0].clone().cast::<FnPtr>();
+    ///         let x = context.call_fn_ptr(&fp, &mut [args[1]])?;
+    ///         context.call_fn_ptr(&fp, &mut [&mut x.clone()])
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(engine.eval::<i64>(r#"twice(Fn("abs"), -42)"#)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_raw_fn<T: Variant + Clone>(
+        &mut self,
+        name: &str,
+        arg_types: &[TypeId],
+        func: impl Fn(NativeCallContext, &mut [&mut Dynamic]) -> Result<T, Box<EvalAltResult>>
+            + SendSync
+            + 'static,
+    ) {
+        self.global_module.set_fn_var_args(name, arg_types, func);
+    }
+
+    /// Register an additional name for a native Rust function already registered with the `Engine`,
+    /// so that scripts can call it under either name (e.g. to keep an old name after a rename).
+    ///
+    /// All overloads of the function are aliased.  Functions in loaded packages are not affected.
+    ///
+    /// Returns `EvalAltResult::ErrorFunctionNotFound` if no function named `name` is registered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("add", |x: i64, y: i64| x + y);
+    /// engine.register_global_fn_alias("add", "plus")?;
+    ///
+    /// assert_eq!(engine.eval::<i64>("plus(40, 2)")?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_global_fn_alias(
+        &mut self,
+        name: &str,
+        alias: &str,
+    ) -> Result<(), Box<EvalAltResult>> {
+        if self.global_module.set_fn_alias(name, alias) == 0 {
+            return Err(Box::new(EvalAltResult::ErrorFunctionNotFound(
+                name.into(),
+                Position::none(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Generate signatures for all the registered Rust functions, including those in loaded
+    /// packages, sorted by name.
+    ///
+    /// Each signature is in the form `name(type1, type2, ...) -> return_type`, with type names
+    /// mapped in the same way as `type_of` (including any names registered via
+    /// `register_type_with_name`). This is useful for tooling such as auto-completion.
+    ///
+    /// Functions registered directly via `Module::set_fn` carry no type information
+    /// and are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("add", |x: i64, y: i64| x + y);
+    ///
+    /// let signatures = engine.gen_fn_signatures();
+    /// assert!(signatures.contains(&"add(i64, i64) -> i64".to_string()));
+    /// ```
+    pub fn gen_fn_signatures(&self) -> Vec<String> {
+        let map_name = |name| self.map_type_name(map_std_type_name(name));
+
+        let mut signatures: Vec<_> = self
+            .packages
+            .iter()
+            .flat_map(|p| p.iter_fn())
+            .chain(self.global_module.iter_fn())
+            .filter(|(_, access, _, _, _)| *access == FnAccess::Public)
+            .filter_map(|(name, _, _, types, _)| {
+                types.as_ref().map(|(params, ret)| {
+                    let params: Vec<_> = params.iter().map(|&p| map_name(p)).collect();
+                    format!("{}({}) -> {}", name, params.join(", "), map_name(ret))
+                })
+            })
+            .collect();
+
+        signatures.sort();
+        signatures.dedup();
+        signatures
+    }
+
+    /// Register a custom infix operator with the `Engine` at a particular precedence.
+    ///
+    /// The operator can be either a sequence of symbol characters or an identifier.
+    /// Using the operator calls the function of the same name with the two operands as arguments,
+    /// which must be registered separately (or defined in script).
+    ///
+    /// Precedence must be non-zero. For reference, built-in operators range from 40 (`||`)
+    /// through 60 (comparisons) and 80 (`+`, `-`) to 110 (`%`).
+    ///
+    /// Keywords and built-in operators are reserved and cannot be overridden.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_custom_operator("foo", 60)?;
+    /// engine.register_fn("foo", |x: i64, y: i64| x * y - (x + y));
+    ///
+    /// assert_eq!(engine.eval::<i64>("1 + 2 * 3 foo 4 - 5 / 6")?, 17);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_custom_operator(
+        &mut self,
+        symbol: &str,
+        precedence: u8,
+    ) -> Result<(), ParseError> {
+        if precedence == 0 {
+            return Err(PERR::BadInput(format!(
+                "Precedence of custom operator '{}' must be non-zero",
+                symbol
+            ))
+            .into_err(Position::none()));
+        }
+
+        let scripts = [symbol];
+        let tokens: StaticVec<_> = lex_with_engine(&scripts, self)
+            .map(|(token, _)| token)
+            .take_while(|token| !token.is_eof())
+            .collect();
+
+        let valid = match tokens.as_ref() {
+            [Token::Identifier(_)] | [Token::Custom(_)] => true,
+            // Unknown characters (e.g. `$`) are not reserved
+            [token @ Token::LexError(_)] => symbol_text(token).is_some(),
+            [] | [_] => false,
+            tokens => tokens.iter().all(|token| symbol_text(token).is_some()),
+        };
+
+        if !valid {
+            return Err(PERR::BadInput(format!(
+                "'{}' is a reserved symbol or keyword and cannot be a custom operator",
+                symbol
+            ))
+            .into_err(Position::none()));
+        }
+
+        self.custom_operators.insert(symbol.into(), precedence);
+        Ok(())
+    }
+
+    /// Disable a particular keyword or operator in scripts.
+    ///
+    /// Any script using the keyword or operator then fails to compile with a parse error.
+    /// This is useful for restricting the language available to untrusted scripts
+    /// (e.g. disabling `while` and `loop` to forbid loops).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.disable_symbol("while");
+    ///
+    /// assert!(engine.compile("while true {}").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn disable_symbol(&mut self, symbol: &str) {
+        self.disabled_symbols.insert(symbol.into());
+    }
+
     /// Register a getter function for a member of a registered type with the `Engine`.
     ///
     /// The function signature must start with `&mut self` and not `&self`.
@@ -487,6 +779,82 @@ impl Engine {
         self.compile_scripts_with_scope(scope, &[script])
     }
 
+    /// Compile a string into a self-contained [`AST`], using own scope.
+    ///
+    /// All `import` statements with a constant string path are resolved at compile time via the
+    /// `Engine`'s module resolver, and the resulting modules are embedded in the [`AST`].
+    /// The [`AST`] can then be evaluated without access to the module files, or with no module
+    /// resolver at all.
+    ///
+    /// Import paths that are not constant strings are still resolved during evaluation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Module, Scope};
+    /// use rhai::module_resolvers::StaticModuleResolver;
+    ///
+    /// let mut resolver = StaticModuleResolver::new();
+    /// let mut module = Module::new();
+    /// module.set_var("answer", 42_i64);
+    /// resolver.insert("hello", module);
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_module_resolver(Some(resolver));
+    ///
+    /// let ast = engine.compile_into_self_contained(&Scope::new(), r#"import "hello" as h; h::answer"#)?;
+    ///
+    /// // The module is embedded, so no resolver is needed any more
+    /// engine.set_module_resolver(None::<StaticModuleResolver>);
+    ///
+    /// assert_eq!(engine.eval_ast::<i64>(&ast)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_module"))]
+    pub fn compile_into_self_contained(
+        &self,
+        scope: &Scope,
+        script: &str,
+    ) -> Result<AST, Box<EvalAltResult>> {
+        let mut ast = self.compile_with_scope(scope, script)?;
+
+        let mut imports: Vec<(ImmutableString, Position)> = Default::default();
+
+        ast.walk(&mut |node| {
+            if let ASTNode::Stmt(Stmt::Import(x)) = node {
+                if let Expr::StringConstant(x) = &x.0 {
+                    imports.push(x.as_ref().clone());
+                }
+            }
+        });
+
+        let mut resolver = StaticModuleResolver::new();
+
+        for (path, pos) in imports {
+            if resolver.contains_path(&path) {
+                continue;
+            }
+
+            let module = match &self.module_resolver {
+                Some(module_resolver) => module_resolver.resolve(self, &path, pos)?,
+                None => {
+                    return Err(Box::new(EvalAltResult::ErrorModuleNotFound(
+                        path.to_string(),
+                        pos,
+                    )))
+                }
+            };
+
+            resolver.insert(path.to_string(), module);
+        }
+
+        ast.set_resolver(Some(Shared::new(resolver)));
+
+        Ok(ast)
+    }
+
     /// When passed a list of strings, first join the strings into one large script,
     /// and then compile them into an [`AST`] using own scope, which can be used later for evaluation.
     ///
@@ -548,7 +916,7 @@ impl Engine {
         scripts: &[&str],
         optimization_level: OptimizationLevel,
     ) -> Result<AST, ParseError> {
-        let stream = lex(scripts, self.max_string_size);
+        let stream = lex_with_engine(scripts, self);
         self.parse(&mut stream.peekable(), scope, optimization_level)
     }
 
@@ -673,7 +1041,7 @@ impl Engine {
 
         // Trims the JSON string and add a '#' in front
         let scripts = ["#", json.trim()];
-        let stream = lex(&scripts, self.max_string_size);
+        let stream = lex_with_engine(&scripts, self);
         let ast =
             self.parse_global_expr(&mut stream.peekable(), &scope, OptimizationLevel::None)?;
 
@@ -754,7 +1122,7 @@ impl Engine {
         script: &str,
     ) -> Result<AST, ParseError> {
         let scripts = [script];
-        let stream = lex(&scripts, self.max_string_size);
+        let stream = lex_with_engine(&scripts, self);
         {
             let mut peekable = stream.peekable();
             self.parse_global_expr(&mut peekable, scope, self.optimization_level)
@@ -909,7 +1277,7 @@ impl Engine {
         script: &str,
     ) -> Result<T, Box<EvalAltResult>> {
         let scripts = [script];
-        let stream = lex(&scripts, self.max_string_size);
+        let stream = lex_with_engine(&scripts, self);
 
         // No need to optimize a lone expression
         let ast = self.parse_global_expr(&mut stream.peekable(), scope, OptimizationLevel::None)?;
@@ -995,6 +1363,11 @@ impl Engine {
     ) -> Result<(Dynamic, u64), Box<EvalAltResult>> {
         let mut state = State::new();
 
+        #[cfg(not(feature = "no_module"))]
+        {
+            state.resolver = ast.resolver();
+        }
+
         ast.statements()
             .iter()
             .try_fold(().into(), |_, stmt| {
@@ -1041,11 +1414,62 @@ impl Engine {
         script: &str,
     ) -> Result<(), Box<EvalAltResult>> {
         let scripts = [script];
-        let stream = lex(&scripts, self.max_string_size);
+        let stream = lex_with_engine(&scripts, self);
         let ast = self.parse(&mut stream.peekable(), scope, self.optimization_level)?;
         self.consume_ast_with_scope(scope, &ast)
     }
 
+    /// Run a script for its side effects, discarding the result and only returning error (if any).
+    ///
+    /// This is the same as `consume`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// engine.run(r#"print("hello, world!");"#)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run(&self, script: &str) -> Result<(), Box<EvalAltResult>> {
+        self.consume(script)
+    }
+
+    /// Run a script with own scope for its side effects, discarding the result and only
+    /// returning error (if any).
+    ///
+    /// This is the same as `consume_with_scope`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.push("x", 40_i64);
+    ///
+    /// engine.run_with_scope(&mut scope, "x += 2; let y = x * 2;")?;
+    ///
+    /// assert_eq!(scope.get_value::<i64>("x"), Some(42));
+    /// assert_eq!(scope.get_value::<i64>("y"), Some(84));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_with_scope(
+        &self,
+        scope: &mut Scope,
+        script: &str,
+    ) -> Result<(), Box<EvalAltResult>> {
+        self.consume_with_scope(scope, script)
+    }
+
     /// Evaluate an AST, but throw away the result and only return error (if any).
     /// Useful for when you don't need the result, but still need to keep track of possible errors.
     pub fn consume_ast(&self, ast: &AST) -> Result<(), Box<EvalAltResult>> {
@@ -1060,6 +1484,11 @@ impl Engine {
         ast: &AST,
     ) -> Result<(), Box<EvalAltResult>> {
         let mut state = State::new();
+
+        #[cfg(not(feature = "no_module"))]
+        {
+            state.resolver = ast.resolver();
+        }
         let mut mods = Default::default();
 
         ast.statements()
@@ -1204,6 +1633,11 @@ impl Engine {
             })?;
 
         let mut state = State::new();
+
+        #[cfg(not(feature = "no_module"))]
+        {
+            state.resolver = ast.resolver();
+        }
         let mut mods = Imports::new();
         let args = args.as_mut();
 
@@ -1241,12 +1675,19 @@ impl Engine {
         let lib = ast
             .lib()
             .iter_fn()
-            .filter(|(_, _, _, f)| f.is_script())
-            .map(|(_, _, _, f)| f.get_fn_def().clone())
+            .filter(|(_, _, _, _, f)| f.is_script())
+            .map(|(_, _, _, _, f)| f.get_fn_def().clone())
             .collect();
 
         let stmt = mem::take(ast.statements_mut());
-        optimize_into_ast(self, scope, stmt, lib, optimization_level)
+
+        #[allow(unused_mut)]
+        let mut optimized = optimize_into_ast(self, scope, stmt, lib, optimization_level);
+
+        #[cfg(not(feature = "no_module"))]
+        optimized.set_resolver(ast.resolver());
+
+        optimized
     }
 
     /// Register a callback for script evaluation progress.
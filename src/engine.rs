@@ -1,9 +1,10 @@
 //! Main module defining the script evaluation `Engine`.
 
-use crate::any::{Dynamic, Union, Variant};
+use crate::any::{Dynamic, Union, Variant, DEFAULT_TAG};
+
 use crate::calc_fn_hash;
 use crate::error::ParseErrorType;
-use crate::fn_native::{CallableFunction, Callback, FnCallArgs, FnPtr};
+use crate::fn_native::{CallableFunction, Callback, FnCallArgs, FnPtr, NativeCallContext, Shared};
 use crate::module::{resolvers, Module, ModuleRef, ModuleResolver};
 use crate::optimize::OptimizationLevel;
 use crate::packages::{Package, PackageLibrary, PackagesCollection, StandardPackage};
@@ -11,8 +12,11 @@ use crate::parser::{Expr, FnAccess, ImmutableString, ReturnType, ScriptFnDef, St
 use crate::r#unsafe::unsafe_cast_var_name_to_lifetime;
 use crate::result::EvalAltResult;
 use crate::scope::{EntryType as ScopeEntryType, Scope};
+use crate::syntax::{CustomSyntax, EvalContext, Expression};
 use crate::token::{is_valid_identifier, Position};
-use crate::utils::StaticVec;
+use crate::utils::{calc_ident_hash, StaticVec};
+#[cfg(feature = "tags")]
+use crate::{any::Tag, stdlib::convert::TryFrom};
 
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
@@ -21,10 +25,11 @@ use crate::stdlib::{
     any::TypeId,
     borrow::Cow,
     boxed::Box,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     format,
     iter::{empty, once},
     mem,
+    ops::Range,
     string::{String, ToString},
     vec::Vec,
 };
@@ -35,11 +40,8 @@ use crate::stdlib::{
 #[cfg(not(feature = "no_index"))]
 pub type Array = Vec<Dynamic>;
 
-/// Hash map of `Dynamic` values with `String` keys.
-///
-/// Not available under the `no_object` feature.
 #[cfg(not(feature = "no_object"))]
-pub type Map = HashMap<ImmutableString, Dynamic>;
+pub use crate::utils::Map;
 
 /// A stack of imported modules.
 pub type Imports<'a> = Vec<(Cow<'a, str>, Module)>;
@@ -71,14 +73,30 @@ pub const MAX_EXPR_DEPTH: usize = 0;
 #[cfg(feature = "unchecked")]
 pub const MAX_FUNCTION_EXPR_DEPTH: usize = 0;
 
+/// Maximum number of integer arguments considered for promotion to floating-point
+/// when searching for a matching function.
+#[cfg(not(feature = "no_float"))]
+const MAX_PROMOTED_ARGS: usize = 4;
+
+/// Maximum number of arguments of a function call for which native functions taking
+/// `Dynamic` parameters are searched when no function matches the argument types exactly.
+const MAX_DYNAMIC_ARGS: usize = 4;
+
 pub const KEYWORD_PRINT: &str = "print";
 pub const KEYWORD_DEBUG: &str = "debug";
 pub const KEYWORD_TYPE_OF: &str = "type_of";
+#[cfg(feature = "tags")]
+pub const KEYWORD_TAG: &str = "tag";
+#[cfg(feature = "tags")]
+pub const KEYWORD_SET_TAG: &str = "set_tag";
+pub const KEYWORD_ASSERT: &str = "assert";
+pub const KEYWORD_ASSERT_EQ: &str = "assert_eq";
 pub const KEYWORD_EVAL: &str = "eval";
 pub const KEYWORD_FN_PTR: &str = "Fn";
 pub const KEYWORD_FN_PTR_CALL: &str = "call";
 pub const KEYWORD_THIS: &str = "this";
 pub const FN_TO_STRING: &str = "to_string";
+pub const FN_TO_DEBUG: &str = "to_debug";
 pub const FN_GET: &str = "get$";
 pub const FN_SET: &str = "set$";
 pub const FN_IDX_GET: &str = "$index$get$";
@@ -154,7 +172,7 @@ impl Target<'_> {
                     Position::none(),
                 )))
             }
-            Self::StringChar(Dynamic(Union::Str(ref mut s)), index, _) => {
+            Self::StringChar(Dynamic(Union::Str(ref mut s), _), index, _) => {
                 // Replace the character at the specified index position
                 let new_ch = new_val
                     .as_char()
@@ -193,7 +211,7 @@ impl<T: Into<Dynamic>> From<T> for Target<'_> {
 ///
 /// This type uses some unsafe code, mainly for avoiding cloning of local variable names via
 /// direct lifetime casting.
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct State {
     /// Normally, access to variables are parsed with a relative offset into the scope to avoid a lookup.
     /// In some situation, e.g. after running an `eval` statement, subsequent offsets become mis-aligned.
@@ -206,6 +224,11 @@ pub struct State {
     pub operations: u64,
     /// Number of modules loaded.
     pub modules: usize,
+    /// Is evaluation currently inside an `#[unchecked]` block?
+    pub unchecked: bool,
+    /// Modules embedded in a self-contained [`AST`], searched before the `Engine`'s module resolver.
+    #[cfg(not(feature = "no_module"))]
+    pub resolver: Option<Shared<resolvers::StaticModuleResolver>>,
 }
 
 impl State {
@@ -261,11 +284,22 @@ pub struct Engine {
     /// A collection of all library packages loaded into the Engine.
     pub(crate) packages: PackagesCollection,
 
+    /// A collection of modules registered into the Engine under namespaces.
+    pub(crate) static_modules: HashMap<String, Module>,
+    /// A collection of constants visible to all scripts run by the Engine.
+    pub(crate) global_constants: HashMap<String, Dynamic>,
+
     /// A module resolution service.
     pub(crate) module_resolver: Option<Box<dyn ModuleResolver>>,
 
     /// A hashmap mapping type names to pretty-print names.
     pub(crate) type_names: HashMap<String, String>,
+    /// A hashmap mapping custom operator symbols to their precedence.
+    pub(crate) custom_operators: HashMap<String, u8>,
+    /// A set of keywords and symbols disabled in scripts.
+    pub(crate) disabled_symbols: HashSet<String>,
+    /// A hashmap mapping the leading keywords of custom syntax to their definitions.
+    pub(crate) custom_syntax: HashMap<String, CustomSyntax>,
 
     /// Callback closure for implementing the `print` command.
     pub(crate) print: Callback<str, ()>,
@@ -294,6 +328,19 @@ pub struct Engine {
     pub(crate) max_array_size: usize,
     /// Maximum number of properties in a map.
     pub(crate) max_map_size: usize,
+
+    /// Are `if` expressions allowed?
+    pub(crate) allow_if_expression: bool,
+    /// Are statement blocks allowed as expressions?
+    pub(crate) allow_statement_expr: bool,
+    /// Are `while`, `loop` and `for` loops allowed?
+    pub(crate) allow_looping: bool,
+    /// Are script-defined functions allowed?
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) allow_fn_definitions: bool,
+    /// Does reading a non-existent object map property raise an error?
+    #[cfg(not(feature = "no_object"))]
+    pub(crate) fail_on_invalid_map_property: bool,
 }
 
 impl Default for Engine {
@@ -304,6 +351,8 @@ impl Default for Engine {
 
             packages: Default::default(),
             global_module: Default::default(),
+            static_modules: Default::default(),
+            global_constants: Default::default(),
 
             #[cfg(not(feature = "no_module"))]
             #[cfg(not(feature = "no_std"))]
@@ -313,6 +362,9 @@ impl Default for Engine {
             module_resolver: None,
 
             type_names: Default::default(),
+            custom_operators: Default::default(),
+            disabled_symbols: Default::default(),
+            custom_syntax: Default::default(),
 
             // default print/debug implementations
             print: Box::new(default_print),
@@ -336,6 +388,14 @@ impl Default for Engine {
             max_string_size: 0,
             max_array_size: 0,
             max_map_size: 0,
+
+            allow_if_expression: true,
+            allow_statement_expr: true,
+            allow_looping: true,
+            #[cfg(not(feature = "no_function"))]
+            allow_fn_definitions: true,
+            #[cfg(not(feature = "no_object"))]
+            fail_on_invalid_map_property: false,
         };
 
         engine.load_package(StandardPackage::new().get());
@@ -426,15 +486,27 @@ fn search_imports<'s>(
     })
 }
 
-/// Search for a variable within the scope
+/// Set a variable to an integer value, reusing its storage if it already holds an integer.
+fn set_int_in_place(target: &mut Dynamic, value: INT) {
+    match target {
+        Dynamic(Union::Int(n), tag) => {
+            *n = value;
+            *tag = DEFAULT_TAG;
+        }
+        _ => *target = value.into(),
+    }
+}
+
+/// Search for a variable within the scope, falling back to the global constants
 fn search_scope<'s, 'a>(
     scope: &'s mut Scope,
     mods: &'s mut Imports,
     state: &mut State,
+    global_constants: &HashMap<String, Dynamic>,
     this_ptr: &'s mut Option<&mut Dynamic>,
     expr: &'a Expr,
-) -> Result<(&'s mut Dynamic, &'a str, ScopeEntryType, Position), Box<EvalAltResult>> {
-    let ((name, pos), modules, hash_var, index) = match expr {
+) -> Result<(Target<'s>, &'a str, ScopeEntryType, Position), Box<EvalAltResult>> {
+    let ((name, pos), modules, hash_var, index, hash) = match expr {
         Expr::Variable(v) => v.as_ref(),
         _ => unreachable!(),
     };
@@ -461,23 +533,33 @@ fn search_scope<'s, 'a>(
             })?;
 
         // Module variables are constant
-        Ok((target, name, ScopeEntryType::Constant, *pos))
+        Ok((target.into(), name, ScopeEntryType::Constant, *pos))
     } else {
         // Unqualified - check if it is directly indexed
         let index = if state.always_search { None } else { *index };
 
         let index = if let Some(index) = index {
             scope.len() - index.get()
-        } else {
+        } else if let Some((index, _)) = scope.get_index(name, *hash) {
             // Find the variable in the scope
-            scope
-                .get_index(name)
-                .ok_or_else(|| Box::new(EvalAltResult::ErrorVariableNotFound(name.into(), *pos)))?
-                .0
+            index
+        } else {
+            // Not in scope - global constants are always copied and can never be modified
+            return global_constants
+                .get(name)
+                .map(|val| {
+                    (
+                        val.clone().into(),
+                        name.as_str(),
+                        ScopeEntryType::Constant,
+                        *pos,
+                    )
+                })
+                .ok_or_else(|| Box::new(EvalAltResult::ErrorVariableNotFound(name.into(), *pos)));
         };
 
         let (val, typ) = scope.get_mut(index);
-        Ok((val, name, typ, *pos))
+        Ok((val.into(), name, typ, *pos))
     }
 }
 
@@ -495,9 +577,14 @@ impl Engine {
 
             packages: Default::default(),
             global_module: Default::default(),
+            static_modules: Default::default(),
+            global_constants: Default::default(),
             module_resolver: None,
 
             type_names: Default::default(),
+            custom_operators: Default::default(),
+            disabled_symbols: Default::default(),
+            custom_syntax: Default::default(),
             print: Box::new(|_| {}),
             debug: Box::new(|_| {}),
             progress: None,
@@ -516,6 +603,14 @@ impl Engine {
             max_string_size: 0,
             max_array_size: 0,
             max_map_size: 0,
+
+            allow_if_expression: true,
+            allow_statement_expr: true,
+            allow_looping: true,
+            #[cfg(not(feature = "no_function"))]
+            allow_fn_definitions: true,
+            #[cfg(not(feature = "no_object"))]
+            fail_on_invalid_map_property: false,
         }
     }
 
@@ -596,6 +691,20 @@ impl Engine {
         self.max_modules
     }
 
+    /// Set the depth limit for expressions and statements at global level (0 for unlimited).
+    ///
+    /// The limit is checked by the parser, so a script nesting too deeply fails to compile with
+    /// `ParseErrorType::ExprTooDeep` instead of overflowing the stack.
+    /// The limit for function bodies is not changed - use `set_max_expr_depths` to set both.
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_expr_depth(&mut self, max_expr_depth: usize) {
+        self.max_expr_depth = if max_expr_depth == usize::MAX {
+            0
+        } else {
+            max_expr_depth
+        };
+    }
+
     /// Set the depth limits for expressions (0 for unlimited).
     #[cfg(not(feature = "unchecked"))]
     pub fn set_max_expr_depths(&mut self, max_expr_depth: usize, max_function_expr_depth: usize) {
@@ -663,6 +772,83 @@ impl Engine {
         self.max_map_size
     }
 
+    /// Allow or disallow `if` statements to be used as expressions (default allowed).
+    ///
+    /// When disallowed, a script using an `if` expression fails to compile with
+    /// `ParseErrorType::ForbiddenSyntax`.  `if` statements are not affected.
+    pub fn set_allow_if_expression(&mut self, enable: bool) {
+        self.allow_if_expression = enable;
+    }
+
+    /// Are `if` statements allowed to be used as expressions?
+    pub fn allow_if_expression(&self) -> bool {
+        self.allow_if_expression
+    }
+
+    /// Allow or disallow statement blocks to be used as expressions (default allowed).
+    ///
+    /// When disallowed, a script using a statement expression fails to compile with
+    /// `ParseErrorType::ForbiddenSyntax`.  Statement blocks are not affected.
+    pub fn set_allow_statement_expr(&mut self, enable: bool) {
+        self.allow_statement_expr = enable;
+    }
+
+    /// Are statement blocks allowed to be used as expressions?
+    pub fn allow_statement_expr(&self) -> bool {
+        self.allow_statement_expr
+    }
+
+    /// Allow or disallow `while`, `loop` and `for` loops (default allowed).
+    ///
+    /// When disallowed, a script using a loop fails to compile with `ParseErrorType::ForbiddenSyntax`.
+    pub fn set_allow_looping(&mut self, enable: bool) {
+        self.allow_looping = enable;
+    }
+
+    /// Are `while`, `loop` and `for` loops allowed?
+    pub fn allow_looping(&self) -> bool {
+        self.allow_looping
+    }
+
+    /// Allow or disallow scripts to define functions (default allowed).
+    ///
+    /// When disallowed, a script containing a function definition fails to compile with
+    /// `ParseErrorType::ForbiddenSyntax`.
+    ///
+    /// Not available under the `no_function` feature.
+    #[cfg(not(feature = "no_function"))]
+    pub fn set_allow_fn_definitions(&mut self, enable: bool) {
+        self.allow_fn_definitions = enable;
+    }
+
+    /// Are scripts allowed to define functions?
+    ///
+    /// Not available under the `no_function` feature.
+    #[cfg(not(feature = "no_function"))]
+    pub fn allow_fn_definitions(&self) -> bool {
+        self.allow_fn_definitions
+    }
+
+    /// Control whether reading a non-existent object map property raises an error
+    /// (default returns `()`).
+    ///
+    /// When enabled, reading a missing property (e.g. `obj.missing` or `obj["missing"]`) fails with
+    /// `EvalAltResult::ErrorDotExpr`.  Assigning to a missing property still adds it to the map.
+    ///
+    /// Not available under the `no_object` feature.
+    #[cfg(not(feature = "no_object"))]
+    pub fn set_fail_on_invalid_map_property(&mut self, enable: bool) {
+        self.fail_on_invalid_map_property = enable;
+    }
+
+    /// Does reading a non-existent object map property raise an error?
+    ///
+    /// Not available under the `no_object` feature.
+    #[cfg(not(feature = "no_object"))]
+    pub fn fail_on_invalid_map_property(&self) -> bool {
+        self.fail_on_invalid_map_property
+    }
+
     /// Set the module resolution service used by the `Engine`.
     ///
     /// Not available under the `no_module` feature.
@@ -679,9 +865,12 @@ impl Engine {
     /// Function call arguments be _consumed_ when the function requires them to be passed by value.
     /// All function arguments not in the first position are always passed by value and thus consumed.
     /// **DO NOT** reuse the argument values unless for the first `&mut` argument - all others are silently replaced by `()`!
+    ///
+    /// `scope` is the scope of the calling script, which is made available to native functions.
+    /// Script-defined functions always run in a new scope.
     pub(crate) fn call_fn_raw(
         &self,
-        scope: &mut Scope,
+        scope: Option<&Scope>,
         mods: &mut Imports,
         state: &mut State,
         lib: &Module,
@@ -691,6 +880,7 @@ impl Engine {
         is_ref: bool,
         is_method: bool,
         def_val: Option<&Dynamic>,
+        pos: Position,
         level: usize,
     ) -> Result<(Dynamic, bool), Box<EvalAltResult>> {
         self.inc_operations(state)?;
@@ -784,7 +974,7 @@ impl Engine {
                     let (first, rest) = args.split_at_mut(1);
                     return Ok((
                         self.call_script_fn(
-                            scope,
+                            &mut Scope::new(),
                             mods,
                             state,
                             lib,
@@ -798,7 +988,15 @@ impl Engine {
                     ));
                 } else {
                     let result = self.call_script_fn(
-                        scope, mods, state, lib, &mut None, fn_name, fn_def, args, level,
+                        &mut Scope::new(),
+                        mods,
+                        state,
+                        lib,
+                        &mut None,
+                        fn_name,
+                        fn_def,
+                        args,
+                        level,
                     )?;
 
                     // Restore the original reference
@@ -808,7 +1006,9 @@ impl Engine {
                 };
             } else {
                 // Run external function
-                let result = func.get_native_fn()(self, args)?;
+                let context =
+                    NativeCallContext::new(self, lib, scope, pos, level, &mut state.operations);
+                let result = func.get_native_fn()(context, args)?;
 
                 // Restore the original reference
                 restore_first_arg(old_this_ptr, args);
@@ -842,15 +1042,79 @@ impl Engine {
 
         // See if it is built in.
         if args.len() == 2 {
-            match run_builtin_binary_op(fn_name, args[0], args[1])? {
+            match run_builtin_binary_op(fn_name, args[0], args[1], state.unchecked)? {
                 Some(v) => return Ok((v, false)),
                 None => (),
             }
         }
 
-        // Return default value (if any)
+        // No exact match - see if promoting integer arguments to floating-point finds a native function
+        #[cfg(not(feature = "no_float"))]
+        {
+            if let Some((hash, mask)) = self.search_promoted_fn(fn_name, args)? {
+                let mut promoted: StaticVec<Dynamic> = args
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, arg)| (arg.as_int().unwrap() as FLOAT).into())
+                    .collect();
+                let mut promoted = promoted.iter_mut();
+                let mut args: StaticVec<&mut Dynamic> = args
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, arg)| {
+                        if mask & (1 << i) != 0 {
+                            promoted.next().unwrap()
+                        } else {
+                            &mut **arg
+                        }
+                    })
+                    .collect();
+
+                return self.call_fn_raw(
+                    scope,
+                    mods,
+                    state,
+                    lib,
+                    fn_name,
+                    (hash, 0),
+                    args.as_mut(),
+                    is_ref && mask & 1 == 0,
+                    is_method,
+                    None,
+                    pos,
+                    level,
+                );
+            }
+        }
+
+        // Still no match - see if a native function takes `Dynamic` in place of some arguments
+        if let Some(hash) = self.search_dynamic_fn(fn_name, args) {
+            return self.call_fn_raw(
+                scope,
+                mods,
+                state,
+                lib,
+                fn_name,
+                (hash, 0),
+                args,
+                is_ref,
+                is_method,
+                None,
+                pos,
+                level,
+            );
+        }
+
+        // Return default value (if any) - except when comparing two values of the same custom type,
+        // which requires a registered comparison operator
         if let Some(val) = def_val {
-            return Ok((val.clone(), false));
+            let is_same_custom_type =
+                args.len() == 2 && args[0].is_variant() && args[0].type_id() == args[1].type_id();
+
+            if !is_same_custom_type {
+                return Ok((val.clone(), false));
+            }
         }
 
         // Getter function not found?
@@ -911,6 +1175,127 @@ impl Engine {
         )))
     }
 
+    /// Search for a native function that matches the arguments after promoting some
+    /// integer arguments to floating-point.
+    ///
+    /// Returns the function hash together with a bit mask of the promoted argument positions.
+    /// Candidates promoting the fewest arguments are preferred; if more than one candidate
+    /// promotes the same number of arguments, the call is ambiguous and an error is returned.
+    #[cfg(not(feature = "no_float"))]
+    fn search_promoted_fn(
+        &self,
+        fn_name: &str,
+        args: &FnCallArgs,
+    ) -> Result<Option<(u64, u32)>, Box<EvalAltResult>> {
+        // Positions of all integer arguments
+        let int_args: StaticVec<usize> = args
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| arg.is::<INT>())
+            .map(|(i, _)| i)
+            .collect();
+
+        if int_args.is_empty() || int_args.len() > MAX_PROMOTED_ARGS {
+            return Ok(None);
+        }
+
+        let arg_type = |mask: u32, i: usize, arg: &Dynamic| {
+            if mask & (1 << i) != 0 {
+                TypeId::of::<FLOAT>()
+            } else {
+                arg.type_id()
+            }
+        };
+
+        for num_promoted in 1..=int_args.len() as u32 {
+            let candidates: Vec<_> = (1..(1_u32 << int_args.len()))
+                .filter(|subset| subset.count_ones() == num_promoted)
+                .map(|subset| {
+                    int_args
+                        .iter()
+                        .enumerate()
+                        .filter(|&(bit, _)| subset & (1 << bit) != 0)
+                        .fold(0_u32, |mask, (_, &i)| mask | (1 << i))
+                })
+                .filter_map(|mask| {
+                    let arg_types = args
+                        .iter()
+                        .enumerate()
+                        .map(|(i, arg)| arg_type(mask, i, arg));
+                    let hash = calc_fn_hash(empty(), fn_name, args.len(), arg_types);
+
+                    if self.global_module.contains_fn(hash) || self.packages.contains_fn(hash) {
+                        Some((hash, mask))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            match candidates.len() {
+                0 => (),
+                1 => return Ok(Some(candidates[0])),
+                _ => {
+                    let signature = |mask: u32| {
+                        format!(
+                            "{} ({})",
+                            fn_name,
+                            args.iter()
+                                .enumerate()
+                                .map(|(i, arg)| {
+                                    let name = if mask & (1 << i) != 0 {
+                                        crate::stdlib::any::type_name::<FLOAT>()
+                                    } else {
+                                        (*arg).type_name()
+                                    };
+                                    self.map_type_name(name).to_string()
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    };
+
+                    return Err(Box::new(EvalAltResult::ErrorAmbiguousFunctionCall(
+                        signature(0),
+                        candidates
+                            .iter()
+                            .map(|&(_, mask)| signature(mask))
+                            .collect(),
+                        Position::none(),
+                    )));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Search for a native function with `Dynamic` parameters in place of some of the arguments,
+    /// which therefore accepts values of any type in those positions.
+    ///
+    /// Returns the function hash. Candidates with the fewest `Dynamic` parameters are preferred.
+    fn search_dynamic_fn(&self, fn_name: &str, args: &FnCallArgs) -> Option<u64> {
+        if args.is_empty() || args.len() > MAX_DYNAMIC_ARGS {
+            return None;
+        }
+
+        let num_masks = 1_u32 << args.len();
+
+        (1..=args.len() as u32)
+            .flat_map(|num_dynamic| (1..num_masks).filter(move |m| m.count_ones() == num_dynamic))
+            .map(|mask| {
+                let arg_types = args.iter().enumerate().map(|(i, arg)| {
+                    if mask & (1 << i) != 0 {
+                        TypeId::of::<Dynamic>()
+                    } else {
+                        arg.type_id()
+                    }
+                });
+                calc_fn_hash(empty(), fn_name, args.len(), arg_types)
+            })
+            .find(|&hash| self.global_module.contains_fn(hash) || self.packages.contains_fn(hash))
+    }
+
     /// Call a script-defined function.
     /// Position in `EvalAltResult` is None and must be set afterwards.
     ///
@@ -934,21 +1319,24 @@ impl Engine {
         let orig_scope_level = state.scope_level;
         state.scope_level += 1;
 
+        // `#[unchecked]` is lexically scoped, so it does not extend into called functions
+        let orig_unchecked = state.unchecked;
+        state.unchecked = false;
+
         let prev_scope_len = scope.len();
         let prev_mods_len = mods.len();
 
         // Put arguments into scope as variables
         // Actually consume the arguments instead of cloning them
-        scope.extend(
-            fn_def
-                .params
-                .iter()
-                .zip(args.iter_mut().map(|v| mem::take(*v)))
-                .map(|(name, value)| {
-                    let var_name = unsafe_cast_var_name_to_lifetime(name.as_str(), state);
-                    (var_name, ScopeEntryType::Normal, value)
-                }),
-        );
+        fn_def
+            .params
+            .iter()
+            .zip(fn_def.param_hashes.iter())
+            .zip(args.iter_mut().map(|v| mem::take(*v)))
+            .for_each(|((name, hash), value)| {
+                let var_name = unsafe_cast_var_name_to_lifetime(name.as_str(), state);
+                scope.push_with_hash(var_name, *hash, ScopeEntryType::Normal, value, false);
+            });
 
         // Evaluate the function at one higher level of call depth
         let result = self
@@ -974,6 +1362,7 @@ impl Engine {
         scope.rewind(prev_scope_len);
         mods.truncate(prev_mods_len);
         state.scope_level = orig_scope_level;
+        state.unchecked = orig_unchecked;
 
         result
     }
@@ -1003,6 +1392,7 @@ impl Engine {
     /// **DO NOT** reuse the argument values unless for the first `&mut` argument - all others are silently replaced by `()`!
     fn exec_fn_call(
         &self,
+        scope: Option<&Scope>,
         state: &mut State,
         lib: &Module,
         fn_name: &str,
@@ -1012,6 +1402,7 @@ impl Engine {
         is_ref: bool,
         is_method: bool,
         def_val: Option<&Dynamic>,
+        pos: Position,
         level: usize,
     ) -> Result<(Dynamic, bool), Box<EvalAltResult>> {
         // Qualifiers (none) + function name + number of arguments + argument `TypeId`'s.
@@ -1026,6 +1417,77 @@ impl Engine {
                 false,
             )),
 
+            // tag
+            #[cfg(feature = "tags")]
+            KEYWORD_TAG if args.len() == 1 && !self.has_override(lib, hashes) => {
+                Ok(((args[0].tag() as INT).into(), false))
+            }
+
+            // set_tag
+            #[cfg(feature = "tags")]
+            KEYWORD_SET_TAG
+                if args.len() == 2 && args[1].is::<INT>() && !self.has_override(lib, hashes) =>
+            {
+                let value = args[1].as_int().unwrap();
+                let tag = Tag::try_from(value).map_err(|_| {
+                    Box::new(EvalAltResult::ErrorArithmetic(
+                        format!("Tag value out of range: {}", value),
+                        Position::none(),
+                    ))
+                })?;
+                args[0].set_tag(tag);
+                Ok((().into(), false))
+            }
+
+            // assert
+            KEYWORD_ASSERT
+                if args.len() == 1 && args[0].is::<bool>() && !self.has_override(lib, hashes) =>
+            {
+                if args[0].as_bool().unwrap() {
+                    Ok((().into(), false))
+                } else {
+                    Err(Box::new(EvalAltResult::ErrorRuntime(
+                        "Assertion failed".into(),
+                        Position::none(),
+                    )))
+                }
+            }
+
+            // assert_eq
+            KEYWORD_ASSERT_EQ if args.len() == 2 && !self.has_override(lib, hashes) => {
+                // Compare copies because the comparison may consume its arguments
+                let mut left = args[0].clone();
+                let mut right = args[1].clone();
+                let def_val = false.into();
+
+                let (equal, _) = self.exec_fn_call(
+                    None,
+                    state,
+                    lib,
+                    "==",
+                    true,
+                    0,
+                    &mut [&mut left, &mut right],
+                    false,
+                    false,
+                    Some(&def_val),
+                    pos,
+                    level,
+                )?;
+
+                if equal.as_bool().unwrap_or(false) {
+                    return Ok((().into(), false));
+                }
+
+                let left = self.debug_value(state, lib, args[0], level)?;
+                let right = self.debug_value(state, lib, args[1], level)?;
+
+                Err(Box::new(EvalAltResult::ErrorRuntime(
+                    format!("Assertion failed: left = {}, right = {}", left, right),
+                    Position::none(),
+                )))
+            }
+
             // Fn
             KEYWORD_FN_PTR if args.len() == 1 && !self.has_override(lib, hashes) => {
                 Err(Box::new(EvalAltResult::ErrorRuntime(
@@ -1044,16 +1506,88 @@ impl Engine {
 
             // Normal function call
             _ => {
-                let mut scope = Scope::new();
                 let mut mods = Imports::new();
                 self.call_fn_raw(
-                    &mut scope, &mut mods, state, lib, fn_name, hashes, args, is_ref, is_method,
-                    def_val, level,
+                    scope, &mut mods, state, lib, fn_name, hashes, args, is_ref, is_method,
+                    def_val, pos, level,
                 )
             }
         }
     }
 
+    /// Get the debug representation of a value via its `to_debug` function.
+    /// Position in `EvalAltResult` is None and must be set afterwards.
+    fn debug_value(
+        &self,
+        state: &mut State,
+        lib: &Module,
+        value: &Dynamic,
+        level: usize,
+    ) -> Result<String, Box<EvalAltResult>> {
+        let mut value = value.clone();
+
+        let (result, _) = self.exec_fn_call(
+            None,
+            state,
+            lib,
+            FN_TO_DEBUG,
+            true,
+            0,
+            &mut [&mut value],
+            false,
+            false,
+            None,
+            Position::none(),
+            level,
+        )?;
+
+        Ok(result
+            .take_immutable_string()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|typ| typ.to_string()))
+    }
+
+    /// Call a function pointer with a list of arguments from within a native Rust function.
+    /// Position in `EvalAltResult` is None and must be set afterwards.
+    ///
+    /// Script-defined functions are searched in the functions library of the calling script.
+    pub(crate) fn call_fn_ptr(
+        &self,
+        context: NativeCallContext,
+        fn_ptr: &FnPtr,
+        args: &mut FnCallArgs,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        self.call_fn_by_name(context, fn_ptr.fn_name(), args, None)
+    }
+
+    /// Call a function by name with a list of arguments from within a native Rust function,
+    /// returning `def_val` (if any) when no matching function is found.
+    /// Position in `EvalAltResult` is None and must be set afterwards.
+    pub(crate) fn call_fn_by_name(
+        &self,
+        context: NativeCallContext,
+        fn_name: &str,
+        args: &mut FnCallArgs,
+        def_val: Option<&Dynamic>,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        let hash = calc_fn_hash(empty(), fn_name, args.len(), empty());
+        let mut state = State::new();
+        let lib = context.lib();
+        let pos = context.position();
+        let level = context.level() + 1;
+
+        // Continue counting from the caller so the callback stays within `max_operations`
+        state.operations = context.operations();
+
+        let result = self.exec_fn_call(
+            None, &mut state, lib, fn_name, false, hash, args, false, false, def_val, pos, level,
+        );
+
+        context.set_operations(state.operations);
+
+        result.map(|(v, _)| v)
+    }
+
     /// Evaluate a text string as a script - used primarily for 'eval'.
     /// Position in `EvalAltResult` is None and must be set afterwards.
     fn eval_script_expr(
@@ -1082,7 +1616,12 @@ impl Engine {
         }
 
         let statements = mem::take(ast.statements_mut());
-        let ast = AST::new(statements, lib.clone());
+        #[allow(unused_mut)]
+        let mut ast = AST::new(statements, lib.clone());
+
+        // Keep access to any embedded modules
+        #[cfg(not(feature = "no_module"))]
+        ast.set_resolver(state.resolver.clone());
 
         // Evaluate the AST
         let (result, operations) = self.eval_ast_with_scope_raw(scope, mods, &ast)?;
@@ -1154,8 +1693,8 @@ impl Engine {
                                     &mut [target.as_mut(), &mut idx_val2, &mut new_val.unwrap()];
 
                                 self.exec_fn_call(
-                                    state, lib, FN_IDX_SET, true, 0, args, is_ref, true, None,
-                                    level,
+                                    None, state, lib, FN_IDX_SET, true, 0, args, is_ref, true,
+                                    None, pos, level,
                                 )
                                 .or_else(|err| match *err {
                                     // If there is no index setter, no need to set it back because the indexer is read-only
@@ -1183,8 +1722,8 @@ impl Engine {
                                     ];
 
                                     self.exec_fn_call(
-                                        state, lib, FN_IDX_SET, true, 0, args, is_ref, true, None,
-                                        level,
+                                        None, state, lib, FN_IDX_SET, true, 0, args, is_ref, true,
+                                        None, pos, level,
                                     )?;
                                 }
                                 // Error
@@ -1226,8 +1765,8 @@ impl Engine {
 
                                 // Map it to name(args) in function-call style
                                 self.exec_fn_call(
-                                    state, lib, fn_name, *native, hash, args, false, false,
-                                    def_val, level,
+                                    None, state, lib, fn_name, *native, hash, args, false, false,
+                                    def_val, *pos, level,
                                 )
                             } else {
                                 let redirected: Option<ImmutableString>;
@@ -1254,8 +1793,8 @@ impl Engine {
                                 let args = arg_values.as_mut();
 
                                 self.exec_fn_call(
-                                    state, lib, fn_name, *native, hash, args, is_ref, true,
-                                    def_val, level,
+                                    None, state, lib, fn_name, *native, hash, args, is_ref, true,
+                                    def_val, *pos, level,
                                 )
                             }
                             .map_err(|err| err.new_position(*pos))?
@@ -1296,7 +1835,8 @@ impl Engine {
                         let ((_, _, setter), pos) = x.as_ref();
                         let mut args = [target.as_mut(), new_val.as_mut().unwrap()];
                         self.exec_fn_call(
-                            state, lib, setter, true, 0, &mut args, is_ref, true, None, level,
+                            None, state, lib, setter, true, 0, &mut args, is_ref, true, None, *pos,
+                            level,
                         )
                         .map(|(v, _)| (v, true))
                         .map_err(|err| err.new_position(*pos))
@@ -1306,7 +1846,8 @@ impl Engine {
                         let ((_, getter, _), pos) = x.as_ref();
                         let mut args = [target.as_mut()];
                         self.exec_fn_call(
-                            state, lib, getter, true, 0, &mut args, is_ref, true, None, level,
+                            None, state, lib, getter, true, 0, &mut args, is_ref, true, None, *pos,
+                            level,
                         )
                         .map(|(v, _)| (v, false))
                         .map_err(|err| err.new_position(*pos))
@@ -1338,7 +1879,8 @@ impl Engine {
                             let ((_, getter, _), _) = p.as_ref();
                             let args = &mut args[..1];
                             self.exec_fn_call(
-                                state, lib, getter, true, 0, args, is_ref, true, None, level,
+                                None, state, lib, getter, true, 0, args, is_ref, true, None, *pos,
+                                level,
                             )
                             .map_err(|err| err.new_position(*pos))?
                         } else {
@@ -1361,7 +1903,8 @@ impl Engine {
                                 // Re-use args because the first &mut parameter will not be consumed
                                 args[1] = val;
                                 self.exec_fn_call(
-                                    state, lib, setter, true, 0, args, is_ref, true, None, level,
+                                    None, state, lib, setter, true, 0, args, is_ref, true, None,
+                                    *pos, level,
                                 )
                                 .or_else(|err| match *err {
                                     // If there is no setter, no need to feed it back because the property is read-only
@@ -1411,13 +1954,21 @@ impl Engine {
 
         match dot_lhs {
             // id.??? or id[???]
-            Expr::Variable(x) => {
+            // Qualified variables are constants, so they are only searched here for assignments
+            Expr::Variable(x) if x.1.is_none() || new_val.is_some() => {
                 let (var_name, var_pos) = &x.0;
 
                 self.inc_operations(state)
                     .map_err(|err| err.new_position(*var_pos))?;
 
-                let (target, _, typ, pos) = search_scope(scope, mods, state, this_ptr, dot_lhs)?;
+                let (mut target, _, typ, pos) = search_scope(
+                    scope,
+                    mods,
+                    state,
+                    &self.global_constants,
+                    this_ptr,
+                    dot_lhs,
+                )?;
 
                 // Constants cannot be modified
                 match typ {
@@ -1430,9 +1981,16 @@ impl Engine {
                     ScopeEntryType::Constant | ScopeEntryType::Normal => (),
                 }
 
-                let obj_ptr = &mut target.into();
                 self.eval_dot_index_chain_helper(
-                    state, lib, &mut None, obj_ptr, dot_rhs, idx_values, chain_type, level, new_val,
+                    state,
+                    lib,
+                    &mut None,
+                    &mut target,
+                    dot_rhs,
+                    idx_values,
+                    chain_type,
+                    level,
+                    new_val,
                 )
                 .map(|(v, _)| v)
                 .map_err(|err| err.new_position(*op_pos))
@@ -1530,7 +2088,7 @@ impl Engine {
 
         match val {
             #[cfg(not(feature = "no_index"))]
-            Dynamic(Union::Array(arr)) => {
+            Dynamic(Union::Array(arr), _) => {
                 // val_array[idx]
                 let index = idx
                     .as_int()
@@ -1538,8 +2096,15 @@ impl Engine {
 
                 let arr_len = arr.len();
 
-                if index >= 0 {
-                    arr.get_mut(index as usize)
+                // Negative index counts from the end
+                let offset = if index >= 0 {
+                    index
+                } else {
+                    arr_len as INT + index
+                };
+
+                if offset >= 0 {
+                    arr.get_mut(offset as usize)
                         .map(Target::from)
                         .ok_or_else(|| {
                             Box::new(EvalAltResult::ErrorArrayBounds(arr_len, index, idx_pos))
@@ -1552,35 +2117,49 @@ impl Engine {
             }
 
             #[cfg(not(feature = "no_object"))]
-            Dynamic(Union::Map(map)) => {
+            Dynamic(Union::Map(map), _) => {
                 // val_map[idx]
                 Ok(if create {
                     let index = idx
                         .take_immutable_string()
                         .map_err(|_| EvalAltResult::ErrorStringIndexExpr(idx_pos))?;
 
-                    map.entry(index).or_insert(Default::default()).into()
+                    map.get_mut_or_insert(index).into()
                 } else {
                     let index = idx
                         .downcast_ref::<String>()
                         .ok_or_else(|| EvalAltResult::ErrorStringIndexExpr(idx_pos))?;
 
-                    map.get_mut(index.as_str())
-                        .map(Target::from)
-                        .unwrap_or_else(|| Target::from(()))
+                    match map.get_mut(index.as_str()) {
+                        Some(value) => Target::from(value),
+                        None if self.fail_on_invalid_map_property => {
+                            return Err(Box::new(EvalAltResult::ErrorDotExpr(
+                                format!("- property '{}' not found", index),
+                                idx_pos,
+                            )))
+                        }
+                        None => Target::from(()),
+                    }
                 })
             }
 
             #[cfg(not(feature = "no_index"))]
-            Dynamic(Union::Str(s)) => {
+            Dynamic(Union::Str(s), _) => {
                 // val_string[idx]
                 let chars_len = s.chars().count();
                 let index = idx
                     .as_int()
                     .map_err(|_| EvalAltResult::ErrorNumericIndexExpr(idx_pos))?;
 
-                if index >= 0 {
-                    let offset = index as usize;
+                // Negative index counts from the end
+                let offset = if index >= 0 {
+                    index
+                } else {
+                    chars_len as INT + index
+                };
+
+                if offset >= 0 {
+                    let offset = offset as usize;
                     let ch = s.chars().nth(offset).ok_or_else(|| {
                         Box::new(EvalAltResult::ErrorStringBounds(chars_len, index, idx_pos))
                     })?;
@@ -1597,7 +2176,7 @@ impl Engine {
                 let type_name = self.map_type_name(val.type_name());
                 let args = &mut [val, &mut idx];
                 self.exec_fn_call(
-                    state, lib, FN_IDX_GET, true, 0, args, is_ref, true, None, level,
+                    None, state, lib, FN_IDX_GET, true, 0, args, is_ref, true, None, idx_pos, level,
                 )
                 .map(|(v, _)| v.into())
                 .map_err(|_| {
@@ -1636,10 +2215,9 @@ impl Engine {
 
         match rhs_value {
             #[cfg(not(feature = "no_index"))]
-            Dynamic(Union::Array(mut rhs_value)) => {
+            Dynamic(Union::Array(mut rhs_value), _) => {
                 let op = "==";
                 let def_value = false.into();
-                let mut scope = Scope::new();
 
                 // Call the `==` operator to compare each value
                 for value in rhs_value.iter_mut() {
@@ -1652,12 +2230,34 @@ impl Engine {
                         0,
                     );
 
-                    let (r, _) = self
-                        .call_fn_raw(
-                            &mut scope, mods, state, lib, op, hashes, args, false, false,
-                            def_value, level,
-                        )
-                        .map_err(|err| err.new_position(rhs.position()))?;
+                    // Values of the same custom type without a registered `==` are never equal,
+                    // rather than an error as when compared directly
+                    let is_same_custom_type =
+                        args[0].is_variant() && args[0].type_id() == args[1].type_id();
+
+                    let r = match self.call_fn_raw(
+                        None,
+                        mods,
+                        state,
+                        lib,
+                        op,
+                        hashes,
+                        args,
+                        false,
+                        false,
+                        def_value,
+                        rhs.position(),
+                        level,
+                    ) {
+                        Ok((r, _)) => r,
+                        Err(err)
+                            if is_same_custom_type
+                                && matches!(*err, EvalAltResult::ErrorFunctionNotFound(_, _)) =>
+                        {
+                            false.into()
+                        }
+                        Err(err) => return Err(err.new_position(rhs.position())),
+                    };
                     if r.as_bool().unwrap_or(false) {
                         return Ok(true.into());
                     }
@@ -1666,18 +2266,18 @@ impl Engine {
                 Ok(false.into())
             }
             #[cfg(not(feature = "no_object"))]
-            Dynamic(Union::Map(rhs_value)) => match lhs_value {
+            Dynamic(Union::Map(rhs_value), _) => match lhs_value {
                 // Only allows String or char
-                Dynamic(Union::Str(s)) => Ok(rhs_value.contains_key(s.as_str()).into()),
-                Dynamic(Union::Char(c)) => {
+                Dynamic(Union::Str(s), _) => Ok(rhs_value.contains_key(s.as_str()).into()),
+                Dynamic(Union::Char(c), _) => {
                     Ok(rhs_value.contains_key(c.to_string().as_str()).into())
                 }
                 _ => Err(Box::new(EvalAltResult::ErrorInExpr(lhs.position()))),
             },
-            Dynamic(Union::Str(rhs_value)) => match lhs_value {
+            Dynamic(Union::Str(rhs_value), _) => match lhs_value {
                 // Only allows String or char
-                Dynamic(Union::Str(s)) => Ok(rhs_value.contains(s.as_str()).into()),
-                Dynamic(Union::Char(c)) => Ok(rhs_value.contains(c).into()),
+                Dynamic(Union::Str(s), _) => Ok(rhs_value.contains(s.as_str()).into()),
+                Dynamic(Union::Char(c), _) => Ok(rhs_value.contains(c).into()),
                 _ => Err(Box::new(EvalAltResult::ErrorInExpr(lhs.position()))),
             },
             _ => Err(Box::new(EvalAltResult::ErrorInExpr(rhs.position()))),
@@ -1685,7 +2285,7 @@ impl Engine {
     }
 
     /// Evaluate an expression
-    fn eval_expr(
+    pub(crate) fn eval_expr(
         &self,
         scope: &mut Scope,
         mods: &mut Imports,
@@ -1704,6 +2304,8 @@ impl Engine {
             Expr::IntegerConstant(x) => Ok(x.0.into()),
             #[cfg(not(feature = "no_float"))]
             Expr::FloatConstant(x) => Ok(x.0.into()),
+            #[cfg(feature = "decimal")]
+            Expr::DecimalConstant(x) => Ok(x.0.into()),
             Expr::StringConstant(x) => Ok(x.0.to_string().into()),
             Expr::CharConstant(x) => Ok(x.0.into()),
             Expr::Variable(x) if (x.0).0 == KEYWORD_THIS => {
@@ -1713,9 +2315,29 @@ impl Engine {
                     Err(Box::new(EvalAltResult::ErrorUnboundedThis((x.0).1)))
                 }
             }
-            Expr::Variable(_) => {
-                let (val, _, _, _) = search_scope(scope, mods, state, this_ptr, expr)?;
-                Ok(val.clone())
+            Expr::Variable(x) => {
+                match search_scope(scope, mods, state, &self.global_constants, this_ptr, expr) {
+                    Ok((val, _, _, _)) => Ok(val.clone_into_dynamic()),
+                    // Qualified variable not in any imported module - try the static modules
+                    Err(err) if matches!(*err, EvalAltResult::ErrorModuleNotFound(_, _)) => {
+                        let ((name, pos), modules, hash_var, _, _) = x.as_ref();
+                        let modules = modules.as_ref().unwrap();
+                        let (root, _) = modules.get(0);
+
+                        self.static_modules
+                            .get(root)
+                            .ok_or(err)?
+                            .get_qualified_var(*hash_var)
+                            .cloned()
+                            .ok_or_else(|| {
+                                Box::new(EvalAltResult::ErrorVariableNotFound(
+                                    format!("{}{}", modules, name),
+                                    *pos,
+                                ))
+                            })
+                    }
+                    Err(err) => Err(err),
+                }
             }
             Expr::Property(_) => unreachable!(),
 
@@ -1727,8 +2349,15 @@ impl Engine {
                 let (lhs_expr, op, rhs_expr, op_pos) = x.as_ref();
                 let mut rhs_val =
                     self.eval_expr(scope, mods, state, lib, this_ptr, rhs_expr, level)?;
-                let (lhs_ptr, name, typ, pos) =
-                    search_scope(scope, mods, state, this_ptr, lhs_expr)?;
+                let (mut target, name, typ, pos) = search_scope(
+                    scope,
+                    mods,
+                    state,
+                    &self.global_constants,
+                    this_ptr,
+                    lhs_expr,
+                )?;
+                let lhs_ptr = target.as_mut();
                 self.inc_operations(state)
                     .map_err(|err| err.new_position(pos))?;
 
@@ -1758,8 +2387,20 @@ impl Engine {
                             .or_else(|| self.packages.get_fn(hash_fn))
                         {
                             // Overriding exact implementation
-                            func(self, &mut [lhs_ptr, &mut rhs_val])?;
-                        } else if run_builtin_op_assignment(op, lhs_ptr, &rhs_val)?.is_none() {
+                            let context = NativeCallContext::new(
+                                self,
+                                lib,
+                                None,
+                                *op_pos,
+                                level,
+                                &mut state.operations,
+                            );
+                            func(context, &mut [lhs_ptr, &mut rhs_val])
+                                .map_err(|err| err.new_position(*op_pos))?;
+                        } else if run_builtin_op_assignment(op, lhs_ptr, &rhs_val, state.unchecked)
+                            .map_err(|err| err.new_position(*op_pos))?
+                            .is_none()
+                        {
                             // Not built in, map to `var = var op rhs`
                             let op = &op[..op.len() - 1]; // extract operator without =
                             let hash = calc_fn_hash(empty(), op, 2, empty());
@@ -1768,7 +2409,8 @@ impl Engine {
                             // Set variable value
                             *lhs_ptr = self
                                 .exec_fn_call(
-                                    state, lib, op, true, hash, args, false, false, None, level,
+                                    None, state, lib, op, true, hash, args, false, false, None,
+                                    *op_pos, level,
                                 )
                                 .map(|(v, _)| v)
                                 .map_err(|err| err.new_position(*op_pos))?;
@@ -1795,9 +2437,11 @@ impl Engine {
                         &mut self.eval_expr(scope, mods, state, lib, this_ptr, lhs_expr, level)?,
                         &mut rhs_val,
                     ];
-                    self.exec_fn_call(state, lib, op, true, hash, args, false, false, None, level)
-                        .map(|(v, _)| v)
-                        .map_err(|err| err.new_position(*op_pos))?
+                    self.exec_fn_call(
+                        None, state, lib, op, true, hash, args, false, false, None, *op_pos, level,
+                    )
+                    .map(|(v, _)| v)
+                    .map_err(|err| err.new_position(*op_pos))?
                 });
 
                 match lhs_expr {
@@ -1840,21 +2484,27 @@ impl Engine {
             }
 
             #[cfg(not(feature = "no_index"))]
-            Expr::Array(x) => Ok(Dynamic(Union::Array(Box::new(
-                x.0.iter()
-                    .map(|item| self.eval_expr(scope, mods, state, lib, this_ptr, item, level))
-                    .collect::<Result<Vec<_>, _>>()?,
-            )))),
+            Expr::Array(x) => Ok(Dynamic(
+                Union::Array(Box::new(
+                    x.0.iter()
+                        .map(|item| self.eval_expr(scope, mods, state, lib, this_ptr, item, level))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                DEFAULT_TAG,
+            )),
 
             #[cfg(not(feature = "no_object"))]
-            Expr::Map(x) => Ok(Dynamic(Union::Map(Box::new(
-                x.0.iter()
-                    .map(|((key, _), expr)| {
-                        self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)
-                            .map(|val| (key.clone(), val))
-                    })
-                    .collect::<Result<HashMap<_, _>, _>>()?,
-            )))),
+            Expr::Map(x) => Ok(Dynamic(
+                Union::Map(Box::new(
+                    x.0.iter()
+                        .map(|((key, _), expr)| {
+                            self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)
+                                .map(|val| (key.clone(), val))
+                        })
+                        .collect::<Result<Map, _>>()?,
+                )),
+                DEFAULT_TAG,
+            )),
 
             // Normal function call
             Expr::FnCall(x) if x.1.is_none() => {
@@ -1919,19 +2569,15 @@ impl Engine {
                 }
 
                 // Normal function call - except for Fn and eval (handled above)
-                let mut arg_values: StaticVec<Dynamic>;
-                let mut args: StaticVec<_>;
-                let mut is_ref = false;
+                let mut arg_values: StaticVec<Dynamic> = Default::default();
+                let mut first_arg = None;
 
-                if args_expr.is_empty() {
-                    // No arguments
-                    args = Default::default();
-                } else {
+                if !args_expr.is_empty() {
                     // See if the first argument is a variable, if so, convert to method-call style
                     // in order to leverage potential &mut first argument and avoid cloning the value
                     match args_expr.get(0) {
                         // func(x, ...) -> x.func(...)
-                        lhs @ Expr::Variable(_) => {
+                        lhs @ Expr::Variable(v) if v.1.is_none() => {
                             arg_values = args_expr
                                 .iter()
                                 .skip(1)
@@ -1940,15 +2586,21 @@ impl Engine {
                                 })
                                 .collect::<Result<_, _>>()?;
 
-                            let (target, _, _, pos) =
-                                search_scope(scope, mods, state, this_ptr, lhs)?;
+                            let (mut target, _, _, pos) = search_scope(
+                                scope,
+                                mods,
+                                state,
+                                &self.global_constants,
+                                this_ptr,
+                                lhs,
+                            )?;
 
                             self.inc_operations(state)
                                 .map_err(|err| err.new_position(pos))?;
 
-                            args = once(target).chain(arg_values.iter_mut()).collect();
-
-                            is_ref = true;
+                            // Move the value out of the variable, so that the scope can be passed
+                            // to the function alongside it - it is put back after the call
+                            first_arg = Some((lhs, mem::take(target.as_mut())));
                         }
                         // func(..., ...)
                         _ => {
@@ -1958,18 +2610,44 @@ impl Engine {
                                     self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)
                                 })
                                 .collect::<Result<_, _>>()?;
-
-                            args = arg_values.iter_mut().collect();
                         }
                     }
                 }
 
-                let args = args.as_mut();
-                self.exec_fn_call(
-                    state, lib, name, *native, *hash, args, is_ref, false, def_val, level,
-                )
-                .map(|(v, _)| v)
-                .map_err(|err| err.new_position(*pos))
+                let is_ref = first_arg.is_some();
+
+                let result = {
+                    let mut args: StaticVec<_> = match first_arg {
+                        Some((_, ref mut value)) => {
+                            once(value).chain(arg_values.iter_mut()).collect()
+                        }
+                        None => arg_values.iter_mut().collect(),
+                    };
+
+                    self.exec_fn_call(
+                        Some(scope),
+                        state,
+                        lib,
+                        name,
+                        *native,
+                        *hash,
+                        args.as_mut(),
+                        is_ref,
+                        false,
+                        def_val,
+                        *pos,
+                        level,
+                    )
+                };
+
+                // Put the first argument back into its variable
+                if let Some((lhs, value)) = first_arg {
+                    let (mut target, _, _, _) =
+                        search_scope(scope, mods, state, &self.global_constants, this_ptr, lhs)?;
+                    *target.as_mut() = value;
+                }
+
+                result.map(|(v, _)| v).map_err(|err| err.new_position(*pos))
             }
 
             // Module-qualified function call
@@ -1984,7 +2662,11 @@ impl Engine {
 
                 let mut args: StaticVec<_> = arg_values.iter_mut().collect();
 
-                let module = search_imports(mods, state, modules)?;
+                // Imported modules take precedence over static modules
+                let module: &Module = match search_imports(mods, state, modules) {
+                    Ok(module) => module,
+                    Err(err) => self.static_modules.get(&modules.get(0).0).ok_or(err)?,
+                };
 
                 // First search in script-defined functions (can override built-in)
                 let func = match module.get_qualified_fn(*hash_script) {
@@ -2020,7 +2702,16 @@ impl Engine {
                         .map_err(|err| err.new_position(*pos))
                     }
                     Ok(f) => {
-                        f.get_native_fn()(self, args.as_mut()).map_err(|err| err.new_position(*pos))
+                        let context = NativeCallContext::new(
+                            self,
+                            lib,
+                            Some(scope),
+                            *pos,
+                            level,
+                            &mut state.operations,
+                        );
+                        f.get_native_fn()(context, args.as_mut())
+                            .map_err(|err| err.new_position(*pos))
                     }
                     Err(err) => match *err {
                         EvalAltResult::ErrorFunctionNotFound(_, _) if def_val.is_some() => {
@@ -2039,6 +2730,51 @@ impl Engine {
 
             Expr::In(x) => self.eval_in_expr(scope, mods, state, lib, this_ptr, &x.0, &x.1, level),
 
+            // lhs < expr < expr ... - each operand is evaluated at most once, left to right,
+            // stopping at the first comparison that is false
+            Expr::CmpChain(x) => {
+                let (operands, ops) = x.as_ref();
+                let def_val: Dynamic = false.into();
+                let mut lhs =
+                    self.eval_expr(scope, mods, state, lib, this_ptr, &operands[0], level)?;
+
+                for ((op, hash, pos), expr) in ops.iter().zip(operands.iter().skip(1)) {
+                    let mut rhs = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
+                    let args = &mut [&mut lhs, &mut rhs];
+
+                    let result = self
+                        .exec_fn_call(
+                            None,
+                            state,
+                            lib,
+                            op,
+                            true,
+                            *hash,
+                            args,
+                            false,
+                            false,
+                            Some(&def_val),
+                            *pos,
+                            level,
+                        )
+                        .map(|(v, _)| v)
+                        .map_err(|err| err.new_position(*pos))?;
+
+                    match result.as_bool() {
+                        Ok(true) => lhs = rhs,
+                        Ok(false) => return Ok(false.into()),
+                        Err(typ) => {
+                            return Err(Box::new(EvalAltResult::ErrorMismatchOutputType(
+                                self.map_type_name(typ).into(),
+                                *pos,
+                            )))
+                        }
+                    }
+                }
+
+                Ok(true.into())
+            }
+
             Expr::And(x) => {
                 let (lhs, rhs, _) = x.as_ref();
                 Ok((self
@@ -2079,6 +2815,29 @@ impl Engine {
             Expr::False(_) => Ok(false.into()),
             Expr::Unit(_) => Ok(().into()),
 
+            Expr::Custom(x) => {
+                let (custom, _) = x.as_ref();
+                let inputs: StaticVec<Expression> =
+                    custom.inputs().iter().map(Expression::from).collect();
+                let prev_scope_len = scope.len();
+
+                let mut context = EvalContext {
+                    mods,
+                    state,
+                    lib,
+                    this_ptr,
+                    level,
+                };
+                let result = (custom.func())(self, &mut context, scope, inputs.as_ref());
+
+                // Variables defined by the custom syntax mis-align the offsets of subsequent accesses
+                if scope.len() != prev_scope_len {
+                    state.always_search = true;
+                }
+
+                result
+            }
+
             _ => unreachable!(),
         };
 
@@ -2115,6 +2874,15 @@ impl Engine {
                 })
             }
 
+            // #[unchecked] block
+            Stmt::Unchecked(x) => {
+                let orig_unchecked = state.unchecked;
+                state.unchecked = true;
+                let result = self.eval_stmt(scope, mods, state, lib, this_ptr, &x.0, level);
+                state.unchecked = orig_unchecked;
+                result
+            }
+
             // Block scope
             Stmt::Block(x) => {
                 let prev_scope_len = scope.len();
@@ -2136,6 +2904,27 @@ impl Engine {
                 result
             }
 
+            // Try-finally statement
+            Stmt::TryFinally(x) => {
+                let (body, finally_block, _) = x.as_ref();
+                let prev_scope_len = scope.len();
+                let prev_mods_len = mods.len();
+                let prev_scope_level = state.scope_level;
+
+                let result = self.eval_stmt(scope, mods, state, lib, this_ptr, body, level);
+
+                // An error may leave the scope unbalanced - restore it before running the cleanup
+                scope.rewind(prev_scope_len);
+                mods.truncate(prev_mods_len);
+                state.scope_level = prev_scope_level;
+
+                // The finally block always runs, before any error (or return, break etc.) propagates.
+                // An error in the finally block takes precedence.
+                self.eval_stmt(scope, mods, state, lib, this_ptr, finally_block, level)?;
+
+                result
+            }
+
             // If-else statement
             Stmt::IfThenElse(x) => {
                 let (expr, if_block, else_block) = x.as_ref();
@@ -2181,6 +2970,41 @@ impl Engine {
                 }
             },
 
+            // While-let loop - runs until the expression yields ()
+            Stmt::WhileLet(x) => {
+                let ((name, _), expr, body, hash) = x.as_ref();
+                let prev_scope_len = scope.len();
+
+                state.scope_level += 1;
+
+                loop {
+                    let value = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
+
+                    if value.is::<()>() {
+                        break;
+                    }
+
+                    // Bind a fresh loop variable for each iteration
+                    let var_name = unsafe_cast_var_name_to_lifetime(name, state);
+                    scope.push_with_hash(var_name, *hash, ScopeEntryType::Normal, value, false);
+
+                    let result = self.eval_stmt(scope, mods, state, lib, this_ptr, body, level);
+                    scope.rewind(prev_scope_len);
+
+                    match result {
+                        Ok(_) => (),
+                        Err(err) => match *err {
+                            EvalAltResult::ErrorLoopBreak(false, _) => (),
+                            EvalAltResult::ErrorLoopBreak(true, _) => break,
+                            _ => return Err(err),
+                        },
+                    }
+                }
+
+                state.scope_level -= 1;
+                Ok(Default::default())
+            }
+
             // Loop statement
             Stmt::Loop(body) => loop {
                 match self.eval_stmt(scope, mods, state, lib, this_ptr, body, level) {
@@ -2195,23 +3019,90 @@ impl Engine {
 
             // For loop
             Stmt::For(x) => {
-                let (name, expr, stmt) = x.as_ref();
+                let ((name, counter), expr, stmt, (hash, counter_hash)) = x.as_ref();
                 let iter_type = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
                 let tid = iter_type.type_id();
 
-                if let Some(func) = self
-                    .global_module
-                    .get_iter(tid)
-                    .or_else(|| self.packages.get_iter(tid))
+                // Fast path for integer ranges (unless the iterator is overridden) -
+                // the loop variable is updated in place, skipping the boxed iterator
+                let mut range = if tid == TypeId::of::<Range<INT>>()
+                    && self.global_module.get_iter(tid).is_none()
                 {
+                    iter_type.downcast_ref::<Range<INT>>().cloned()
+                } else {
+                    None
+                };
+
+                // Each item is a pair of (counter, value)
+                let iter: Option<Box<dyn Iterator<Item = (Dynamic, Dynamic)>>> = match iter_type {
+                    _ if range.is_some() => None,
+                    // for (key, value) in map
+                    #[cfg(not(feature = "no_object"))]
+                    Dynamic(Union::Map(map), _) if counter.is_some() => Some(Box::new(
+                        map.into_iter().map(|(key, value)| (key.into(), value)),
+                    )),
+                    _ => self
+                        .global_module
+                        .get_iter(tid)
+                        .or_else(|| self.packages.get_iter(tid))
+                        .map(|func| {
+                            Box::new(
+                                func(iter_type)
+                                    .enumerate()
+                                    .map(|(i, value)| ((i as INT).into(), value)),
+                            ) as Box<dyn Iterator<Item = _>>
+                        }),
+                };
+
+                if range.is_some() || iter.is_some() {
+                    let mut iter = iter;
+                    let mut range_counter: INT = 0;
+                    let prev_scope_len = scope.len();
+
                     // Add the loop variable
                     let var_name = unsafe_cast_var_name_to_lifetime(name, &state);
-                    scope.push(var_name, ());
+                    scope.push_with_hash(var_name, *hash, ScopeEntryType::Normal, ().into(), false);
                     let index = scope.len() - 1;
+
+                    // Add the counter variable (if any)
+                    let counter_index =
+                        counter.as_ref().zip(*counter_hash).map(|(counter, hash)| {
+                            let var_name = unsafe_cast_var_name_to_lifetime(counter, state);
+                            let value = (0 as INT).into();
+                            scope.push_with_hash(
+                                var_name,
+                                hash,
+                                ScopeEntryType::Normal,
+                                value,
+                                false,
+                            );
+                            scope.len() - 1
+                        });
+
                     state.scope_level += 1;
 
-                    for loop_var in func(iter_type) {
-                        *scope.get_mut(index).0 = loop_var;
+                    loop {
+                        if let Some(range) = range.as_mut() {
+                            match range.next() {
+                                Some(value) => set_int_in_place(scope.get_mut(index).0, value),
+                                None => break,
+                            }
+                            if let Some(counter_index) = counter_index {
+                                set_int_in_place(scope.get_mut(counter_index).0, range_counter);
+                                range_counter += 1;
+                            }
+                        } else {
+                            match iter.as_mut().unwrap().next() {
+                                Some((counter_var, loop_var)) => {
+                                    *scope.get_mut(index).0 = loop_var;
+                                    if let Some(counter_index) = counter_index {
+                                        *scope.get_mut(counter_index).0 = counter_var;
+                                    }
+                                }
+                                None => break,
+                            }
+                        }
+
                         self.inc_operations(state)
                             .map_err(|err| err.new_position(stmt.position()))?;
 
@@ -2225,7 +3116,7 @@ impl Engine {
                         }
                     }
 
-                    scope.rewind(scope.len() - 1);
+                    scope.rewind(prev_scope_len);
                     state.scope_level -= 1;
                     Ok(Default::default())
                 } else {
@@ -2286,7 +3177,7 @@ impl Engine {
 
             // Let statement
             Stmt::Let(x) if x.1.is_some() => {
-                let ((var_name, _), expr) = x.as_ref();
+                let ((var_name, _), expr, hash) = x.as_ref();
                 let val = self.eval_expr(
                     scope,
                     mods,
@@ -2297,23 +3188,58 @@ impl Engine {
                     level,
                 )?;
                 let var_name = unsafe_cast_var_name_to_lifetime(var_name, &state);
-                scope.push_dynamic_value(var_name, ScopeEntryType::Normal, val, false);
+                scope.push_with_hash(var_name, *hash, ScopeEntryType::Normal, val, false);
                 Ok(Default::default())
             }
 
             Stmt::Let(x) => {
-                let ((var_name, _), _) = x.as_ref();
+                let ((var_name, _), _, hash) = x.as_ref();
                 let var_name = unsafe_cast_var_name_to_lifetime(var_name, &state);
-                scope.push(var_name, ());
+                scope.push_with_hash(var_name, *hash, ScopeEntryType::Normal, ().into(), false);
+                Ok(Default::default())
+            }
+
+            // Destructuring let statement
+            #[cfg(not(feature = "no_index"))]
+            Stmt::LetTuple(x) => {
+                let (names, expr, _) = x.as_ref();
+                let val = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
+                let type_name = self.map_type_name(val.type_name()).to_string();
+
+                let values = val.try_cast::<Array>().ok_or_else(|| {
+                    Box::new(EvalAltResult::ErrorMismatchOutputType(
+                        type_name,
+                        expr.position(),
+                    ))
+                })?;
+
+                if values.len() != names.len() {
+                    return Err(Box::new(EvalAltResult::ErrorRuntime(
+                        format!(
+                            "Cannot unpack an array of {} element(s) into {} variable(s)",
+                            values.len(),
+                            names.len()
+                        ),
+                        expr.position(),
+                    )));
+                }
+
+                for ((var_name, _, hash), value) in names.iter().zip(values) {
+                    let var_name = unsafe_cast_var_name_to_lifetime(var_name, state);
+                    scope.push_with_hash(var_name, *hash, ScopeEntryType::Normal, value, false);
+                }
                 Ok(Default::default())
             }
 
+            #[cfg(feature = "no_index")]
+            Stmt::LetTuple(_) => unreachable!(),
+
             // Const statement
             Stmt::Const(x) if x.1.is_constant() => {
-                let ((var_name, _), expr) = x.as_ref();
+                let ((var_name, _), expr, hash) = x.as_ref();
                 let val = self.eval_expr(scope, mods, state, lib, this_ptr, &expr, level)?;
                 let var_name = unsafe_cast_var_name_to_lifetime(var_name, &state);
-                scope.push_dynamic_value(var_name, ScopeEntryType::Constant, val, true);
+                scope.push_with_hash(var_name, *hash, ScopeEntryType::Constant, val, true);
                 Ok(Default::default())
             }
 
@@ -2335,20 +3261,31 @@ impl Engine {
                 {
                     #[cfg(not(feature = "no_module"))]
                     {
-                        if let Some(resolver) = &self.module_resolver {
-                            let mut module = resolver.resolve(self, &path, expr.position())?;
-                            module.index_all_sub_modules();
-                            mods.push((name.clone().into(), module));
-
-                            state.modules += 1;
+                        // Embedded modules take precedence over the module resolver
+                        let embedded = match &state.resolver {
+                            Some(resolver) if resolver.contains_path(&path) => {
+                                Some(resolver.resolve(self, &path, expr.position())?)
+                            }
+                            _ => None,
+                        };
 
-                            Ok(Default::default())
+                        let mut module = if let Some(module) = embedded {
+                            module
+                        } else if let Some(resolver) = &self.module_resolver {
+                            resolver.resolve(self, &path, expr.position())?
                         } else {
-                            Err(Box::new(EvalAltResult::ErrorModuleNotFound(
+                            return Err(Box::new(EvalAltResult::ErrorModuleNotFound(
                                 path.to_string(),
                                 expr.position(),
-                            )))
-                        }
+                            )));
+                        };
+
+                        module.index_all_sub_modules();
+                        mods.push((name.clone().into(), module));
+
+                        state.modules += 1;
+
+                        Ok(Default::default())
                     }
 
                     #[cfg(feature = "no_module")]
@@ -2362,7 +3299,7 @@ impl Engine {
             Stmt::Export(list) => {
                 for ((id, id_pos), rename) in list.iter() {
                     // Mark scope variables as public
-                    if let Some(index) = scope.get_index(id).map(|(i, _)| i) {
+                    if let Some(index) = scope.get_index(id, calc_ident_hash(id)).map(|(i, _)| i) {
                         let alias = rename
                             .as_ref()
                             .map(|(n, _)| n.clone())
@@ -2402,12 +3339,12 @@ impl Engine {
         fn calc_size(value: &Dynamic) -> (usize, usize, usize) {
             match value {
                 #[cfg(not(feature = "no_index"))]
-                Dynamic(Union::Array(arr)) => {
+                Dynamic(Union::Array(arr), _) => {
                     let mut arrays = 0;
                     let mut maps = 0;
 
                     arr.iter().for_each(|value| match value {
-                        Dynamic(Union::Array(_)) | Dynamic(Union::Map(_)) => {
+                        Dynamic(Union::Array(_), _) | Dynamic(Union::Map(_), _) => {
                             let (a, m, _) = calc_size(value);
                             arrays += a;
                             maps += m;
@@ -2418,12 +3355,12 @@ impl Engine {
                     (arrays, maps, 0)
                 }
                 #[cfg(not(feature = "no_object"))]
-                Dynamic(Union::Map(map)) => {
+                Dynamic(Union::Map(map), _) => {
                     let mut arrays = 0;
                     let mut maps = 0;
 
                     map.values().for_each(|value| match value {
-                        Dynamic(Union::Array(_)) | Dynamic(Union::Map(_)) => {
+                        Dynamic(Union::Array(_), _) | Dynamic(Union::Map(_), _) => {
                             let (a, m, _) = calc_size(value);
                             arrays += a;
                             maps += m;
@@ -2433,7 +3370,7 @@ impl Engine {
 
                     (arrays, maps, 0)
                 }
-                Dynamic(Union::Str(s)) => (0, 0, s.len()),
+                Dynamic(Union::Str(s), _) => (0, 0, s.len()),
                 _ => (0, 0, 0),
             }
         }
@@ -2442,13 +3379,13 @@ impl Engine {
             // Simply return all errors
             Err(_) => return result,
             // String with limit
-            Ok(Dynamic(Union::Str(_))) if self.max_string_size > 0 => (),
+            Ok(Dynamic(Union::Str(_), _)) if self.max_string_size > 0 => (),
             // Array with limit
             #[cfg(not(feature = "no_index"))]
-            Ok(Dynamic(Union::Array(_))) if self.max_array_size > 0 => (),
+            Ok(Dynamic(Union::Array(_), _)) if self.max_array_size > 0 => (),
             // Map with limit
             #[cfg(not(feature = "no_object"))]
-            Ok(Dynamic(Union::Map(_))) if self.max_map_size > 0 => (),
+            Ok(Dynamic(Union::Map(_), _)) if self.max_map_size > 0 => (),
             // Everything else is simply returned
             Ok(_) => return result,
         };
@@ -2521,6 +3458,7 @@ fn run_builtin_binary_op(
     op: &str,
     x: &Dynamic,
     y: &Dynamic,
+    unchecked: bool,
 ) -> Result<Option<Dynamic>, Box<EvalAltResult>> {
     use crate::packages::arithmetic::*;
 
@@ -2534,6 +3472,13 @@ fn run_builtin_binary_op(
         let x = *x.downcast_ref::<INT>().unwrap();
         let y = *y.downcast_ref::<INT>().unwrap();
 
+        // Inside an `#[unchecked]` block, overflows wrap around
+        if unchecked {
+            if let Some(v) = wrapping_op(op, x, y) {
+                return Ok(Some(v.into()));
+            }
+        }
+
         #[cfg(not(feature = "unchecked"))]
         match op {
             "+" => return add(x, y).map(Into::into).map(Some),
@@ -2650,6 +3595,7 @@ fn run_builtin_op_assignment(
     op: &str,
     x: &mut Dynamic,
     y: &Dynamic,
+    unchecked: bool,
 ) -> Result<Option<()>, Box<EvalAltResult>> {
     use crate::packages::arithmetic::*;
 
@@ -2663,6 +3609,14 @@ fn run_builtin_op_assignment(
         let x = x.downcast_mut::<INT>().unwrap();
         let y = *y.downcast_ref::<INT>().unwrap();
 
+        // Inside an `#[unchecked]` block, overflows wrap around
+        if unchecked {
+            if let Some(v) = wrapping_op(&op[..op.len() - 1], *x, y) {
+                *x = v;
+                return Ok(Some(()));
+            }
+        }
+
         #[cfg(not(feature = "unchecked"))]
         match op {
             "+=" => return Ok(Some(*x = add(*x, y)?)),
@@ -1,5 +1,6 @@
 //! Module containing error definitions for the parsing process.
 
+use crate::parser::INT;
 use crate::result::EvalAltResult;
 use crate::token::Position;
 
@@ -25,6 +26,8 @@ pub enum LexError {
     MalformedEscapeSequence(String),
     /// An numeric literal is in an invalid format.
     MalformedNumber(String),
+    /// An integer literal does not fit into the range of `INT`.
+    IntegerOutOfRange(String),
     /// An character literal is in an invalid format.
     MalformedChar(String),
     /// An identifier is in an invalid format.
@@ -41,6 +44,13 @@ impl fmt::Display for LexError {
             Self::UnexpectedChar(c) => write!(f, "Unexpected '{}'", c),
             Self::MalformedEscapeSequence(s) => write!(f, "Invalid escape sequence: '{}'", s),
             Self::MalformedNumber(s) => write!(f, "Invalid number: '{}'", s),
+            Self::IntegerOutOfRange(s) => write!(
+                f,
+                "Integer literal is out of range: '{}' (must be between {} and {})",
+                s,
+                INT::MIN,
+                INT::MAX
+            ),
             Self::MalformedChar(s) => write!(f, "Invalid character: '{}'", s),
             Self::MalformedIdentifier(s) => write!(f, "Variable name is not proper: '{}'", s),
             Self::UnterminatedString => write!(f, "Open string is not terminated"),
@@ -77,6 +87,14 @@ pub enum ParseErrorType {
     UnknownOperator(String),
     /// Expecting a particular token but not finding one. Wrapped values are the token and description.
     MissingToken(String, String),
+    /// Expecting a particular token but finding another one instead.
+    /// Wrapped values are the token found, the token expected and description.
+    UnexpectedToken(String, String, String),
+    /// The script ends before a delimiter is closed.
+    /// Wrapped values are the opening delimiter and the position where the delimited construct starts.
+    UnclosedDelimiter(String, Position),
+    /// A string literal is not terminated before a new-line or EOF.
+    UnterminatedString,
     /// An expression in function call arguments `()` has syntax error. Wrapped value is the error description (if any).
     MalformedCallExpr(String),
     /// An expression in indexing brackets `[]` has syntax error. Wrapped value is the error description (if any).
@@ -143,6 +161,8 @@ pub enum ParseErrorType {
     LiteralTooLarge(String, usize),
     /// Break statement not inside a loop.
     LoopBreak,
+    /// A language construct that is disallowed by the `Engine`. Wrapped value is the construct.
+    ForbiddenSyntax(String),
 }
 
 impl ParseErrorType {
@@ -157,6 +177,9 @@ impl ParseErrorType {
             Self::UnexpectedEOF => "Script is incomplete",
             Self::UnknownOperator(_) => "Unknown operator",
             Self::MissingToken(_, _) => "Expecting a certain token that is missing",
+            Self::UnexpectedToken(_, _, _) => "Expecting a certain token but finding another",
+            Self::UnclosedDelimiter(_, _) => "Script ends before the closing delimiter",
+            Self::UnterminatedString => "Open string is not terminated",
             Self::MalformedCallExpr(_) => "Invalid expression in function call arguments",
             Self::MalformedIndexExpr(_) => "Invalid index in indexing expression",
             Self::MalformedInExpr(_) => "Invalid 'in' expression",
@@ -176,7 +199,8 @@ impl ParseErrorType {
             Self::AssignmentToConstant(_) => "Cannot assign to a constant value",
             Self::ExprTooDeep => "Expression exceeds maximum complexity",
             Self::LiteralTooLarge(_, _) => "Literal exceeds maximum limit",
-            Self::LoopBreak => "Break statement should only be used inside a loop"
+            Self::LoopBreak => "Break statement should only be used inside a loop",
+            Self::ForbiddenSyntax(_) => "Syntax is not allowed by the engine"
         }
     }
 }
@@ -222,11 +246,29 @@ impl fmt::Display for ParseErrorType {
 
             Self::MissingToken(token, s) => write!(f, "Expecting '{}' {}", token, s),
 
+            Self::UnexpectedToken(found, token, s) => {
+                write!(f, "Unexpected '{}': expecting '{}' {}", found, token, s)
+            }
+
+            Self::UnclosedDelimiter(delimiter, pos) => {
+                let closing = match delimiter.as_str() {
+                    "(" => ")",
+                    "[" => "]",
+                    _ => "}",
+                };
+                write!(
+                    f,
+                    "Expecting '{}' to close the '{}' opened at {}",
+                    closing, delimiter, pos
+                )
+            }
+
             Self::AssignmentToConstant(s) if s.is_empty() => write!(f, "{}", self.desc()),
             Self::AssignmentToConstant(s) => write!(f, "Cannot assign to constant '{}'", s),
             Self::LiteralTooLarge(typ, max) => {
                 write!(f, "{} exceeds the maximum limit ({})", typ, max)
             }
+            Self::ForbiddenSyntax(s) => write!(f, "{} is not allowed", s),
             _ => write!(f, "{}", self.desc()),
         }
     }
@@ -238,6 +280,7 @@ impl From<&LexError> for ParseErrorType {
             LexError::StringTooLong(max) => {
                 Self::LiteralTooLarge("Length of string literal".to_string(), *max)
             }
+            LexError::UnterminatedString => Self::UnterminatedString,
             _ => Self::BadInput(err.to_string()),
         }
     }
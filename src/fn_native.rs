@@ -1,10 +1,13 @@
 use crate::any::Dynamic;
 use crate::engine::Engine;
+use crate::module::Module;
 use crate::parser::ScriptFnDef;
 use crate::result::EvalAltResult;
+use crate::scope::Scope;
+use crate::token::Position;
 use crate::utils::ImmutableString;
 
-use crate::stdlib::{boxed::Box, fmt, rc::Rc, sync::Arc};
+use crate::stdlib::{boxed::Box, cell::Cell, fmt, rc::Rc, sync::Arc};
 
 /// Trait that maps to `Send + Sync` only under the `sync` feature.
 #[cfg(feature = "sync")]
@@ -54,6 +57,92 @@ pub fn shared_take<T: Clone>(value: Shared<T>) -> T {
 
 pub type FnCallArgs<'a> = [&'a mut Dynamic];
 
+/// Context of a native Rust function call.
+#[derive(Clone, Copy)]
+pub struct NativeCallContext<'a> {
+    /// The scripting `Engine` running the call.
+    engine: &'a Engine,
+    /// The functions library of the calling script.
+    lib: &'a Module,
+    /// The scope of the calling script, if available.
+    scope: Option<&'a Scope<'a>>,
+    /// Position of the call site.
+    pos: Position,
+    /// Current level of the call stack.
+    level: usize,
+    /// Number of operations performed by the calling script, including any callbacks.
+    operations: &'a Cell<u64>,
+}
+
+impl<'a> NativeCallContext<'a> {
+    /// Create a new `NativeCallContext`.
+    pub(crate) fn new(
+        engine: &'a Engine,
+        lib: &'a Module,
+        scope: Option<&'a Scope<'a>>,
+        pos: Position,
+        level: usize,
+        operations: &'a mut u64,
+    ) -> Self {
+        Self {
+            engine,
+            lib,
+            scope,
+            pos,
+            level,
+            operations: Cell::from_mut(operations),
+        }
+    }
+    /// The scripting `Engine` running the call.
+    pub fn engine(&self) -> &'a Engine {
+        self.engine
+    }
+    /// The functions library of the calling script.
+    pub(crate) fn lib(&self) -> &'a Module {
+        self.lib
+    }
+    /// The scope of the calling script, holding the variables visible at the call site.
+    ///
+    /// The scope is available when the function is called in function-call style (e.g. `foo(x)`).
+    /// It is `None` for method calls, operators, property access and indexing, as well as for calls
+    /// made from other native functions (e.g. via `call_fn_ptr`).
+    ///
+    /// If the first argument is a variable, it is passed by reference and reads as `()` in the scope
+    /// until the function returns.
+    pub fn scope(&self) -> Option<&'a Scope<'a>> {
+        self.scope
+    }
+    /// Position of the call site in the script.
+    ///
+    /// Use this to create errors with the correct position.
+    /// The position is `Position::none()` when the function is not called from a script.
+    pub fn position(&self) -> Position {
+        self.pos
+    }
+    /// Current level of the call stack.
+    pub(crate) fn level(&self) -> usize {
+        self.level
+    }
+    /// Number of operations performed by the calling script so far.
+    pub(crate) fn operations(&self) -> u64 {
+        self.operations.get()
+    }
+    /// Update the number of operations performed by the calling script.
+    pub(crate) fn set_operations(&self, operations: u64) {
+        self.operations.set(operations)
+    }
+    /// Call a function pointer with a list of arguments.
+    ///
+    /// Script-defined functions are searched in the functions library of the calling script.
+    pub fn call_fn_ptr(
+        &self,
+        fn_ptr: &FnPtr,
+        args: &mut [&mut Dynamic],
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        self.engine.call_fn_ptr(*self, fn_ptr, args)
+    }
+}
+
 /// A general function pointer.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
 pub struct FnPtr(ImmutableString);
@@ -87,11 +176,11 @@ impl<S: Into<ImmutableString>> From<S> for FnPtr {
 
 /// A general function trail object.
 #[cfg(not(feature = "sync"))]
-pub type FnAny = dyn Fn(&Engine, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>>;
+pub type FnAny = dyn Fn(NativeCallContext, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>>;
 /// A general function trail object.
 #[cfg(feature = "sync")]
 pub type FnAny =
-    dyn Fn(&Engine, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>> + Send + Sync;
+    dyn Fn(NativeCallContext, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>> + Send + Sync;
 
 /// A standard function that gets an iterator from a type.
 pub type IteratorFn = fn(Dynamic) -> Box<dyn Iterator<Item = Dynamic>>;
@@ -3,12 +3,49 @@
 
 use crate::any::{Dynamic, Variant};
 use crate::engine::Engine;
-use crate::fn_native::{CallableFunction, FnAny, FnCallArgs, SendSync};
-use crate::parser::FnAccess;
+use crate::fn_native::{CallableFunction, FnAny, FnCallArgs, FnPtr, NativeCallContext, SendSync};
+use crate::parser::{FnAccess, INT};
+use crate::r#unsafe::unsafe_try_cast;
 use crate::result::EvalAltResult;
+use crate::token::Position;
 use crate::utils::ImmutableString;
 
-use crate::stdlib::{any::TypeId, boxed::Box, mem};
+#[cfg(not(feature = "no_float"))]
+use crate::parser::FLOAT;
+
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
+#[cfg(not(feature = "no_index"))]
+use crate::engine::Array;
+
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+use crate::any::map_std_type_name;
+
+#[cfg(not(feature = "no_object"))]
+use crate::engine::Map;
+
+use crate::stdlib::{
+    any::{type_name, TypeId},
+    boxed::Box,
+    mem,
+    string::String,
+};
+
+#[cfg(not(feature = "no_index"))]
+use crate::stdlib::vec::Vec;
+
+#[cfg(not(feature = "sync"))]
+use crate::stdlib::cell::RefCell;
+
+#[cfg(feature = "sync")]
+use crate::stdlib::sync::Mutex;
+
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+use crate::stdlib::format;
+
+#[cfg(not(feature = "no_object"))]
+use crate::stdlib::{collections::HashMap, string::ToString};
 
 /// Trait to register custom functions with the `Engine`.
 pub trait RegisterFn<FN, ARGS, RET> {
@@ -72,6 +109,70 @@ pub trait RegisterResultFn<FN, ARGS> {
     fn register_result_fn(&mut self, name: &str, f: FN);
 }
 
+/// Trait to register fallible custom functions returning `Result<T, Box<EvalAltResult>>` with the `Engine`,
+/// where `T` is any type that can be returned from a function registered via `RegisterFn`.
+pub trait RegisterFallibleFn<FN, ARGS, RET> {
+    /// Register a custom fallible function with the `Engine`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rhai::{Engine, RegisterFallibleFn, EvalAltResult};
+    ///
+    /// // Fallible function returning nothing on success
+    /// fn check(x: i64) -> Result<(), Box<EvalAltResult>> {
+    ///     if x < 0 {
+    ///         // '.into()' automatically converts to 'Box<EvalAltResult::ErrorRuntime>'
+    ///         Err("negative number!".into())
+    ///     } else {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// // You must use the trait rhai::RegisterFallibleFn to get this method.
+    /// engine.register_fallible_fn("check", check);
+    ///
+    /// engine.consume("check(42)").unwrap();
+    ///
+    /// engine.consume("check(-1)")
+    ///         .expect_err("expecting negative number error!");
+    /// ```
+    fn register_fallible_fn(&mut self, name: &str, f: FN);
+}
+
+/// Trait to register custom functions that mutate their own captured state (i.e. `FnMut` closures)
+/// with the `Engine`.
+pub trait RegisterFnMut<FN, ARGS, RET> {
+    /// Register a custom `FnMut` function with the `Engine`.
+    ///
+    /// The closure is locked for the duration of each call - a `RefCell`, or a `Mutex` under the
+    /// `sync` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, RegisterFnMut};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// let mut count = 0_i64;
+    ///
+    /// // You must use the trait rhai::RegisterFnMut to get this method.
+    /// engine.register_fn_mut("next_id", move || {
+    ///     count += 1;
+    ///     count
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<i64>("next_id(); next_id(); next_id()")?, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn register_fn_mut(&mut self, name: &str, f: FN);
+}
+
 // These types are used to build a unique _marker_ tuple type for each combination
 // of function parameter types in order to make each trait implementation unique.
 // That is because stable Rust currently does not allow distinguishing implementations
@@ -89,26 +190,148 @@ pub trait RegisterResultFn<FN, ARGS> {
 pub struct Mut<T>(T);
 //pub struct Ref<T>(T);
 
+/// Invoke a macro with the standard types that can be wrapped in `Option`, `Result`, `Vec`
+/// and `HashMap` in the signatures of registered functions.
+macro_rules! std_types {
+    ($m:ident) => {
+        $m!(Dynamic, (), bool, INT, char, ImmutableString, String, FnPtr);
+        #[cfg(not(feature = "no_float"))]
+        $m!(FLOAT);
+        #[cfg(feature = "decimal")]
+        $m!(Decimal);
+        #[cfg(not(feature = "no_index"))]
+        $m!(Array);
+        #[cfg(not(feature = "no_object"))]
+        $m!(Map);
+    };
+}
+
 /// Dereference into &mut.
 #[inline(always)]
-pub fn by_ref<T: Variant + Clone>(data: &mut Dynamic) -> &mut T {
+pub fn by_ref<T: Variant + Clone>(data: &mut Dynamic) -> Result<&mut T, Box<EvalAltResult>> {
     // Directly cast the &mut Dynamic into &mut T to access the underlying data.
-    data.downcast_mut::<T>().unwrap()
+    Ok(data.downcast_mut::<T>().unwrap())
 }
 
 /// Dereference into value.
 #[inline(always)]
-pub fn by_value<T: Variant + Clone>(data: &mut Dynamic) -> T {
+pub fn by_value<T: Variant + Clone>(data: &mut Dynamic) -> Result<T, Box<EvalAltResult>> {
     if TypeId::of::<T>() == TypeId::of::<&str>() {
         // If T is &str, data must be ImmutableString, so map directly to it
         let ref_str = data.as_str().unwrap();
         let ref_T = unsafe { mem::transmute::<_, &T>(&ref_str) };
-        ref_T.clone()
-    } else {
-        // We consume the argument and then replace it with () - the argument is not supposed to be used again.
-        // This way, we avoid having to clone the argument again, because it is already a clone when passed here.
-        mem::take(data).cast::<T>()
+        return Ok(ref_T.clone());
+    }
+
+    // If T is a Vec of a standard type, data must be an Array - convert each element
+    #[cfg(not(feature = "no_index"))]
+    {
+        if let Some(result) = array_to_vec::<T>(data) {
+            return result;
+        }
+    }
+
+    // If T is a HashMap of a standard type, data must be a Map - convert each property
+    #[cfg(not(feature = "no_object"))]
+    {
+        if let Some(result) = map_to_hashmap::<T>(data) {
+            return result;
+        }
+    }
+
+    // We consume the argument and then replace it with () - the argument is not supposed to be used again.
+    // This way, we avoid having to clone the argument again, because it is already a clone when passed here.
+    Ok(mem::take(data).cast::<T>())
+}
+
+/// Convert an `Array` argument into a `Vec` of a standard type, if `T` is such a `Vec`.
+/// Returns `None` if `T` is not a supported `Vec` type.
+#[cfg(not(feature = "no_index"))]
+#[inline(always)]
+fn array_to_vec<T: Variant + Clone>(data: &mut Dynamic) -> Option<Result<T, Box<EvalAltResult>>> {
+    macro_rules! array_to_vec_of {
+        ($($t:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<Vec<$t>>() {
+                    let result = mem::take(data)
+                        .cast::<Array>()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, item)| {
+                            let actual = item.type_name();
+
+                            item.try_cast::<$t>().ok_or_else(|| {
+                                Box::new(EvalAltResult::ErrorRuntime(
+                                    format!(
+                                        "Array element {} is {}, expecting {}",
+                                        index,
+                                        map_std_type_name(actual),
+                                        map_std_type_name(type_name::<$t>())
+                                    ),
+                                    Position::none(),
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<$t>, _>>()
+                        .map(|list| unsafe_try_cast::<_, T>(list).unwrap());
+
+                    return Some(result);
+                }
+            )*
+        };
+    }
+
+    // An `Array` is already a `Vec<Dynamic>` and needs no conversion
+    if TypeId::of::<T>() == TypeId::of::<Array>() {
+        return None;
+    }
+
+    std_types!(array_to_vec_of);
+
+    None
+}
+
+/// Convert a `Map` argument into a `HashMap` with `String` keys and values of a standard type,
+/// if `T` is such a `HashMap`.
+/// Returns `None` if `T` is not a supported `HashMap` type.
+#[cfg(not(feature = "no_object"))]
+#[inline(always)]
+fn map_to_hashmap<T: Variant + Clone>(data: &mut Dynamic) -> Option<Result<T, Box<EvalAltResult>>> {
+    macro_rules! map_to_hashmap_of {
+        ($($t:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<HashMap<String, $t>>() {
+                    let result = mem::take(data)
+                        .cast::<Map>()
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let actual = value.type_name();
+
+                            match value.try_cast::<$t>() {
+                                Some(value) => Ok((key.to_string(), value)),
+                                None => Err(Box::new(EvalAltResult::ErrorRuntime(
+                                    format!(
+                                        "Property '{}' is {}, expecting {}",
+                                        key,
+                                        map_std_type_name(actual),
+                                        map_std_type_name(type_name::<$t>())
+                                    ),
+                                    Position::none(),
+                                ))),
+                            }
+                        })
+                        .collect::<Result<HashMap<String, $t>, _>>()
+                        .map(|map| unsafe_try_cast::<_, T>(map).unwrap());
+
+                    return Some(result);
+                }
+            )*
+        };
     }
+
+    std_types!(map_to_hashmap_of);
+
+    None
 }
 
 /// This macro creates a closure wrapping a registered function.
@@ -119,7 +342,7 @@ macro_rules! make_func {
 //                           ^ function parameter generic type name (A, B, C etc.)
 //                                           ^ dereferencing function
 
-		Box::new(move |_: &Engine, args: &mut FnCallArgs| {
+		Box::new(move |_: NativeCallContext, args: &mut FnCallArgs| {
             // The arguments are assumed to be of the correct number and types!
 
 			#[allow(unused_variables, unused_mut)]
@@ -127,7 +350,7 @@ macro_rules! make_func {
 			$(
 			// Downcast every element, panic in case of a type mismatch (which shouldn't happen).
 			// Call the user-supplied function using ($convert) to access it either by value or by reference.
-			let $par = ($convert)(drain.next().unwrap());
+			let $par = ($convert)(drain.next().unwrap())?;
 			)*
 
             // Call the function with each parameter value
@@ -139,42 +362,189 @@ macro_rules! make_func {
 	};
 }
 
+/// Map an `Option` of a standard type into `Dynamic`, with `None` mapped to `()`.
+/// Any other type is passed back unchanged.
+#[inline(always)]
+fn map_option<T: Variant + Clone>(data: T) -> Result<Dynamic, T> {
+    macro_rules! map_option_of {
+        ($($t:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<Option<$t>>() {
+                    let value = unsafe_try_cast::<_, Option<$t>>(data).unwrap();
+                    return Ok(value.map_or_else(Default::default, Dynamic::from));
+                }
+            )*
+        };
+    }
+
+    std_types!(map_option_of);
+
+    Err(data)
+}
+
+/// Map a `Result` of a standard type with a `String` error into `Dynamic`, with `Err` mapped to
+/// a runtime error carrying the message.
+/// Any other type is passed back unchanged.
+#[inline(always)]
+fn map_string_result<T: Variant + Clone>(
+    data: T,
+) -> Result<Result<Dynamic, Box<EvalAltResult>>, T> {
+    macro_rules! map_string_result_of {
+        ($($t:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<Result<$t, String>>() {
+                    let value = unsafe_try_cast::<_, Result<$t, String>>(data).unwrap();
+                    return Ok(value.map(Dynamic::from).map_err(|err| {
+                        Box::new(EvalAltResult::ErrorRuntime(err, Position::none()))
+                    }));
+                }
+            )*
+        };
+    }
+
+    std_types!(map_string_result_of);
+
+    Err(data)
+}
+
+/// Map a `HashMap` with `String` keys and values of a standard type into an object map.
+/// Any other type is passed back unchanged.
+#[cfg(not(feature = "no_object"))]
+#[inline(always)]
+fn map_hashmap<T: Variant + Clone>(data: T) -> Result<Dynamic, T> {
+    macro_rules! map_hashmap_of {
+        ($($t:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<HashMap<String, $t>>() {
+                    let value = unsafe_try_cast::<_, HashMap<String, $t>>(data).unwrap();
+                    return Ok(value.into());
+                }
+            )*
+        };
+    }
+
+    std_types!(map_hashmap_of);
+
+    Err(data)
+}
+
+/// Convert a return value into `Dynamic`, mapping `Option` and `HashMap` of standard types.
+#[inline(always)]
+fn into_dynamic<T: Variant + Clone>(data: T) -> Dynamic {
+    let data = match map_option(data) {
+        Ok(value) => return value,
+        Err(data) => data,
+    };
+
+    #[cfg(not(feature = "no_object"))]
+    let data = match map_hashmap(data) {
+        Ok(value) => return value,
+        Err(data) => data,
+    };
+
+    data.into_dynamic()
+}
+
 /// To Dynamic mapping function.
 #[inline(always)]
 pub fn map_dynamic<T: Variant + Clone>(data: T) -> Result<Dynamic, Box<EvalAltResult>> {
-    Ok(data.into_dynamic())
+    match map_string_result(data) {
+        Ok(result) => result,
+        Err(data) => Ok(into_dynamic(data)),
+    }
 }
 
 /// To Dynamic mapping function.
 #[inline(always)]
-pub fn map_result(
-    data: Result<Dynamic, Box<EvalAltResult>>,
+pub fn map_result<T: Variant + Clone>(
+    data: Result<T, Box<EvalAltResult>>,
 ) -> Result<Dynamic, Box<EvalAltResult>> {
-    data
+    data.map(into_dynamic)
+}
+
+/// Type ID of a parameter passed by reference, which is never remapped.
+#[inline(always)]
+fn type_id<T: 'static>() -> TypeId {
+    TypeId::of::<T>()
+}
+
+/// Is the type a `Vec` of a standard type, which can be converted from an `Array`?
+#[cfg(not(feature = "no_index"))]
+#[inline(always)]
+fn is_std_vec(id: TypeId) -> bool {
+    macro_rules! is_vec_of {
+        ($($t:ty),*) => {
+            $(
+                if id == TypeId::of::<Vec<$t>>() {
+                    return true;
+                }
+            )*
+        };
+    }
+
+    std_types!(is_vec_of);
+
+    false
 }
 
-/// Remap `&str` to `ImmutableString`.
+/// Is the type a `HashMap` with `String` keys and values of a standard type, which can be
+/// converted from a `Map`?
+#[cfg(not(feature = "no_object"))]
+#[inline(always)]
+fn is_std_hashmap(id: TypeId) -> bool {
+    macro_rules! is_hashmap_of {
+        ($($t:ty),*) => {
+            $(
+                if id == TypeId::of::<HashMap<String, $t>>() {
+                    return true;
+                }
+            )*
+        };
+    }
+
+    std_types!(is_hashmap_of);
+
+    false
+}
+
+/// Remap `&str` and `String` to `ImmutableString`, a `Vec` of a standard type to `Array`,
+/// and a `HashMap` of a standard type to `Map`, for parameters passed by value.
 #[inline(always)]
 fn map_type_id<T: 'static>() -> TypeId {
     let id = TypeId::of::<T>();
 
-    if id == TypeId::of::<&str>() {
-        TypeId::of::<ImmutableString>()
-    } else {
-        id
+    if id == TypeId::of::<&str>() || id == TypeId::of::<String>() {
+        return TypeId::of::<ImmutableString>();
+    }
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        if is_std_vec(id) {
+            return TypeId::of::<Array>();
+        }
+    }
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        if is_std_hashmap(id) {
+            return TypeId::of::<Map>();
+        }
     }
+
+    id
 }
 
 macro_rules! def_register {
     () => {
         def_register!(imp from_pure :);
     };
-    (imp $abi:ident : $($par:ident => $mark:ty => $param:ty => $clone:expr),*) => {
+    (imp $abi:ident : $($par:ident => $mark:ty => $param:ty => $clone:expr => $type_id:ident),*) => {
     //   ^ function ABI type
     //                  ^ function parameter generic type name (A, B, C etc.)
     //                                ^ function parameter marker type (T, Ref<T> or Mut<T>)
     //                                            ^ function parameter actual type (T, &T or &mut T)
     //                                                         ^ dereferencing function
+    //                                                                         ^ parameter type ID function
         impl<
             $($par: Variant + Clone,)*
             FN: Fn($($param),*) -> RET + SendSync + 'static,
@@ -182,8 +552,9 @@ macro_rules! def_register {
         > RegisterFn<FN, ($($mark,)*), RET> for Engine
         {
             fn register_fn(&mut self, name: &str, f: FN) {
-                self.global_module.set_fn(name, FnAccess::Public,
-                    &[$(map_type_id::<$par>()),*],
+                self.global_module.set_fn_with_types(name, FnAccess::Public,
+                    &[$($type_id::<$par>()),*],
+                    Some(([$(type_name::<$par>()),*].iter().cloned().collect(), type_name::<RET>())),
                     CallableFunction::$abi(make_func!(f : map_dynamic ; $($par => $clone),*))
                 );
             }
@@ -195,18 +566,62 @@ macro_rules! def_register {
         > RegisterResultFn<FN, ($($mark,)*)> for Engine
         {
             fn register_result_fn(&mut self, name: &str, f: FN) {
-                self.global_module.set_fn(name, FnAccess::Public,
-                    &[$(map_type_id::<$par>()),*],
+                self.global_module.set_fn_with_types(name, FnAccess::Public,
+                    &[$($type_id::<$par>()),*],
+                    Some(([$(type_name::<$par>()),*].iter().cloned().collect(), type_name::<Dynamic>())),
                     CallableFunction::$abi(make_func!(f : map_result ; $($par => $clone),*))
                 );
             }
         }
 
+        impl<
+            $($par: Variant + Clone,)*
+            FN: Fn($($param),*) -> Result<RET, Box<EvalAltResult>> + SendSync + 'static,
+            RET: Variant + Clone
+        > RegisterFallibleFn<FN, ($($mark,)*), RET> for Engine
+        {
+            fn register_fallible_fn(&mut self, name: &str, f: FN) {
+                self.global_module.set_fn_with_types(name, FnAccess::Public,
+                    &[$($type_id::<$par>()),*],
+                    Some(([$(type_name::<$par>()),*].iter().cloned().collect(), type_name::<RET>())),
+                    CallableFunction::$abi(make_func!(f : map_result ; $($par => $clone),*))
+                );
+            }
+        }
+
+        impl<
+            $($par: Variant + Clone,)*
+            FN: FnMut($($param),*) -> RET + SendSync + 'static,
+            RET: Variant + Clone
+        > RegisterFnMut<FN, ($($mark,)*), RET> for Engine
+        {
+            fn register_fn_mut(&mut self, name: &str, f: FN) {
+                #[cfg(not(feature = "sync"))]
+                let f = RefCell::new(f);
+                #[cfg(feature = "sync")]
+                let f = Mutex::new(f);
+
+                // Registered functions are shared, so lock the closure for each call
+                let f = move |$($par: $param),*| {
+                    #[cfg(not(feature = "sync"))]
+                    return (&mut *f.borrow_mut())($($par),*);
+                    #[cfg(feature = "sync")]
+                    return (&mut *f.lock().unwrap())($($par),*);
+                };
+
+                self.global_module.set_fn_with_types(name, FnAccess::Public,
+                    &[$($type_id::<$par>()),*],
+                    Some(([$(type_name::<$par>()),*].iter().cloned().collect(), type_name::<RET>())),
+                    CallableFunction::$abi(make_func!(f : map_dynamic ; $($par => $clone),*))
+                );
+            }
+        }
+
         //def_register!(imp_pop $($par => $mark => $param),*);
     };
     ($p0:ident $(, $p:ident)*) => {
-        def_register!(imp from_pure   : $p0 => $p0      => $p0      => by_value $(, $p => $p => $p => by_value)*);
-        def_register!(imp from_method : $p0 => Mut<$p0> => &mut $p0 => by_ref   $(, $p => $p => $p => by_value)*);
+        def_register!(imp from_pure   : $p0 => $p0      => $p0      => by_value => map_type_id $(, $p => $p => $p => by_value => map_type_id)*);
+        def_register!(imp from_method : $p0 => Mut<$p0> => &mut $p0 => by_ref   => type_id     $(, $p => $p => $p => by_value => map_type_id)*);
         //                ^ CallableFunction
         // handle the first parameter                                  ^ first parameter passed through
         //                                                                                            ^ others passed by value (by_value)
@@ -64,6 +64,10 @@
 //! | `no_std`      | Build for `no-std`. Notice that additional dependencies will be pulled in to replace `std` features.                              |
 //! | `sync`        | Restrict all values types to those that are `Send + Sync`. Under this feature, `Engine`, `Scope` and [`AST`] are all `Send + Sync`. |
 //! | `internals`   | Expose internal data structures (beware they may be volatile from version to version).                                            |
+//! | `decimal`     | Add a fixed-point `Decimal` number type, with literals written with a `d` suffix (e.g. `0.1d`).                                    |
+//! | `regex`       | Add regular expression functions (e.g. `regex_match`) via the `regex` crate.                                                      |
+//! | `rand`        | Add seedable random number functions (e.g. `rand`, `rand_int`, `shuffle`, `seed`).                                                |
+//! | `tags`        | Attach an integer tag to every `Dynamic` value (via `Dynamic::tag` and `Dynamic::set_tag`). This makes `Dynamic` larger.          |
 //!
 //! See [The Rhai Book](https://schungx.github.io/rhai) for details on the Rhai script engine and language.
 
@@ -87,19 +91,24 @@ mod parser;
 mod result;
 mod scope;
 mod stdlib;
+mod syntax;
 mod token;
 mod r#unsafe;
 mod utils;
 
 pub use any::Dynamic;
+
+#[cfg(feature = "tags")]
+pub use any::Tag;
 pub use engine::Engine;
 pub use error::{ParseError, ParseErrorType};
-pub use fn_native::IteratorFn;
-pub use fn_register::{RegisterFn, RegisterResultFn};
+pub use fn_native::{FnPtr, IteratorFn, NativeCallContext};
+pub use fn_register::{RegisterFallibleFn, RegisterFn, RegisterFnMut, RegisterResultFn};
 pub use module::Module;
 pub use parser::{ImmutableString, AST, INT};
 pub use result::EvalAltResult;
 pub use scope::Scope;
+pub use syntax::{EvalContext, Expression};
 pub use token::Position;
 pub use utils::calc_fn_spec as calc_fn_hash;
 
@@ -118,6 +127,9 @@ pub use engine::Map;
 #[cfg(not(feature = "no_float"))]
 pub use parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+pub use parser::Decimal;
+
 #[cfg(not(feature = "no_module"))]
 pub use module::ModuleResolver;
 
@@ -138,7 +150,7 @@ pub use token::{get_next_token, parse_string_literal, InputStream, Token, Tokeni
 
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
-pub use parser::{Expr, ReturnType, ScriptFnDef, Stmt};
+pub use parser::{ASTNode, CustomExpr, Expr, ReturnType, ScriptFnDef, Stmt};
 
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
@@ -146,4 +158,4 @@ pub use module::ModuleRef;
 
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
-pub use utils::StaticVec;
+pub use utils::{IdentHash, StaticVec};
@@ -1,9 +1,11 @@
 //! Module defining external-loaded modules for Rhai.
 
-use crate::any::{Dynamic, Variant};
+use crate::any::{type_name_of_id, Dynamic, Variant};
 use crate::calc_fn_hash;
 use crate::engine::{make_getter, make_setter, Engine, Imports, FN_IDX_GET, FN_IDX_SET};
-use crate::fn_native::{CallableFunction, FnCallArgs, IteratorFn, SendSync, Shared};
+use crate::fn_native::{
+    CallableFunction, FnCallArgs, IteratorFn, NativeCallContext, SendSync, Shared,
+};
 use crate::parser::{
     FnAccess,
     FnAccess::{Private, Public},
@@ -15,7 +17,7 @@ use crate::token::{Position, Token};
 use crate::utils::{StaticVec, StraightHasherBuilder};
 
 use crate::stdlib::{
-    any::TypeId,
+    any::{type_name, TypeId},
     boxed::Box,
     cell::RefCell,
     collections::HashMap,
@@ -33,6 +35,18 @@ use crate::stdlib::{
 /// Return type of module-level Rust function.
 pub type FuncReturn<T> = Result<T, Box<EvalAltResult>>;
 
+/// Type names of the parameters and the return value of a Rust function.
+pub(crate) type FnTypeNames = (StaticVec<&'static str>, &'static str);
+
+/// A function in a module: name, access mode, parameter types, type names (if known) and the function itself.
+pub(crate) type FnEntry = (
+    String,
+    FnAccess,
+    StaticVec<TypeId>,
+    Option<FnTypeNames>,
+    CallableFunction,
+);
+
 /// An imported module, which may contain variables, sub-modules,
 /// external Rust functions, and script-defined functions.
 ///
@@ -48,12 +62,8 @@ pub struct Module {
     /// Flattened collection of all module variables, including those in sub-modules.
     all_variables: HashMap<u64, Dynamic, StraightHasherBuilder>,
 
-    /// External Rust functions.
-    functions: HashMap<
-        u64,
-        (String, FnAccess, StaticVec<TypeId>, CallableFunction),
-        StraightHasherBuilder,
-    >,
+    /// External Rust functions, with the type names of their parameters and return values (if known).
+    functions: HashMap<u64, FnEntry, StraightHasherBuilder>,
 
     /// Iterator functions, keyed by the type producing the iterator.
     type_iterators: HashMap<TypeId, IteratorFn>,
@@ -83,7 +93,7 @@ impl fmt::Debug for Module {
                 .join(", "),
             self.functions
                 .values()
-                .map(|(_, _, _, f)| f.to_string())
+                .map(|(_, _, _, _, f)| f.to_string())
                 .collect::<Vec<_>>()
                 .join(", "),
         )
@@ -233,6 +243,13 @@ impl Module {
         })
     }
 
+    /// Get a reference to a modules-qualified variable.
+    ///
+    /// The `u64` hash is calculated by the function `crate::calc_fn_hash`.
+    pub(crate) fn get_qualified_var(&self, hash_var: u64) -> Option<&Dynamic> {
+        self.all_variables.get(&hash_var)
+    }
+
     /// Set a script-defined function into the module.
     ///
     /// If there is an existing function of the same name and number of arguments, it is replaced.
@@ -245,6 +262,7 @@ impl Module {
                 fn_def.name.to_string(),
                 fn_def.access,
                 Default::default(),
+                None,
                 fn_def.into(),
             ),
         );
@@ -345,6 +363,21 @@ impl Module {
         access: FnAccess,
         params: &[TypeId],
         func: CallableFunction,
+    ) -> u64 {
+        self.set_fn_with_types(name, access, params, None, func)
+    }
+
+    /// Set a Rust function into the module, recording the type names of its parameters
+    /// and return value, returning a hash key.
+    ///
+    /// The type names are used to generate function signatures.
+    pub(crate) fn set_fn_with_types(
+        &mut self,
+        name: impl Into<String>,
+        access: FnAccess,
+        params: &[TypeId],
+        types: Option<FnTypeNames>,
+        func: CallableFunction,
     ) -> u64 {
         let name = name.into();
 
@@ -353,32 +386,80 @@ impl Module {
         let params = params.into_iter().cloned().collect();
 
         self.functions
-            .insert(hash_fn, (name, access, params, func.into()));
+            .insert(hash_fn, (name, access, params, types, func.into()));
 
         self.indexed = false;
 
         hash_fn
     }
 
-    /// Set a Rust function taking a reference to the scripting `Engine`, plus a list of
-    /// mutable `Dynamic` references into the module, returning a hash key.
+    /// Make all Rust functions named `name` also callable as `alias`,
+    /// returning the number of functions aliased.
+    ///
+    /// If there is an existing Rust function of the same alias and parameter types, it is replaced.
+    pub(crate) fn set_fn_alias(&mut self, name: &str, alias: &str) -> usize {
+        let entries: Vec<_> = self
+            .functions
+            .values()
+            .filter(|(fn_name, _, _, _, func)| fn_name == name && !func.is_script())
+            .map(|(_, access, params, types, func)| {
+                let params: Vec<_> = params.iter().cloned().collect();
+                (*access, params, types.clone(), func.clone())
+            })
+            .collect();
+
+        let count = entries.len();
+
+        for (access, params, types, func) in entries {
+            self.set_fn_with_types(alias, access, &params, types, func);
+        }
+
+        count
+    }
+
+    /// Set a Rust function taking the context of the call (including a reference to the
+    /// scripting `Engine`), plus a list of mutable `Dynamic` references into the module,
+    /// returning a hash key.
     /// A list of `TypeId`'s is taken as the argument types.
     ///
     /// Use this to register a built-in function which must reference settings on the scripting
-    /// `Engine` (e.g. to prevent growing an array beyond the allowed maximum size).
+    /// `Engine` (e.g. to prevent growing an array beyond the allowed maximum size), or which
+    /// calls back into script (e.g. via a function pointer).
     ///
     /// If there is a similar existing Rust function, it is replaced.
-    pub(crate) fn set_fn_var_args<T: Variant + Clone>(
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::any::TypeId;
+    /// use rhai::{Module, NativeCallContext};
+    ///
+    /// let mut module = Module::new();
+    /// let hash = module.set_fn_var_args(
+    ///     "calc",
+    ///     &[TypeId::of::<i64>()],
+    ///     |_: NativeCallContext, args| Ok(args[0].as_int().unwrap() + 1),
+    /// );
+    /// assert!(module.contains_fn(hash));
+    /// ```
+    pub fn set_fn_var_args<T: Variant + Clone>(
         &mut self,
         name: impl Into<String>,
         args: &[TypeId],
-        func: impl Fn(&Engine, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
+        func: impl Fn(NativeCallContext, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |engine: &Engine, args: &mut FnCallArgs| func(engine, args).map(Dynamic::from);
-        self.set_fn(
+        let f = move |context: NativeCallContext, args: &mut FnCallArgs| {
+            func(context, args).map(Dynamic::from)
+        };
+        let types = (
+            args.iter().map(|&id| type_name_of_id(id)).collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             args,
+            Some(types),
             CallableFunction::from_method(Box::new(f)),
         )
     }
@@ -401,12 +482,14 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn() -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &mut FnCallArgs| func().map(Dynamic::from);
+        let f = move |_: NativeCallContext, _: &mut FnCallArgs| func().map(Dynamic::from);
         let args = [];
-        self.set_fn(
+        let types = (Default::default(), type_name::<T>());
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_pure(Box::new(f)),
         )
     }
@@ -429,14 +512,19 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             func(mem::take(args[0]).cast::<A>()).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>()].iter().cloned().collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_pure(Box::new(f)),
         )
     }
@@ -459,14 +547,19 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             func(args[0].downcast_mut::<A>().unwrap()).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>()].iter().cloned().collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_method(Box::new(f)),
         )
     }
@@ -513,17 +606,25 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A, B) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let a = mem::take(args[0]).cast::<A>();
             let b = mem::take(args[1]).cast::<B>();
 
             func(a, b).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>(), TypeId::of::<B>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>(), type_name::<B>()]
+                .iter()
+                .cloned()
+                .collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_pure(Box::new(f)),
         )
     }
@@ -549,17 +650,25 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A, B) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let a = args[0].downcast_mut::<A>().unwrap();
 
             func(a, b).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>(), TypeId::of::<B>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>(), type_name::<B>()]
+                .iter()
+                .cloned()
+                .collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_method(Box::new(f)),
         )
     }
@@ -640,7 +749,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A, B, C) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let a = mem::take(args[0]).cast::<A>();
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
@@ -648,10 +757,18 @@ impl Module {
             func(a, b, c).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>(), TypeId::of::<B>(), TypeId::of::<C>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>(), type_name::<B>(), type_name::<C>()]
+                .iter()
+                .cloned()
+                .collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_pure(Box::new(f)),
         )
     }
@@ -682,7 +799,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A, B, C) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
             let a = args[0].downcast_mut::<A>().unwrap();
@@ -690,10 +807,18 @@ impl Module {
             func(a, b, c).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>(), TypeId::of::<B>(), TypeId::of::<C>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>(), type_name::<B>(), type_name::<C>()]
+                .iter()
+                .cloned()
+                .collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_method(Box::new(f)),
         )
     }
@@ -719,7 +844,7 @@ impl Module {
         &mut self,
         func: impl Fn(&mut A, B, A) -> FuncReturn<()> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<A>();
             let a = args[0].downcast_mut::<A>().unwrap();
@@ -727,10 +852,18 @@ impl Module {
             func(a, b, c).map(Dynamic::from)
         };
         let args = [TypeId::of::<A>(), TypeId::of::<B>(), TypeId::of::<A>()];
-        self.set_fn(
+        let types = (
+            [type_name::<A>(), type_name::<B>(), type_name::<A>()]
+                .iter()
+                .cloned()
+                .collect(),
+            type_name::<()>(),
+        );
+        self.set_fn_with_types(
             FN_IDX_SET,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_method(Box::new(f)),
         )
     }
@@ -761,7 +894,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A, B, C, D) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let a = mem::take(args[0]).cast::<A>();
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
@@ -775,10 +908,23 @@ impl Module {
             TypeId::of::<C>(),
             TypeId::of::<D>(),
         ];
-        self.set_fn(
+        let types = (
+            [
+                type_name::<A>(),
+                type_name::<B>(),
+                type_name::<C>(),
+                type_name::<D>(),
+            ]
+            .iter()
+            .cloned()
+            .collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_pure(Box::new(f)),
         )
     }
@@ -810,7 +956,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A, B, C, D) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
             let d = mem::take(args[3]).cast::<D>();
@@ -822,12 +968,25 @@ impl Module {
             TypeId::of::<A>(),
             TypeId::of::<B>(),
             TypeId::of::<C>(),
-            TypeId::of::<C>(),
+            TypeId::of::<D>(),
         ];
-        self.set_fn(
+        let types = (
+            [
+                type_name::<A>(),
+                type_name::<B>(),
+                type_name::<C>(),
+                type_name::<D>(),
+            ]
+            .iter()
+            .cloned()
+            .collect(),
+            type_name::<T>(),
+        );
+        self.set_fn_with_types(
             name,
             Public,
             &args,
+            Some(types),
             CallableFunction::from_method(Box::new(f)),
         )
     }
@@ -837,7 +996,7 @@ impl Module {
     /// The `u64` hash is calculated by the function `crate::calc_fn_hash`.
     /// It is also returned by the `set_fn_XXX` calls.
     pub(crate) fn get_fn(&self, hash_fn: u64) -> Option<&CallableFunction> {
-        self.functions.get(&hash_fn).map(|(_, _, _, v)| v)
+        self.functions.get(&hash_fn).map(|(_, _, _, _, v)| v)
     }
 
     /// Get a modules-qualified function.
@@ -846,7 +1005,7 @@ impl Module {
     /// The `u64` hash is calculated by the function `crate::calc_fn_hash` and must match
     /// the hash calculated by `index_all_sub_modules`.
     pub(crate) fn get_qualified_fn(
-        &mut self,
+        &self,
         hash_qualified_fn: u64,
     ) -> Result<&CallableFunction, Box<EvalAltResult>> {
         self.all_functions.get(&hash_qualified_fn).ok_or_else(|| {
@@ -875,7 +1034,7 @@ impl Module {
             other
                 .functions
                 .iter()
-                .filter(|(_, (_, _, _, v))| match v {
+                .filter(|(_, (_, _, _, _, v))| match v {
                     CallableFunction::Pure(_)
                     | CallableFunction::Method(_)
                     | CallableFunction::Iterator(_) => true,
@@ -896,7 +1055,7 @@ impl Module {
 
     /// Filter out the functions, retaining only some based on a filter predicate.
     pub(crate) fn retain_functions(&mut self, filter: impl Fn(FnAccess, &str, usize) -> bool) {
-        self.functions.retain(|_, (_, _, _, v)| match v {
+        self.functions.retain(|_, (_, _, _, _, v)| match v {
             CallableFunction::Pure(_)
             | CallableFunction::Method(_)
             | CallableFunction::Iterator(_) => true,
@@ -927,9 +1086,7 @@ impl Module {
     }
 
     /// Get an iterator to the functions in the module.
-    pub(crate) fn iter_fn(
-        &self,
-    ) -> impl Iterator<Item = &(String, FnAccess, StaticVec<TypeId>, CallableFunction)> {
+    pub(crate) fn iter_fn(&self) -> impl Iterator<Item = &FnEntry> {
         self.functions.values()
     }
 
@@ -937,7 +1094,7 @@ impl Module {
     pub fn iter_script_fn<'a>(&'a self) -> impl Iterator<Item = Shared<ScriptFnDef>> + 'a {
         self.functions
             .values()
-            .map(|(_, _, _, f)| f)
+            .map(|(_, _, _, _, f)| f)
             .filter(|f| f.is_script())
             .map(|f| f.get_shared_fn_def())
     }
@@ -1011,7 +1168,7 @@ impl Module {
                 variables.push((hash_var, value.clone()));
             }
             // Index all Rust functions
-            for (name, access, params, func) in module.functions.values() {
+            for (name, access, params, _, func) in module.functions.values() {
                 match access {
                     // Private functions are not exported
                     Private => continue,
@@ -4,6 +4,7 @@ use crate::engine::{Engine, Imports, KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_PRINT,
 use crate::module::Module;
 use crate::parser::{map_dynamic_to_expr, Expr, ReturnType, ScriptFnDef, Stmt, AST};
 use crate::scope::{Entry as ScopeEntry, EntryType as ScopeEntryType, Scope};
+use crate::token::Position;
 use crate::utils::StaticVec;
 
 use crate::stdlib::{
@@ -121,7 +122,7 @@ fn call_fn_with_constant_arguments(
     state
         .engine
         .call_fn_raw(
-            &mut Scope::new(),
+            None,
             &mut Imports::new(),
             &mut Default::default(),
             state.lib,
@@ -131,6 +132,7 @@ fn call_fn_with_constant_arguments(
             false,
             false,
             None,
+            Position::none(),
             0,
         )
         .map(|(v, _)| Some(v))
@@ -217,6 +219,13 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
                 stmt => Stmt::While(Box::new((optimize_expr(expr, state), stmt))),
             },
         },
+        // while let id = expr { block }
+        Stmt::WhileLet(x) => Stmt::WhileLet(Box::new((
+            x.0,
+            optimize_expr(x.1, state),
+            optimize_stmt(x.2, state, false),
+            x.3,
+        ))),
         // loop { block }
         Stmt::Loop(block) => match optimize_stmt(*block, state, false) {
             // loop { break; } -> Noop
@@ -228,20 +237,42 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
             // loop { block }
             stmt => Stmt::Loop(Box::new(stmt)),
         },
-        // for id in expr { block }
+        // for id in expr { block } or for (counter, id) in expr { block }
         Stmt::For(x) => Stmt::For(Box::new((
             x.0,
             optimize_expr(x.1, state),
             optimize_stmt(x.2, state, false),
+            x.3,
         ))),
         // let id = expr;
-        Stmt::Let(x) if x.1.is_some() => {
-            Stmt::Let(Box::new((x.0, Some(optimize_expr(x.1.unwrap(), state)))))
-        }
+        Stmt::Let(x) if x.1.is_some() => Stmt::Let(Box::new((
+            x.0,
+            Some(optimize_expr(x.1.unwrap(), state)),
+            x.2,
+        ))),
         // let id;
         stmt @ Stmt::Let(_) => stmt,
+        // try { block } finally { block }
+        Stmt::TryFinally(x) => {
+            let (body, finally_block, pos) = *x;
+            Stmt::TryFinally(Box::new((
+                optimize_stmt(body, state, preserve_result),
+                optimize_stmt(finally_block, state, false),
+                pos,
+            )))
+        }
+        // let (id, ...) = expr;
+        Stmt::LetTuple(x) => {
+            let (names, expr, pos) = *x;
+            Stmt::LetTuple(Box::new((names, optimize_expr(expr, state), pos)))
+        }
         // import expr as id;
         Stmt::Import(x) => Stmt::Import(Box::new((optimize_expr(x.0, state), x.1))),
+        // #[unchecked] { block }
+        Stmt::Unchecked(x) => {
+            let (body, pos) = *x;
+            Stmt::Unchecked(Box::new((optimize_stmt(body, state, preserve_result), pos)))
+        }
         // { block }
         Stmt::Block(x) => {
             let orig_len = x.0.len(); // Original number of statements in the block, for change detection
@@ -254,7 +285,7 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
                     .map(|stmt| match stmt {
                         // Add constant into the state
                         Stmt::Const(v) => {
-                            let ((name, pos), expr) = *v;
+                            let ((name, pos), expr, _) = *v;
                             state.push_constant(&name, expr);
                             state.set_dirty();
                             Stmt::Noop(pos) // No need to keep constants
@@ -338,7 +369,9 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
                     Stmt::Noop(pos)
                 }
                 // Only one let/import statement - leave it alone
-                [Stmt::Let(_)] | [Stmt::Import(_)] => Stmt::Block(Box::new((result.into(), pos))),
+                [Stmt::Let(_)] | [Stmt::LetTuple(_)] | [Stmt::Import(_)] => {
+                    Stmt::Block(Box::new((result.into(), pos)))
+                }
                 // Only one statement - promote
                 [_] => {
                     state.set_dirty();
@@ -362,6 +395,8 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
 fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
     // These keywords are handled specially
     const DONT_EVAL_KEYWORDS: [&str; 3] = [KEYWORD_PRINT, KEYWORD_DEBUG, KEYWORD_EVAL];
+    // These functions have side effects, so calling them at compile time changes their results
+    const DONT_EVAL_FUNCTIONS: [&str; 4] = ["seed", "rand", "rand_int", "shuffle"];
 
     match expr {
         // expr - do not promote because there is a reason it is wrapped in an `Expr::Expr`
@@ -404,7 +439,10 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
         #[cfg(not(feature = "no_object"))]
         Expr::Dot(x) => match (x.0, x.1) {
             // map.string
-            (Expr::Map(m), Expr::Property(p)) if m.0.iter().all(|(_, x)| x.is_pure()) => {
+            // Missing properties are left alone if they must fail at runtime
+            (Expr::Map(m), Expr::Property(p)) if m.0.iter().all(|(_, x)| x.is_pure())
+                && (!state.engine.fail_on_invalid_map_property
+                    || m.0.iter().any(|((name, _), _)| name.as_str() == (p.0).0)) => {
                 let ((prop, _, _), _) = p.as_ref();
                 // Map literal where everything is pure - promote the indexed item.
                 // All other items can be thrown away.
@@ -431,7 +469,10 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
                 a.0.take(i.0 as usize).set_position(a.1)
             }
             // map[string]
-            (Expr::Map(m), Expr::StringConstant(s)) if m.0.iter().all(|(_, x)| x.is_pure()) => {
+            #[cfg(not(feature = "no_object"))]
+            (Expr::Map(m), Expr::StringConstant(s)) if m.0.iter().all(|(_, x)| x.is_pure())
+                && (!state.engine.fail_on_invalid_map_property
+                    || m.0.iter().any(|((name, _), _)| *name == s.0)) => {
                 // Map literal where everything is pure - promote the indexed item.
                 // All other items can be thrown away.
                 state.set_dirty();
@@ -535,8 +576,15 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
             (lhs, rhs) => Expr::Or(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2))),
         },
 
-        // Do not call some special keywords
-        Expr::FnCall(mut x) if DONT_EVAL_KEYWORDS.contains(&(x.0).0.as_ref())=> {
+        // lhs < expr < expr ... -> optimize the operands
+        Expr::CmpChain(mut x) => {
+            x.0 = x.0.into_iter().map(|a| optimize_expr(a, state)).collect();
+            Expr::CmpChain(x)
+        }
+
+        // Do not call some special keywords, nor functions with side effects
+        Expr::FnCall(mut x) if DONT_EVAL_KEYWORDS.contains(&(x.0).0.as_ref())
+                            || DONT_EVAL_FUNCTIONS.contains(&(x.0).0.as_ref()) => {
             x.3 = x.3.into_iter().map(|a| optimize_expr(a, state)).collect();
             Expr::FnCall(x)
         }
@@ -551,7 +599,7 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
 
             // First search in functions lib (can override built-in)
             // Cater for both normal function call style and method call style (one additional arguments)
-            if state.lib.iter_fn().find(|(_, _, _, f)| {
+            if state.lib.iter_fn().find(|(_, _, _, _, f)| {
                 if !f.is_script() { return false; }
                 let fn_def = f.get_fn_def();
                 &fn_def.name == name && (args.len()..=args.len() + 1).contains(&fn_def.params.len())
@@ -661,7 +709,7 @@ fn optimize(
                 match &stmt {
                     Stmt::Const(v) => {
                         // Load constants
-                        let ((name, _), expr) = v.as_ref();
+                        let ((name, _), expr, _) = v.as_ref();
                         state.push_constant(&name, expr.clone());
                         stmt // Keep it in the global scope
                     }
@@ -669,7 +717,7 @@ fn optimize(
                         // Keep all variable declarations at this level
                         // and always keep the last return value
                         let keep = match stmt {
-                            Stmt::Let(_) | Stmt::Import(_) => true,
+                            Stmt::Let(_) | Stmt::LetTuple(_) | Stmt::Import(_) => true,
                             _ => i == num_statements - 1,
                         };
                         optimize_stmt(stmt, &mut state, keep)
@@ -726,6 +774,7 @@ pub fn optimize_into_ast(
                         access: fn_def.access,
                         body: Default::default(),
                         params: fn_def.params.clone(),
+                        param_hashes: fn_def.param_hashes.clone(),
                         pos: fn_def.pos,
                     }
                     .into()
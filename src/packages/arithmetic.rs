@@ -7,6 +7,9 @@ use crate::token::Position;
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
 use num_traits::{
     identities::Zero, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedShl,
     CheckedShr, CheckedSub,
@@ -16,7 +19,7 @@ use crate::stdlib::{
     boxed::Box,
     fmt::Display,
     format,
-    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Rem, Shl, Shr, Sub},
+    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub},
 };
 
 // Checked add
@@ -132,6 +135,9 @@ fn binary_or<T: BitOr>(x: T, y: T) -> FuncReturn<<T as BitOr>::Output> {
 fn binary_xor<T: BitXor>(x: T, y: T) -> FuncReturn<<T as BitXor>::Output> {
     Ok(x ^ y)
 }
+fn binary_not<T: Not>(x: T) -> FuncReturn<<T as Not>::Output> {
+    Ok(!x)
+}
 // Checked left-shift
 pub(crate) fn shl<T: Display + CheckedShl>(x: T, y: INT) -> FuncReturn<T> {
     // Cannot shift by a negative number of bits
@@ -232,6 +238,20 @@ pub(crate) fn pow_i_i(x: INT, y: INT) -> FuncReturn<INT> {
 pub(crate) fn pow_i_i_u(x: INT, y: INT) -> FuncReturn<INT> {
     Ok(x.pow(y as u32))
 }
+// Wrapping integer arithmetic, used within `#[unchecked]` blocks.
+// Returns `None` when there is no sensible wrapped result (e.g. division by zero),
+// in which case the normal checked operation should be used to raise the error.
+pub(crate) fn wrapping_op(op: &str, x: INT, y: INT) -> Option<INT> {
+    match op {
+        "+" => Some(x.wrapping_add(y)),
+        "-" => Some(x.wrapping_sub(y)),
+        "*" => Some(x.wrapping_mul(y)),
+        "/" if y != 0 => Some(x.wrapping_div(y)),
+        "%" if y != 0 => Some(x.wrapping_rem(y)),
+        "~" if y >= 0 && y as u64 <= u32::MAX as u64 => Some(x.wrapping_pow(y as u32)),
+        _ => None,
+    }
+}
 // Floating-point power - always well-defined
 #[cfg(not(feature = "no_float"))]
 pub(crate) fn pow_f_f(x: FLOAT, y: FLOAT) -> FuncReturn<FLOAT> {
@@ -267,6 +287,29 @@ macro_rules! reg_op {
         $( $lib.set_fn_2($op, $func::<$par>); )*
     };
 }
+// Checked Euclidean division and remainder
+macro_rules! reg_euclid {
+    ($lib:expr, $($par:ty),*) => {
+        $(
+            $lib.set_fn_2("div_euclid", |x: $par, y: $par| {
+                x.checked_div_euclid(y).ok_or_else(|| {
+                    Box::new(EvalAltResult::ErrorArithmetic(
+                        format!("Division by zero or overflow: div_euclid({}, {})", x, y),
+                        Position::none(),
+                    ))
+                })
+            });
+            $lib.set_fn_2("rem_euclid", |x: $par, y: $par| {
+                x.checked_rem_euclid(y).ok_or_else(|| {
+                    Box::new(EvalAltResult::ErrorArithmetic(
+                        format!("Modulo division by zero or overflow: rem_euclid({}, {})", x, y),
+                        Position::none(),
+                    ))
+                })
+            });
+        )*
+    };
+}
 macro_rules! reg_sign {
     ($lib:expr, $op:expr, $ret:ty, $($par:ty),*) => {
         $( $lib.set_fn_1($op, |value: $par| -> Result<$ret, _> {
@@ -336,11 +379,18 @@ def_package!(crate:ArithmeticPackage:"Basic arithmetic", lib, {
         }
 
         reg_sign!(lib, "sign", INT, i8, i16, i32, i64);
+        reg_euclid!(lib, i8, u8, i16, u16, i32, u32, u64);
 
         #[cfg(not(target_arch = "wasm32"))]
-        reg_sign!(lib, "sign", INT, i128);
+        {
+            reg_sign!(lib, "sign", INT, i128);
+            reg_euclid!(lib, i128, u128);
+        }
     }
 
+    // Euclidean division and remainder - unlike `/` and `%`, the remainder is never negative
+    reg_euclid!(lib, INT);
+
     // Basic arithmetic for floating-point - no need to check
     #[cfg(not(feature = "no_float"))]
     {
@@ -352,18 +402,23 @@ def_package!(crate:ArithmeticPackage:"Basic arithmetic", lib, {
         reg_sign!(lib, "sign", f64, f64);
     }
 
+    // Bitwise complement
+    reg_unary!(lib, "!", binary_not, INT);
+
     #[cfg(not(feature = "only_i32"))]
     #[cfg(not(feature = "only_i64"))]
     {
         reg_op!(lib, "|", binary_or, i8, u8, i16, u16, i32, u32, u64);
         reg_op!(lib, "&", binary_and, i8, u8, i16, u16, i32, u32, u64);
         reg_op!(lib, "^", binary_xor, i8, u8, i16, u16, i32, u32, u64);
+        reg_unary!(lib, "!", binary_not, i8, u8, i16, u16, i32, i64, u32, u64);
 
         #[cfg(not(target_arch = "wasm32"))]
         {
             reg_op!(lib, "|", binary_or, i128, u128);
             reg_op!(lib, "&", binary_and, i128, u128);
             reg_op!(lib, "^", binary_xor, i128, u128);
+            reg_unary!(lib, "!", binary_not, i128, u128);
         }
     }
 
@@ -385,6 +440,34 @@ def_package!(crate:ArithmeticPackage:"Basic arithmetic", lib, {
         reg_unary!(lib, "abs", abs_u, f32, f64);
     }
 
+    // Basic arithmetic for decimal numbers - checked to avoid panics on overflow
+    #[cfg(feature = "decimal")]
+    {
+        #[cfg(not(feature = "unchecked"))]
+        {
+            reg_op!(lib, "+", add, Decimal);
+            reg_op!(lib, "-", sub, Decimal);
+            reg_op!(lib, "*", mul, Decimal);
+            reg_op!(lib, "/", div, Decimal);
+            reg_op!(lib, "%", modulo, Decimal);
+        }
+
+        #[cfg(feature = "unchecked")]
+        {
+            reg_op!(lib, "+", add_u, Decimal);
+            reg_op!(lib, "-", sub_u, Decimal);
+            reg_op!(lib, "*", mul_u, Decimal);
+            reg_op!(lib, "/", div_u, Decimal);
+            reg_op!(lib, "%", modulo_u, Decimal);
+        }
+
+        reg_unary!(lib, "-", neg_u, Decimal);
+        reg_unary!(lib, "abs", abs_u, Decimal);
+        lib.set_fn_1("sign", |x: Decimal| {
+            Ok(if x.is_zero() { 0 } else if x.is_sign_negative() { -1 } else { 1 } as INT)
+        });
+    }
+
     // Checked unary
     #[cfg(not(feature = "unchecked"))]
     {
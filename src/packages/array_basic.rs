@@ -2,30 +2,136 @@
 
 use crate::any::{Dynamic, Variant};
 use crate::def_package;
-use crate::engine::{Array, Engine};
+use crate::engine::Array;
+#[cfg(not(feature = "no_object"))]
+use crate::engine::Map;
+use crate::fn_native::{FnPtr, NativeCallContext};
 use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
 use crate::result::EvalAltResult;
 use crate::token::Position;
 
-use crate::stdlib::{any::TypeId, boxed::Box, string::ToString};
+use crate::stdlib::{
+    any::TypeId, boxed::Box, convert::TryFrom, format, mem, string::ToString, vec,
+};
 
 // Register array utility functions
 fn push<T: Variant + Clone>(list: &mut Array, item: T) -> FuncReturn<()> {
     list.push(Dynamic::from(item));
     Ok(())
 }
+fn unshift<T: Variant + Clone>(list: &mut Array, item: T) -> FuncReturn<()> {
+    list.insert(0, Dynamic::from(item));
+    Ok(())
+}
 fn ins<T: Variant + Clone>(list: &mut Array, position: INT, item: T) -> FuncReturn<()> {
-    if position <= 0 {
-        list.insert(0, Dynamic::from(item));
-    } else if (position as usize) >= list.len() - 1 {
-        push(list, item)?;
+    // Inserting at the end of the array appends
+    if position < 0 || (position as usize) > list.len() {
+        return Err(Box::new(EvalAltResult::ErrorArrayBounds(
+            list.len(),
+            position,
+            Position::none(),
+        )));
+    }
+
+    list.insert(position as usize, Dynamic::from(item));
+    Ok(())
+}
+fn remove(list: &mut Array, position: INT) -> FuncReturn<Dynamic> {
+    if position < 0 || (position as usize) >= list.len() {
+        return Err(Box::new(EvalAltResult::ErrorArrayBounds(
+            list.len(),
+            position,
+            Position::none(),
+        )));
+    }
+
+    Ok(list.remove(position as usize))
+}
+/// Resolve an index into the array, counting from the end if negative.
+/// Returns `None` if the index is out of bounds.
+fn resolve_index(list: &Array, index: INT) -> Option<usize> {
+    if index >= 0 {
+        Some(index as usize).filter(|&i| i < list.len())
+    } else {
+        usize::try_from(index.unsigned_abs())
+            .ok()
+            .and_then(|n| list.len().checked_sub(n))
+    }
+}
+// Read an element, or the default value (if any, otherwise `()`) when the index is out of bounds
+fn get(_: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Dynamic> {
+    let list = args[0].downcast_ref::<Array>().unwrap();
+    let index = args[1].as_int().unwrap();
+
+    Ok(match resolve_index(list, index) {
+        Some(i) => list[i].clone(),
+        None if args.len() > 2 => mem::take(args[2]),
+        None => ().into(),
+    })
+}
+fn non_negative(list: &Array, n: INT) -> FuncReturn<usize> {
+    if n < 0 {
+        Err(Box::new(EvalAltResult::ErrorArrayBounds(
+            list.len(),
+            n,
+            Position::none(),
+        )))
     } else {
-        list.insert(position as usize, Dynamic::from(item));
+        Ok(n as usize)
     }
+}
+fn truncate(list: &mut Array, len: INT) -> FuncReturn<()> {
+    let len = non_negative(list, len)?;
+    list.truncate(len);
     Ok(())
 }
-fn pad<T: Variant + Clone>(engine: &Engine, args: &mut [&mut Dynamic]) -> FuncReturn<()> {
+fn keep_first(list: &mut Array, n: INT) -> FuncReturn<()> {
+    truncate(list, n)
+}
+fn keep_last(list: &mut Array, n: INT) -> FuncReturn<()> {
+    let n = non_negative(list, n)?;
+
+    if n < list.len() {
+        list.drain(..list.len() - n);
+    }
+    Ok(())
+}
+fn zip(x: Array, y: Array) -> FuncReturn<Array> {
+    Ok(x.into_iter()
+        .zip(y)
+        .map(|(a, b)| Dynamic::from(vec![a, b]))
+        .collect())
+}
+fn positive_size(fn_name: &str, size: INT) -> FuncReturn<usize> {
+    if size > 0 {
+        Ok(size as usize)
+    } else {
+        Err(Box::new(EvalAltResult::ErrorRuntime(
+            format!("Size for '{}' must be positive: {}", fn_name, size),
+            Position::none(),
+        )))
+    }
+}
+fn chunk(list: &mut Array, size: INT) -> FuncReturn<Array> {
+    let size = positive_size("chunk", size)?;
+    Ok(list
+        .chunks(size)
+        .map(|c| Dynamic::from(c.to_vec()))
+        .collect())
+}
+fn windows(list: &mut Array, size: INT) -> FuncReturn<Array> {
+    let size = positive_size("windows", size)?;
+    Ok(list
+        .windows(size)
+        .map(|w| Dynamic::from(w.to_vec()))
+        .collect())
+}
+fn pad<T: Variant + Clone>(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<()> {
+    let engine = context.engine();
     let len = *args[1].downcast_ref::<INT>().unwrap();
 
     // Check if array will be over max size limit
@@ -52,6 +158,226 @@ fn pad<T: Variant + Clone>(engine: &Engine, args: &mut [&mut Dynamic]) -> FuncRe
     Ok(())
 }
 
+fn resize<T: Variant + Clone>(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<()> {
+    let engine = context.engine();
+    let len = *args[1].downcast_ref::<INT>().unwrap();
+
+    if len < 0 {
+        let list = args[0].downcast_ref::<Array>().unwrap();
+        return Err(Box::new(EvalAltResult::ErrorArrayBounds(
+            list.len(),
+            len,
+            Position::none(),
+        )));
+    }
+
+    // Check if array will be over max size limit
+    #[cfg(not(feature = "unchecked"))]
+    {
+        if engine.max_array_size > 0 && (len as usize) > engine.max_array_size {
+            return Err(Box::new(EvalAltResult::ErrorDataTooLarge(
+                "Size of array".to_string(),
+                engine.max_array_size,
+                len as usize,
+                Position::none(),
+            )));
+        }
+    }
+
+    let item = args[2].clone();
+    let list = args[0].downcast_mut::<Array>().unwrap();
+    list.resize(len as usize, item);
+    Ok(())
+}
+fn append(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<()> {
+    let other = mem::take(args[1]).cast::<Array>();
+    let list = args[0].downcast_mut::<Array>().unwrap();
+
+    // Check if array will be over max size limit
+    #[cfg(not(feature = "unchecked"))]
+    {
+        let engine = context.engine();
+        let len = list.len() + other.len();
+
+        if engine.max_array_size > 0 && len > engine.max_array_size {
+            return Err(Box::new(EvalAltResult::ErrorDataTooLarge(
+                "Size of array".to_string(),
+                engine.max_array_size,
+                len,
+                Position::none(),
+            )));
+        }
+    }
+    #[cfg(feature = "unchecked")]
+    let _ = context;
+
+    list.extend(other);
+    Ok(())
+}
+fn fill<T: Variant + Clone>(list: &mut Array, item: T) -> FuncReturn<()> {
+    let item = Dynamic::from(item);
+    list.iter_mut().for_each(|x| *x = item.clone());
+    Ok(())
+}
+
+fn call_predicate(context: NativeCallContext, filter: &FnPtr, item: &Dynamic) -> FuncReturn<bool> {
+    context
+        .engine()
+        .call_fn_ptr(context, filter, &mut [&mut item.clone()])?
+        .as_bool()
+        .map_err(|type_name| {
+            Box::new(EvalAltResult::ErrorMismatchOutputType(
+                type_name.into(),
+                Position::none(),
+            ))
+        })
+}
+fn all(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<bool> {
+    let filter = args[1].downcast_ref::<FnPtr>().unwrap().clone();
+    let list = args[0].downcast_ref::<Array>().unwrap();
+
+    for item in list {
+        if !call_predicate(context, &filter, item)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+fn any(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<bool> {
+    let filter = args[1].downcast_ref::<FnPtr>().unwrap().clone();
+    let list = args[0].downcast_ref::<Array>().unwrap();
+
+    for item in list {
+        if call_predicate(context, &filter, item)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+fn none(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<bool> {
+    any(context, args).map(|result| !result)
+}
+fn count(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<INT> {
+    let filter = args[1].downcast_ref::<FnPtr>().unwrap().clone();
+    let list = args[0].downcast_ref::<Array>().unwrap();
+    let mut count = 0;
+
+    for item in list {
+        if call_predicate(context, &filter, item)? {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+fn count_value<T: Variant + Clone>(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<INT> {
+    let value = args[1].clone();
+    let list = args[0].downcast_ref::<Array>().unwrap();
+    let def_val: Dynamic = false.into();
+    let mut count = 0;
+
+    for item in list {
+        // Elements of a different type simply do not compare equal
+        let equals = context
+            .engine()
+            .call_fn_by_name(
+                context,
+                "==",
+                &mut [&mut item.clone(), &mut value.clone()],
+                Some(&def_val),
+            )?
+            .as_bool()
+            .unwrap_or(false);
+
+        if equals {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+fn extreme_by(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+    op: &str,
+) -> FuncReturn<Dynamic> {
+    let mapper = args[1].downcast_ref::<FnPtr>().unwrap().clone();
+    let list = args[0].downcast_ref::<Array>().unwrap();
+    let mut result: Option<(Dynamic, &Dynamic)> = None;
+
+    for item in list {
+        let key = context
+            .engine()
+            .call_fn_ptr(context, &mapper, &mut [&mut item.clone()])?;
+
+        // Only a strictly better key replaces the current one, so ties keep the first element
+        let better = match &result {
+            None => true,
+            Some((best, _)) => context
+                .engine()
+                .call_fn_by_name(
+                    context,
+                    op,
+                    &mut [&mut key.clone(), &mut best.clone()],
+                    None,
+                )?
+                .as_bool()
+                .map_err(|type_name| {
+                    Box::new(EvalAltResult::ErrorMismatchOutputType(
+                        type_name.into(),
+                        Position::none(),
+                    ))
+                })?,
+        };
+
+        if better {
+            result = Some((key, item));
+        }
+    }
+
+    Ok(result
+        .map(|(_, item)| item.clone())
+        .unwrap_or_else(|| ().into()))
+}
+fn min_by(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Dynamic> {
+    extreme_by(context, args, "<")
+}
+fn max_by(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Dynamic> {
+    extreme_by(context, args, ">")
+}
+#[cfg(not(feature = "no_object"))]
+fn group_by(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Map> {
+    let mapper = args[1].downcast_ref::<FnPtr>().unwrap().clone();
+    let list = args[0].downcast_ref::<Array>().unwrap();
+    let mut groups = Map::new();
+
+    for item in list {
+        let key = context
+            .engine()
+            .call_fn_ptr(context, &mapper, &mut [&mut item.clone()])?
+            .take_immutable_string()
+            .map_err(|type_name| {
+                Box::new(EvalAltResult::ErrorMismatchOutputType(
+                    type_name.into(),
+                    Position::none(),
+                ))
+            })?;
+
+        let group = groups.get_mut_or_insert(key);
+
+        match group.downcast_mut::<Array>() {
+            Some(items) => items.push(item.clone()),
+            None => *group = Dynamic::from_array(vec![item.clone()]),
+        }
+    }
+
+    Ok(groups)
+}
+
 macro_rules! reg_op {
     ($lib:expr, $op:expr, $func:ident, $($par:ty),*) => {
         $( $lib.set_fn_2_mut($op, $func::<$par>); )*
@@ -72,21 +398,31 @@ macro_rules! reg_pad {
         })*
     };
 }
+macro_rules! reg_count {
+    ($lib:expr, $op:expr, $func:ident, $($par:ty),*) => {
+        $({
+            $lib.set_fn_var_args($op,
+                &[TypeId::of::<Array>(), TypeId::of::<$par>()],
+                $func::<$par>
+            );
+        })*
+    };
+}
 
 #[cfg(not(feature = "no_index"))]
 def_package!(crate:BasicArrayPackage:"Basic array utilities.", lib, {
     reg_op!(lib, "push", push, INT, bool, char, ImmutableString, Array, ());
+    reg_op!(lib, "unshift", unshift, INT, bool, char, ImmutableString, Array, ());
+    reg_op!(lib, "fill", fill, INT, bool, char, ImmutableString, Array, ());
     reg_pad!(lib, "pad", pad, INT, bool, char, ImmutableString, Array, ());
+    reg_pad!(lib, "resize", resize, INT, bool, char, ImmutableString, Array, ());
     reg_tri!(lib, "insert", ins, INT, bool, char, ImmutableString, Array, ());
+    reg_count!(lib, "count", count_value, INT, bool, char, ImmutableString, Array, ());
 
-    lib.set_fn_2_mut("append", |x: &mut Array, y: Array| {
-        x.extend(y);
-        Ok(())
-    });
-    lib.set_fn_2_mut("+=", |x: &mut Array, y: Array| {
-        x.extend(y);
-        Ok(())
-    });
+    // In-place - the left array is extended
+    lib.set_fn_var_args("append", &[TypeId::of::<Array>(), TypeId::of::<Array>()], append);
+    lib.set_fn_var_args("+=", &[TypeId::of::<Array>(), TypeId::of::<Array>()], append);
+    // Non-mutating - a new concatenated array is returned
     lib.set_fn_2(
         "+",
         |mut x: Array, y: Array| {
@@ -99,22 +435,34 @@ def_package!(crate:BasicArrayPackage:"Basic array utilities.", lib, {
     #[cfg(not(feature = "only_i64"))]
     {
         reg_op!(lib, "push", push, i8, u8, i16, u16, i32, i64, u32, u64);
+        reg_op!(lib, "unshift", unshift, i8, u8, i16, u16, i32, i64, u32, u64);
+        reg_op!(lib, "fill", fill, i8, u8, i16, u16, i32, i64, u32, u64);
         reg_pad!(lib, "pad", pad, i8, u8, i16, u16, i32, u32, i64, u64);
+        reg_pad!(lib, "resize", resize, i8, u8, i16, u16, i32, u32, i64, u64);
         reg_tri!(lib, "insert", ins, i8, u8, i16, u16, i32, i64, u32, u64);
+        reg_count!(lib, "count", count_value, i8, u8, i16, u16, i32, i64, u32, u64);
 
         #[cfg(not(target_arch = "wasm32"))]
         {
             reg_op!(lib, "push", push, i128, u128);
+            reg_op!(lib, "unshift", unshift, i128, u128);
+            reg_op!(lib, "fill", fill, i128, u128);
             reg_pad!(lib, "pad", pad, i128, u128);
+            reg_pad!(lib, "resize", resize, i128, u128);
             reg_tri!(lib, "insert", ins, i128, u128);
+            reg_count!(lib, "count", count_value, i128, u128);
         }
     }
 
     #[cfg(not(feature = "no_float"))]
     {
         reg_op!(lib, "push", push, f32, f64);
+        reg_op!(lib, "unshift", unshift, f32, f64);
+        reg_op!(lib, "fill", fill, f32, f64);
         reg_pad!(lib, "pad", pad, f32, f64);
+        reg_pad!(lib, "resize", resize, f32, f64);
         reg_tri!(lib, "insert", ins, f32, f64);
+        reg_count!(lib, "count", count_value, f32, f64);
     }
 
     lib.set_fn_1_mut(
@@ -131,16 +479,28 @@ def_package!(crate:BasicArrayPackage:"Basic array utilities.", lib, {
             })
         },
     );
-    lib.set_fn_2_mut(
-        "remove",
-        |list: &mut Array, len: INT| {
-            Ok(if len < 0 || (len as usize) >= list.len() {
-                ().into()
-            } else {
-                list.remove(len as usize)
-            })
-        },
+    lib.set_fn_2_mut("remove", remove);
+    lib.set_fn_var_args("get", &[TypeId::of::<Array>(), TypeId::of::<INT>()], get);
+    lib.set_fn_var_args(
+        "get",
+        &[TypeId::of::<Array>(), TypeId::of::<INT>(), TypeId::of::<Dynamic>()],
+        get,
     );
+    lib.set_fn_2("zip", zip);
+    lib.set_fn_2_mut("chunk", chunk);
+    lib.set_fn_2_mut("windows", windows);
+
+    let predicate_args = &[TypeId::of::<Array>(), TypeId::of::<FnPtr>()];
+    lib.set_fn_var_args("all", predicate_args, all);
+    lib.set_fn_var_args("any", predicate_args, any);
+    lib.set_fn_var_args("none", predicate_args, none);
+    lib.set_fn_var_args("count", predicate_args, count);
+    lib.set_fn_var_args("min_by", predicate_args, min_by);
+    lib.set_fn_var_args("max_by", predicate_args, max_by);
+
+    #[cfg(not(feature = "no_object"))]
+    lib.set_fn_var_args("group_by", predicate_args, group_by);
+
     lib.set_fn_1_mut("len", |list: &mut Array| Ok(list.len() as INT));
 
     #[cfg(not(feature = "no_object"))]
@@ -150,17 +510,9 @@ def_package!(crate:BasicArrayPackage:"Basic array utilities.", lib, {
         list.clear();
         Ok(())
     });
-    lib.set_fn_2_mut(
-        "truncate",
-        |list: &mut Array, len: INT| {
-            if len >= 0 {
-                list.truncate(len as usize);
-            } else {
-                list.clear();
-            }
-            Ok(())
-        },
-    );
+    lib.set_fn_2_mut("truncate", truncate);
+    lib.set_fn_2_mut("keep_first", keep_first);
+    lib.set_fn_2_mut("keep_last", keep_last);
 
     // Register array iterator
     lib.set_iter(
@@ -1,8 +1,32 @@
 use crate::def_package;
 use crate::fn_native::FnPtr;
 
+#[cfg(not(feature = "no_index"))]
+use crate::any::Dynamic;
+#[cfg(not(feature = "no_index"))]
+use crate::engine::Array;
+#[cfg(not(feature = "no_index"))]
+use crate::fn_native::NativeCallContext;
+#[cfg(not(feature = "no_index"))]
+use crate::module::FuncReturn;
+
+#[cfg(not(feature = "no_index"))]
+use crate::stdlib::{any::TypeId, vec::Vec};
+
+/// Call a function pointer with the elements of an array as individual arguments.
+#[cfg(not(feature = "no_index"))]
+fn apply(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Dynamic> {
+    let fn_ptr = args[0].downcast_ref::<FnPtr>().unwrap().clone();
+    let mut arg_values = args[1].downcast_ref::<Array>().unwrap().clone();
+    let mut fn_args = arg_values.iter_mut().collect::<Vec<_>>();
+
+    context.engine().call_fn_ptr(context, &fn_ptr, &mut fn_args)
+}
+
 def_package!(crate:BasicFnPackage:"Basic Fn functions.", lib, {
     lib.set_fn_1_mut("name", |f: &mut FnPtr| Ok(f.get_fn_name().clone()));
     lib.set_getter_fn("name", |f: &mut FnPtr| Ok(f.get_fn_name().clone()));
 
+    #[cfg(not(feature = "no_index"))]
+    lib.set_fn_var_args("apply", &[TypeId::of::<FnPtr>(), TypeId::of::<Array>()], apply);
 });
@@ -1,5 +1,24 @@
+use crate::any::Dynamic;
 use crate::def_package;
+use crate::fn_native::{FnPtr, NativeCallContext};
 use crate::module::FuncReturn;
+use crate::parser::{ImmutableString, INT};
+use crate::stdlib::any::TypeId;
+
+#[cfg(not(feature = "no_index"))]
+use crate::engine::Array;
+
+#[cfg(not(feature = "no_object"))]
+use crate::engine::Map;
+
+#[cfg(not(feature = "no_float"))]
+use crate::stdlib::cmp::Ordering;
+
+#[cfg(not(feature = "no_float"))]
+use crate::parser::FLOAT;
+
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
 
 // Comparison operators
 pub fn lt<T: PartialOrd>(x: T, y: T) -> FuncReturn<bool> {
@@ -21,6 +40,81 @@ pub fn ne<T: PartialEq>(x: T, y: T) -> FuncReturn<bool> {
     Ok(x != y)
 }
 
+// Three-way comparison, returning -1, 0 or 1
+pub fn compare<T: Ord>(x: T, y: T) -> FuncReturn<INT> {
+    Ok(x.cmp(&y) as INT)
+}
+
+// Three-way comparison for floating-point numbers.
+// NaN sorts before all other numbers, and two NaN's compare equal.
+#[cfg(not(feature = "no_float"))]
+fn compare_float<T: PartialOrd>(x: T, y: T) -> FuncReturn<INT> {
+    #[allow(clippy::eq_op)]
+    let ordering = x.partial_cmp(&y).unwrap_or_else(|| match (x != x, y != y) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        _ => Ordering::Greater,
+    });
+    Ok(ordering as INT)
+}
+
+// Comparison operators between integers of different widths.
+// Both operands are promoted to `i128`, which can hold all values of the narrower types exactly.
+#[cfg(not(feature = "only_i32"))]
+#[cfg(not(feature = "only_i64"))]
+mod mixed_int {
+    use crate::module::FuncReturn;
+    use crate::parser::INT;
+
+    pub fn lt<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<bool> {
+        Ok(x.into() < y.into())
+    }
+    pub fn lte<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<bool> {
+        Ok(x.into() <= y.into())
+    }
+    pub fn gt<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<bool> {
+        Ok(x.into() > y.into())
+    }
+    pub fn gte<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<bool> {
+        Ok(x.into() >= y.into())
+    }
+    pub fn eq<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<bool> {
+        Ok(x.into() == y.into())
+    }
+    pub fn ne<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<bool> {
+        Ok(x.into() != y.into())
+    }
+    pub fn compare<X: Into<i128>, Y: Into<i128>>(x: X, y: Y) -> FuncReturn<INT> {
+        Ok(x.into().cmp(&y.into()) as INT)
+    }
+
+    // `u128` does not fit into `i128`, so a negative `INT` is always less than any `u128`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cmp_u128(x: u128, y: INT) -> crate::stdlib::cmp::Ordering {
+        if y < 0 {
+            crate::stdlib::cmp::Ordering::Greater
+        } else {
+            x.cmp(&(y as u128))
+        }
+    }
+}
+
+// Whether a value is a number of any kind
+fn is_number(_: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<bool> {
+    let value = &*args[0];
+
+    #[cfg(not(feature = "no_float"))]
+    if value.is::<FLOAT>() {
+        return Ok(true);
+    }
+    #[cfg(feature = "decimal")]
+    if value.is::<Decimal>() {
+        return Ok(true);
+    }
+
+    Ok(value.is::<INT>())
+}
+
 // Logic operators
 fn not(x: bool) -> FuncReturn<bool> {
     Ok(!x)
@@ -32,7 +126,49 @@ macro_rules! reg_op {
     };
 }
 
+// Register an operator for a type against `INT`, in both operand orders
+#[cfg(not(feature = "only_i32"))]
+#[cfg(not(feature = "only_i64"))]
+macro_rules! reg_mixed {
+    ($lib:expr, $op:expr, $func:ident, $($par:ty),*) => {
+        $(
+            $lib.set_fn_2($op, mixed_int::$func::<$par, INT>);
+            $lib.set_fn_2($op, mixed_int::$func::<INT, $par>);
+        )*
+    };
+}
+
+// Register a comparison operator between `u128` and `INT`, in both operand orders
+#[cfg(not(feature = "only_i32"))]
+#[cfg(not(feature = "only_i64"))]
+#[cfg(not(target_arch = "wasm32"))]
+macro_rules! reg_mixed_u128 {
+    ($lib:expr, $($op:tt),*) => {
+        $(
+            $lib.set_fn_2(stringify!($op), |x: u128, y: INT| {
+                Ok(mixed_int::cmp_u128(x, y) $op crate::stdlib::cmp::Ordering::Equal)
+            });
+            $lib.set_fn_2(stringify!($op), |x: INT, y: u128| {
+                Ok(mixed_int::cmp_u128(y, x).reverse() $op crate::stdlib::cmp::Ordering::Equal)
+            });
+        )*
+    };
+}
+
+// Register a comparison operator between `INT` and `FLOAT`, promoting the integer to `FLOAT`
+#[cfg(not(feature = "no_float"))]
+macro_rules! reg_promote_float {
+    ($lib:expr, $($op:tt),*) => {
+        $(
+            $lib.set_fn_2(stringify!($op), |x: INT, y: FLOAT| Ok((x as FLOAT) $op y));
+            $lib.set_fn_2(stringify!($op), |x: FLOAT, y: INT| Ok(x $op (y as FLOAT)));
+        )*
+    };
+}
+
 def_package!(crate:LogicPackage:"Logical operators.", lib, {
+    reg_op!(lib, "compare", compare, INT, char, bool, ImmutableString);
+
     #[cfg(not(feature = "only_i32"))]
     #[cfg(not(feature = "only_i64"))]
     {
@@ -42,6 +178,15 @@ def_package!(crate:LogicPackage:"Logical operators.", lib, {
         reg_op!(lib, ">=", gte, i8, u8, i16, u16, i32, u32, u64);
         reg_op!(lib, "==", eq, i8, u8, i16, u16, i32, u32, u64);
         reg_op!(lib, "!=", ne, i8, u8, i16, u16, i32, u32, u64);
+        reg_op!(lib, "compare", compare, i8, u8, i16, u16, i32, u32, u64);
+
+        reg_mixed!(lib, "<", lt, i8, u8, i16, u16, i32, u32, u64);
+        reg_mixed!(lib, "<=", lte, i8, u8, i16, u16, i32, u32, u64);
+        reg_mixed!(lib, ">", gt, i8, u8, i16, u16, i32, u32, u64);
+        reg_mixed!(lib, ">=", gte, i8, u8, i16, u16, i32, u32, u64);
+        reg_mixed!(lib, "==", eq, i8, u8, i16, u16, i32, u32, u64);
+        reg_mixed!(lib, "!=", ne, i8, u8, i16, u16, i32, u32, u64);
+        reg_mixed!(lib, "compare", compare, i8, u8, i16, u16, i32, u32, u64);
 
         #[cfg(not(target_arch = "wasm32"))]
         {
@@ -51,6 +196,21 @@ def_package!(crate:LogicPackage:"Logical operators.", lib, {
             reg_op!(lib, ">=", gte, i128, u128);
             reg_op!(lib, "==", eq, i128, u128);
             reg_op!(lib, "!=", ne, i128, u128);
+            reg_op!(lib, "compare", compare, i128, u128);
+
+            reg_mixed!(lib, "<", lt, i128);
+            reg_mixed!(lib, "<=", lte, i128);
+            reg_mixed!(lib, ">", gt, i128);
+            reg_mixed!(lib, ">=", gte, i128);
+            reg_mixed!(lib, "==", eq, i128);
+            reg_mixed!(lib, "!=", ne, i128);
+            reg_mixed!(lib, "compare", compare, i128);
+
+            reg_mixed_u128!(lib, <, <=, >, >=, ==, !=);
+            lib.set_fn_2("compare", |x: u128, y: INT| Ok(mixed_int::cmp_u128(x, y) as INT));
+            lib.set_fn_2("compare", |x: INT, y: u128| {
+                Ok(mixed_int::cmp_u128(y, x).reverse() as INT)
+            });
         }
     }
 
@@ -62,7 +222,45 @@ def_package!(crate:LogicPackage:"Logical operators.", lib, {
         reg_op!(lib, ">=", gte, f32);
         reg_op!(lib, "==", eq, f32);
         reg_op!(lib, "!=", ne, f32);
+
+        reg_promote_float!(lib, <, <=, >, >=, ==, !=);
+
+        reg_op!(lib, "compare", compare_float, FLOAT, f32);
+        lib.set_fn_2("compare", |x: INT, y: FLOAT| compare_float(x as FLOAT, y));
+        lib.set_fn_2("compare", |x: FLOAT, y: INT| compare_float(x, y as FLOAT));
+    }
+
+    #[cfg(feature = "decimal")]
+    {
+        reg_op!(lib, "<", lt, Decimal);
+        reg_op!(lib, "<=", lte, Decimal);
+        reg_op!(lib, ">", gt, Decimal);
+        reg_op!(lib, ">=", gte, Decimal);
+        reg_op!(lib, "==", eq, Decimal);
+        reg_op!(lib, "!=", ne, Decimal);
+        reg_op!(lib, "compare", compare, Decimal);
     }
 
     lib.set_fn_1("!", not);
+
+    // Type predicates take a `Dynamic` parameter, so they accept values of any type
+    macro_rules! reg_predicate {
+        ($lib:expr, $($name:expr => $type:ty),*) => {
+            $( $lib.set_fn_var_args($name, &[TypeId::of::<Dynamic>()],
+                |_: NativeCallContext, args: &mut [&mut Dynamic]| Ok(args[0].is::<$type>())); )*
+        };
+    }
+
+    reg_predicate!(lib, "is_unit" => (), "is_bool" => bool, "is_int" => INT, "is_char" => char);
+    reg_predicate!(lib, "is_string" => ImmutableString, "is_fn_ptr" => FnPtr);
+    lib.set_fn_var_args("is_number", &[TypeId::of::<Dynamic>()], is_number);
+
+    #[cfg(not(feature = "no_float"))]
+    reg_predicate!(lib, "is_float" => FLOAT);
+    #[cfg(feature = "decimal")]
+    reg_predicate!(lib, "is_decimal" => Decimal);
+    #[cfg(not(feature = "no_index"))]
+    reg_predicate!(lib, "is_array" => Array);
+    #[cfg(not(feature = "no_object"))]
+    reg_predicate!(lib, "is_map" => Map);
 });
@@ -3,10 +3,21 @@
 use crate::any::Dynamic;
 use crate::def_package;
 use crate::engine::Map;
+use crate::fn_native::NativeCallContext;
 use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
 
-use crate::stdlib::vec::Vec;
+#[cfg(not(feature = "no_index"))]
+use crate::engine::Array;
+#[cfg(not(feature = "no_index"))]
+use crate::result::EvalAltResult;
+#[cfg(not(feature = "no_index"))]
+use crate::token::Position;
+
+use crate::stdlib::{any::TypeId, mem, vec::Vec};
+
+#[cfg(not(feature = "no_index"))]
+use crate::stdlib::{boxed::Box, format, vec};
 
 fn map_get_keys(map: &mut Map) -> FuncReturn<Vec<Dynamic>> {
     Ok(map.iter().map(|(k, _)| k.clone().into()).collect())
@@ -15,6 +26,65 @@ fn map_get_values(map: &mut Map) -> FuncReturn<Vec<Dynamic>> {
     Ok(map.iter().map(|(_, v)| v.clone()).collect())
 }
 
+#[cfg(not(feature = "no_index"))]
+fn map_entries(map: &mut Map) -> FuncReturn<Array> {
+    Ok(map
+        .iter()
+        .map(|(k, v)| Dynamic::from_array(vec![k.clone().into(), v.clone()]))
+        .collect())
+}
+#[cfg(not(feature = "no_index"))]
+fn map_from_entries(entries: Array) -> FuncReturn<Map> {
+    let mut map = Map::with_capacity(entries.len());
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let bad_entry = || {
+            Box::new(EvalAltResult::ErrorRuntime(
+                format!(
+                    "Entry {} is not a [key, value] pair with a string key",
+                    index
+                ),
+                Position::none(),
+            ))
+        };
+
+        let mut pair = entry.try_cast::<Array>().ok_or_else(bad_entry)?;
+
+        if pair.len() != 2 {
+            return Err(bad_entry());
+        }
+
+        let value = pair.pop().unwrap();
+        let key = pair
+            .pop()
+            .unwrap()
+            .take_immutable_string()
+            .map_err(|_| bad_entry())?;
+
+        map.insert(key, value);
+    }
+
+    Ok(map)
+}
+
+// Read a property, or the default value (if any, otherwise `()`) when it does not exist
+fn map_get(_: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Dynamic> {
+    let map = args[0].downcast_ref::<Map>().unwrap();
+    let key = args[1].as_str().unwrap();
+
+    Ok(match map.get(key) {
+        Some(value) => value.clone(),
+        None if args.len() > 2 => mem::take(args[2]),
+        None => ().into(),
+    })
+}
+fn map_set(_: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<()> {
+    let key = mem::take(args[1]).take_immutable_string().unwrap();
+    let value = mem::take(args[2]);
+    args[0].downcast_mut::<Map>().unwrap().insert(key, value);
+    Ok(())
+}
+
 #[cfg(not(feature = "no_object"))]
 def_package!(crate:BasicMapPackage:"Basic object map utilities.", lib, {
     lib.set_fn_2_mut(
@@ -69,10 +139,33 @@ def_package!(crate:BasicMapPackage:"Basic object map utilities.", lib, {
         },
     );
 
+    let key_args = &[TypeId::of::<Map>(), TypeId::of::<ImmutableString>()];
+    let key_value_args = &[TypeId::of::<Map>(), TypeId::of::<ImmutableString>(), TypeId::of::<Dynamic>()];
+    lib.set_fn_var_args("get", key_args, map_get);
+    lib.set_fn_var_args("get", key_value_args, map_get);
+    lib.set_fn_var_args("set", key_value_args, map_set);
+
     // Register map access functions
     #[cfg(not(feature = "no_index"))]
     lib.set_fn_1_mut("keys", map_get_keys);
 
     #[cfg(not(feature = "no_index"))]
     lib.set_fn_1_mut("values", map_get_values);
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        lib.set_fn_1_mut("entries", map_entries);
+        lib.set_fn_1("from_entries", map_from_entries);
+    }
+
+    // Register map iterator, yielding [key, value] pairs
+    #[cfg(not(feature = "no_index"))]
+    lib.set_iter(
+        TypeId::of::<Map>(),
+        |map| Box::new(
+            map.cast::<Map>()
+                .into_iter()
+                .map(|(key, value)| Dynamic::from_array(vec![key.into(), value]))
+        ) as Box<dyn Iterator<Item = Dynamic>>,
+    );
 });
@@ -1,4 +1,5 @@
 use crate::def_package;
+use crate::module::FuncReturn;
 use crate::parser::INT;
 use crate::result::EvalAltResult;
 use crate::token::Position;
@@ -6,6 +7,18 @@ use crate::token::Position;
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(not(feature = "no_float"))]
+use crate::stdlib::f64::consts;
+
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
+#[cfg(feature = "decimal")]
+use num_traits::ToPrimitive;
+
+#[cfg(feature = "decimal")]
+use crate::stdlib::convert::TryFrom;
+
 use crate::stdlib::{boxed::Box, format, i32, i64};
 
 #[cfg(feature = "only_i32")]
@@ -13,6 +26,50 @@ pub const MAX_INT: INT = i32::MAX;
 #[cfg(not(feature = "only_i32"))]
 pub const MAX_INT: INT = i64::MAX;
 
+// Convert a floored/ceiled floating-point number into an integer, checking that it is in range
+#[cfg(not(feature = "no_float"))]
+fn rounded_to_int(fn_name: &str, x: FLOAT, value: FLOAT) -> FuncReturn<INT> {
+    if value.is_nan() {
+        Err(Box::new(EvalAltResult::ErrorArithmetic(
+            format!("Cannot convert NaN to integer: {}({})", fn_name, x),
+            Position::none(),
+        )))
+    } else if value < (INT::MIN as FLOAT) || value >= -(INT::MIN as FLOAT) {
+        Err(Box::new(EvalAltResult::ErrorArithmetic(
+            format!("Integer overflow: {}({})", fn_name, x),
+            Position::none(),
+        )))
+    } else {
+        Ok(value as INT)
+    }
+}
+
+// Round a floating-point number to a number of decimal places (negative for tens, hundreds etc.)
+#[cfg(not(feature = "no_float"))]
+fn round_to(x: FLOAT, digits: INT) -> FuncReturn<FLOAT> {
+    if !x.is_finite() {
+        return Ok(x);
+    }
+
+    // Beyond this the scale is infinite anyway - clamp to avoid overflowing i32
+    let scale = (10 as FLOAT).powi(digits.unsigned_abs().min(400) as i32);
+
+    if digits >= 0 {
+        let scaled = x * scale;
+
+        // Numbers this large have no fractional part, so there is nothing to round
+        if scaled.abs() >= 1.0 / FLOAT::EPSILON {
+            return Ok(x);
+        }
+
+        Ok(scaled.round() / scale)
+    } else if scale.is_infinite() {
+        Ok(0.0)
+    } else {
+        Ok((x / scale).round() * scale)
+    }
+}
+
 def_package!(crate:BasicMathPackage:"Basic mathematic functions.", lib, {
     #[cfg(not(feature = "no_float"))]
     {
@@ -34,6 +91,10 @@ def_package!(crate:BasicMathPackage:"Basic mathematic functions.", lib, {
         lib.set_fn_1("ln", |x: FLOAT| Ok(x.ln()));
         lib.set_fn_2("log", |x: FLOAT, base: FLOAT| Ok(x.log(base)));
         lib.set_fn_1("log10", |x: FLOAT| Ok(x.log10()));
+        lib.set_fn_3("mul_add", |x: FLOAT, a: FLOAT, b: FLOAT| Ok(x.mul_add(a, b)));
+        lib.set_fn_2("copysign", |x: FLOAT, sign: FLOAT| Ok(x.copysign(sign)));
+        lib.set_fn_1("signum", |x: FLOAT| Ok(x.signum()));
+        lib.set_fn_2("round_to", round_to);
         lib.set_fn_1("floor", |x: FLOAT| Ok(x.floor()));
         lib.set_fn_1("ceiling", |x: FLOAT| Ok(x.ceil()));
         lib.set_fn_1("round", |x: FLOAT| Ok(x.ceil()));
@@ -43,6 +104,22 @@ def_package!(crate:BasicMathPackage:"Basic mathematic functions.", lib, {
         lib.set_fn_1("is_finite", |x: FLOAT| Ok(x.is_finite()));
         lib.set_fn_1("is_infinite", |x: FLOAT| Ok(x.is_infinite()));
 
+        // Rounding to integers
+        lib.set_fn_1("floor_int", |x: FLOAT| rounded_to_int("floor_int", x, x.floor()));
+        lib.set_fn_1("ceil_int", |x: FLOAT| rounded_to_int("ceil_int", x, x.ceil()));
+
+        // Special values
+        lib.set_fn_0("inf", || Ok(FLOAT::INFINITY));
+        lib.set_fn_0("nan", || Ok(FLOAT::NAN));
+
+        // Constants
+        lib.set_fn_0("pi", || Ok(consts::PI));
+        lib.set_fn_0("e", || Ok(consts::E));
+        lib.set_fn_0("sqrt_2", || Ok(consts::SQRT_2));
+        lib.set_fn_0("ln_2", || Ok(consts::LN_2));
+        lib.set_fn_0("ln_10", || Ok(consts::LN_10));
+        lib.set_fn_0("epsilon", || Ok(FLOAT::EPSILON));
+
         #[cfg(not(feature = "no_object"))]
         {
             lib.set_getter_fn("floor", |x: &mut FLOAT| Ok(x.floor()));
@@ -79,6 +156,23 @@ def_package!(crate:BasicMathPackage:"Basic mathematic functions.", lib, {
         }
     }
 
+    #[cfg(feature = "decimal")]
+    {
+        // Register decimal conversion functions
+        lib.set_fn_1("to_decimal", |x: INT| Ok(Decimal::from(x)));
+        lib.set_fn_1("to_int", |x: Decimal| {
+            x.trunc()
+                .to_i64()
+                .and_then(|n| INT::try_from(n).ok())
+                .ok_or_else(|| {
+                    Box::new(EvalAltResult::ErrorArithmetic(
+                        format!("Integer overflow: to_int({})", x),
+                        Position::none(),
+                    ))
+                })
+        });
+    }
+
     lib.set_fn_1("to_int", |ch: char| Ok(ch as INT));
 
     #[cfg(not(feature = "only_i32"))]
@@ -16,6 +16,10 @@ mod map_basic;
 mod math_basic;
 mod pkg_core;
 mod pkg_std;
+#[cfg(feature = "rand")]
+mod rand_basic;
+#[cfg(feature = "regex")]
+mod regex_basic;
 mod string_basic;
 mod string_more;
 mod time_basic;
@@ -33,6 +37,10 @@ pub use map_basic::BasicMapPackage;
 pub use math_basic::BasicMathPackage;
 pub use pkg_core::CorePackage;
 pub use pkg_std::StandardPackage;
+#[cfg(feature = "rand")]
+pub use rand_basic::RandomPackage;
+#[cfg(feature = "regex")]
+pub use regex_basic::RegexPackage;
 pub use string_basic::BasicStringPackage;
 pub use string_more::MoreStringPackage;
 #[cfg(not(feature = "no_std"))]
@@ -85,6 +93,10 @@ impl PackagesCollection {
             .find(|f| f.is_some())
             .flatten()
     }
+    /// Get an iterator over all the packages in the `PackagesCollection`.
+    pub fn iter(&self) -> impl Iterator<Item = &PackageLibrary> {
+        self.0.iter()
+    }
 }
 
 /// Macro that makes it easy to define a _package_ (which is basically a shared module)
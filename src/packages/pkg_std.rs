@@ -4,6 +4,10 @@ use super::array_basic::BasicArrayPackage;
 use super::map_basic::BasicMapPackage;
 use super::math_basic::BasicMathPackage;
 use super::pkg_core::CorePackage;
+#[cfg(feature = "rand")]
+use super::rand_basic::RandomPackage;
+#[cfg(feature = "regex")]
+use super::regex_basic::RegexPackage;
 use super::string_more::MoreStringPackage;
 #[cfg(not(feature = "no_std"))]
 use super::time_basic::BasicTimePackage;
@@ -20,4 +24,8 @@ def_package!(crate:StandardPackage:"_Standard_ package containing all built-in f
     #[cfg(not(feature = "no_std"))]
     BasicTimePackage::init(lib);
     MoreStringPackage::init(lib);
+    #[cfg(feature = "regex")]
+    RegexPackage::init(lib);
+    #[cfg(feature = "rand")]
+    RandomPackage::init(lib);
 });
@@ -0,0 +1,142 @@
+use crate::def_package;
+use crate::fn_native::Shared;
+use crate::parser::INT;
+use crate::result::EvalAltResult;
+use crate::token::Position;
+
+#[cfg(not(feature = "no_float"))]
+use crate::parser::FLOAT;
+
+#[cfg(not(feature = "no_index"))]
+use crate::engine::Array;
+
+use crate::stdlib::{boxed::Box, format};
+
+#[cfg(not(feature = "sync"))]
+use crate::stdlib::cell::RefCell;
+
+#[cfg(feature = "sync")]
+use crate::stdlib::sync::RwLock;
+
+/// Seed used when no source of entropy is available.
+#[cfg(feature = "no_std")]
+const DEFAULT_SEED: u64 = 0x853C_49E6_748F_EA9B;
+
+/// A small seedable pseudo-random number generator (SplitMix64).
+///
+/// The algorithm is fixed, so the same seed always produces the same sequence.
+struct Rng(u64);
+
+impl Rng {
+    /// Create a generator seeded from the hasher randomness of the standard library.
+    #[cfg(not(feature = "no_std"))]
+    fn new() -> Self {
+        use crate::stdlib::collections::hash_map::RandomState;
+        use crate::stdlib::hash::{BuildHasher, Hasher};
+
+        Self(RandomState::new().build_hasher().finish())
+    }
+
+    /// Create a generator with a fixed seed (no source of entropy under `no_std`).
+    #[cfg(feature = "no_std")]
+    fn new() -> Self {
+        Self(DEFAULT_SEED)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `0..n`, or any number if `n` is zero (i.e. the full `u64` range).
+    fn below(&mut self, n: u64) -> u64 {
+        let value = self.next_u64();
+        if n == 0 {
+            value
+        } else {
+            value % n
+        }
+    }
+}
+
+/// Random number generator shared by all functions in the package.
+struct SharedRng {
+    #[cfg(not(feature = "sync"))]
+    rng: RefCell<Rng>,
+
+    #[cfg(feature = "sync")]
+    rng: RwLock<Rng>,
+}
+
+impl SharedRng {
+    fn new() -> Self {
+        Self {
+            #[cfg(not(feature = "sync"))]
+            rng: RefCell::new(Rng::new()),
+
+            #[cfg(feature = "sync")]
+            rng: RwLock::new(Rng::new()),
+        }
+    }
+
+    /// Run a function with exclusive access to the generator.
+    fn with<T>(&self, f: impl FnOnce(&mut Rng) -> T) -> T {
+        #[cfg(not(feature = "sync"))]
+        return f(&mut self.rng.borrow_mut());
+        #[cfg(feature = "sync")]
+        return f(&mut self.rng.write().unwrap());
+    }
+}
+
+def_package!(crate:RandomPackage:"Seedable random number generation.", lib, {
+    let rng = Shared::new(SharedRng::new());
+
+    let r = rng.clone();
+    lib.set_fn_1("seed", move |seed: INT| {
+        r.with(|rng| *rng = Rng(seed as u64));
+        Ok(())
+    });
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        let r = rng.clone();
+        lib.set_fn_0("rand", move || {
+            // Use the top 53 bits for a uniformly-distributed float in [0, 1)
+            let bits = r.with(|rng| rng.next_u64()) >> 11;
+            Ok(bits as FLOAT / (1_u64 << 53) as FLOAT)
+        });
+    }
+
+    let r = rng.clone();
+    lib.set_fn_2("rand_int", move |lo: INT, hi: INT| {
+        if lo > hi {
+            return Err(Box::new(EvalAltResult::ErrorRuntime(
+                format!("Invalid range for rand_int: {} is greater than {}", lo, hi),
+                Position::none(),
+            )));
+        }
+
+        // Both bounds are inclusive - a span covering the full range wraps to zero
+        let span = (hi as u64).wrapping_sub(lo as u64);
+        let offset = r.with(|rng| rng.below(span.wrapping_add(1)));
+        Ok(lo.wrapping_add(offset as INT))
+    });
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        let r = rng;
+        lib.set_fn_1_mut("shuffle", move |list: &mut Array| {
+            // Fisher-Yates shuffle
+            r.with(|rng| {
+                for i in (1..list.len()).rev() {
+                    let j = rng.below(i as u64 + 1) as usize;
+                    list.swap(i, j);
+                }
+            });
+            Ok(())
+        });
+    }
+});
@@ -0,0 +1,176 @@
+use crate::any::Dynamic;
+use crate::def_package;
+use crate::fn_native::Shared;
+use crate::module::FuncReturn;
+use crate::parser::{ImmutableString, INT};
+use crate::result::EvalAltResult;
+use crate::token::Position;
+
+#[cfg(not(feature = "no_index"))]
+use crate::engine::Array;
+
+use crate::stdlib::{
+    boxed::Box,
+    collections::HashMap,
+    format,
+    string::{String, ToString},
+};
+
+#[cfg(not(feature = "sync"))]
+use crate::stdlib::cell::RefCell;
+
+#[cfg(feature = "sync")]
+use crate::stdlib::sync::Mutex;
+
+use regex::Regex;
+
+/// Maximum number of compiled regular expressions kept in the cache.
+///
+/// Scripts may build patterns at run time, so the cache is bounded to keep memory in check.
+const MAX_CACHED_PATTERNS: usize = 64;
+
+/// Compiled regular expressions keyed by pattern string, with the tick at which each was last used.
+#[derive(Default)]
+struct CacheEntries {
+    map: HashMap<String, (Regex, u64)>,
+    tick: u64,
+}
+
+impl CacheEntries {
+    fn get(&mut self, pattern: &str) -> Option<Regex> {
+        self.tick += 1;
+        let tick = self.tick;
+
+        self.map.get_mut(pattern).map(|(re, last_used)| {
+            *last_used = tick;
+            re.clone()
+        })
+    }
+
+    fn insert(&mut self, pattern: &str, re: Regex) {
+        // Evict the least-recently-used pattern when full
+        if self.map.len() >= MAX_CACHED_PATTERNS {
+            let oldest = self
+                .map
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(key) = oldest {
+                self.map.remove(&key);
+            }
+        }
+
+        self.tick += 1;
+        self.map.insert(pattern.into(), (re, self.tick));
+    }
+}
+
+/// Cache of compiled regular expressions, keyed by pattern string.
+///
+/// Holds at most `MAX_CACHED_PATTERNS` patterns, evicting the least-recently-used one when full.
+#[derive(Default)]
+struct RegexCache {
+    #[cfg(not(feature = "sync"))]
+    cache: RefCell<CacheEntries>,
+
+    #[cfg(feature = "sync")]
+    cache: Mutex<CacheEntries>,
+}
+
+impl RegexCache {
+    /// Get the compiled regular expression for a pattern, compiling it on first use.
+    fn get(&self, pattern: &str) -> FuncReturn<Regex> {
+        #[cfg(not(feature = "sync"))]
+        let cached = self.cache.borrow_mut().get(pattern);
+        #[cfg(feature = "sync")]
+        let cached = self.cache.lock().unwrap().get(pattern);
+
+        if let Some(re) = cached {
+            return Ok(re);
+        }
+
+        let re = Regex::new(pattern).map_err(|err| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                format!("Invalid regular expression '{}': {}", pattern, err),
+                Position::none(),
+            ))
+        })?;
+
+        #[cfg(not(feature = "sync"))]
+        self.cache.borrow_mut().insert(pattern, re.clone());
+        #[cfg(feature = "sync")]
+        self.cache.lock().unwrap().insert(pattern, re.clone());
+
+        Ok(re)
+    }
+}
+
+def_package!(crate:RegexPackage:"Regular expression matching.", lib, {
+    let cache: Shared<RegexCache> = Default::default();
+
+    let c = cache.clone();
+    lib.set_fn_2_mut(
+        "regex_match",
+        move |text: &mut ImmutableString, pattern: ImmutableString| {
+            Ok(c.get(&pattern)?.is_match(text))
+        },
+    );
+
+    let c = cache.clone();
+    lib.set_fn_2_mut(
+        "regex_find",
+        move |text: &mut ImmutableString, pattern: ImmutableString| {
+            Ok(c.get(&pattern)?
+                .find(text)
+                .map(|m| Dynamic::from(m.as_str().to_string()))
+                .unwrap_or_else(|| ().into()))
+        },
+    );
+
+    // Replacements may refer to capture groups via `$1` or `${name}`
+    let c = cache.clone();
+    lib.set_fn_3_mut(
+        "regex_replace",
+        move |text: &mut ImmutableString, pattern: ImmutableString, replacement: ImmutableString| {
+            Ok(c.get(&pattern)?
+                .replace_all(text, replacement.as_str())
+                .to_string())
+        },
+    );
+
+    let c = cache.clone();
+    lib.set_fn_4_mut(
+        "regex_replace",
+        move |text: &mut ImmutableString, pattern: ImmutableString, replacement: ImmutableString, count: INT| {
+            let re = c.get(&pattern)?;
+
+            // A limit of zero means 'replace all' to the regex crate, so handle it here
+            if count <= 0 {
+                return Ok(text.to_string());
+            }
+            Ok(re.replacen(text, count as usize, replacement.as_str()).to_string())
+        },
+    );
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        let c = cache;
+        lib.set_fn_2_mut(
+            "regex_captures",
+            move |text: &mut ImmutableString, pattern: ImmutableString| {
+                Ok(c.get(&pattern)?
+                    .captures(text)
+                    .map(|caps| {
+                        caps.iter()
+                            .map(|group| match group {
+                                Some(m) => Dynamic::from(m.as_str().to_string()),
+                                None => ().into(),
+                            })
+                            .collect::<Array>()
+                    })
+                    .unwrap_or_default())
+            },
+        );
+    }
+});
@@ -1,8 +1,10 @@
+use crate::any::{Dynamic, Union};
 use crate::def_package;
-use crate::engine::{FN_TO_STRING, KEYWORD_DEBUG, KEYWORD_PRINT};
-use crate::fn_native::FnPtr;
+use crate::engine::{FN_TO_DEBUG, FN_TO_STRING, KEYWORD_DEBUG, KEYWORD_PRINT};
+use crate::fn_native::{FnPtr, NativeCallContext};
 use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
+use crate::result::EvalAltResult;
 
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
@@ -10,12 +12,20 @@ use crate::engine::Array;
 #[cfg(not(feature = "no_object"))]
 use crate::engine::Map;
 
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
 use crate::stdlib::{
+    any::TypeId,
+    boxed::Box,
     fmt::{Debug, Display},
     format,
-    string::ToString,
+    string::{String, ToString},
 };
 
+#[cfg(not(feature = "ordered_map"))]
+use crate::stdlib::vec::Vec;
+
 // Register print and debug
 fn to_debug<T: Debug>(x: &mut T) -> FuncReturn<ImmutableString> {
     Ok(format!("{:?}", x).into())
@@ -23,9 +33,100 @@ fn to_debug<T: Debug>(x: &mut T) -> FuncReturn<ImmutableString> {
 fn to_string<T: Display>(x: &mut T) -> FuncReturn<ImmutableString> {
     Ok(format!("{}", x).into())
 }
-#[cfg(not(feature = "no_object"))]
-fn format_map(x: &mut Map) -> FuncReturn<ImmutableString> {
-    Ok(format!("#{:?}", x).into())
+
+// Format a value for `print`, or for `debug` with quoted strings and type annotations,
+// recursing into arrays and object maps
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn format_value(
+    context: NativeCallContext,
+    value: &Dynamic,
+    debug: bool,
+    buf: &mut String,
+) -> Result<(), Box<EvalAltResult>> {
+    match &value.0 {
+        #[cfg(not(feature = "no_index"))]
+        Union::Array(list) => {
+            buf.push('[');
+            for (i, item) in list.iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(", ");
+                }
+                format_value(context, item, debug, buf)?;
+            }
+            buf.push(']');
+        }
+        #[cfg(not(feature = "no_object"))]
+        Union::Map(map) => {
+            // Sort the properties for a stable output, unless they are already in insertion order
+            #[cfg(not(feature = "ordered_map"))]
+            let keys = {
+                let mut keys: Vec<_> = map.keys().collect();
+                keys.sort();
+                keys
+            };
+            #[cfg(feature = "ordered_map")]
+            let keys = map.keys();
+
+            buf.push_str("#{");
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(", ");
+                }
+                if debug {
+                    buf.push_str(&format!("{:?}: ", key));
+                } else {
+                    buf.push_str(&format!("{}: ", key));
+                }
+                format_value(context, &map[key], debug, buf)?;
+            }
+            buf.push('}');
+        }
+        Union::Unit(_) => buf.push_str("()"),
+        Union::FnPtr(_) => buf.push_str(&value.to_string()),
+        // Custom types use their `to_string` function, if any, otherwise their registered names
+        Union::Variant(_) => {
+            let mut value = value.clone();
+            let result =
+                context
+                    .engine()
+                    .call_fn_by_name(context, FN_TO_STRING, &mut [&mut value], None)?;
+            buf.push_str(&result.to_string());
+        }
+        _ if debug => buf.push_str(&format!(
+            "{}({:?})",
+            context.engine().map_type_name(value.type_name()),
+            value
+        )),
+        _ => buf.push_str(&value.to_string()),
+    }
+
+    Ok(())
+}
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn print_value(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<ImmutableString> {
+    let mut buf = String::new();
+    format_value(context, args[0], false, &mut buf)?;
+    Ok(buf.into())
+}
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn debug_value(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<ImmutableString> {
+    let mut buf = String::new();
+    format_value(context, args[0], true, &mut buf)?;
+    Ok(buf.into())
+}
+
+// Values without their own string conversion (e.g. custom types) are shown by type name
+fn type_name_of(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<ImmutableString> {
+    Ok(context.engine().map_type_name(args[0].type_name()).into())
 }
 
 macro_rules! reg_op {
@@ -38,6 +139,7 @@ def_package!(crate:BasicStringPackage:"Basic string utilities, including printin
     reg_op!(lib, KEYWORD_PRINT, to_string, INT, bool, char, FnPtr);
     reg_op!(lib, FN_TO_STRING, to_string, INT, bool, char, FnPtr);
     lib.set_fn_1_mut(KEYWORD_DEBUG, |f: &mut FnPtr| Ok(f.to_string()));
+    lib.set_fn_1_mut(FN_TO_DEBUG, |f: &mut FnPtr| Ok(f.to_string()));
 
     lib.set_fn_0(KEYWORD_PRINT, || Ok("".to_string()));
     lib.set_fn_1(KEYWORD_PRINT, |_: ()| Ok("".to_string()));
@@ -47,6 +149,7 @@ def_package!(crate:BasicStringPackage:"Basic string utilities, including printin
     lib.set_fn_1(FN_TO_STRING, |s: ImmutableString| Ok(s));
 
     reg_op!(lib, KEYWORD_DEBUG, to_debug, INT, bool, (), char, ImmutableString);
+    reg_op!(lib, FN_TO_DEBUG, to_debug, INT, bool, (), char, ImmutableString);
 
     #[cfg(not(feature = "only_i32"))]
     #[cfg(not(feature = "only_i64"))]
@@ -54,15 +157,18 @@ def_package!(crate:BasicStringPackage:"Basic string utilities, including printin
         reg_op!(lib, KEYWORD_PRINT, to_string, i8, u8, i16, u16, i32, u32);
         reg_op!(lib, FN_TO_STRING, to_string, i8, u8, i16, u16, i32, u32);
         reg_op!(lib, KEYWORD_DEBUG, to_debug, i8, u8, i16, u16, i32, u32);
+        reg_op!(lib, FN_TO_DEBUG, to_debug, i8, u8, i16, u16, i32, u32);
         reg_op!(lib, KEYWORD_PRINT, to_string, i64, u64);
         reg_op!(lib, FN_TO_STRING, to_string, i64, u64);
         reg_op!(lib, KEYWORD_DEBUG, to_debug, i64, u64);
+        reg_op!(lib, FN_TO_DEBUG, to_debug, i64, u64);
 
         #[cfg(not(target_arch = "wasm32"))]
         {
             reg_op!(lib, KEYWORD_PRINT, to_string, i128, u128);
             reg_op!(lib, FN_TO_STRING, to_string, i128, u128);
             reg_op!(lib, KEYWORD_DEBUG, to_debug, i128, u128);
+            reg_op!(lib, FN_TO_DEBUG, to_debug, i128, u128);
         }
     }
 
@@ -71,22 +177,36 @@ def_package!(crate:BasicStringPackage:"Basic string utilities, including printin
         reg_op!(lib, KEYWORD_PRINT, to_string, f32, f64);
         reg_op!(lib, FN_TO_STRING, to_string, f32, f64);
         reg_op!(lib, KEYWORD_DEBUG, to_debug, f32, f64);
+        reg_op!(lib, FN_TO_DEBUG, to_debug, f32, f64);
+    }
+
+    #[cfg(feature = "decimal")]
+    {
+        reg_op!(lib, KEYWORD_PRINT, to_string, Decimal);
+        reg_op!(lib, FN_TO_STRING, to_string, Decimal);
+        reg_op!(lib, KEYWORD_DEBUG, to_debug, Decimal);
+        reg_op!(lib, FN_TO_DEBUG, to_debug, Decimal);
     }
 
     #[cfg(not(feature = "no_index"))]
     {
-        reg_op!(lib, KEYWORD_PRINT, to_debug, Array);
-        reg_op!(lib, FN_TO_STRING, to_debug, Array);
-        reg_op!(lib, KEYWORD_DEBUG, to_debug, Array);
+        lib.set_fn_var_args(KEYWORD_PRINT, &[TypeId::of::<Array>()], print_value);
+        lib.set_fn_var_args(FN_TO_STRING, &[TypeId::of::<Array>()], print_value);
+        lib.set_fn_var_args(KEYWORD_DEBUG, &[TypeId::of::<Array>()], debug_value);
+        lib.set_fn_var_args(FN_TO_DEBUG, &[TypeId::of::<Array>()], debug_value);
     }
 
     #[cfg(not(feature = "no_object"))]
     {
-        lib.set_fn_1_mut(KEYWORD_PRINT, format_map);
-        lib.set_fn_1_mut(FN_TO_STRING, format_map);
-        lib.set_fn_1_mut(KEYWORD_DEBUG, format_map);
+        lib.set_fn_var_args(KEYWORD_PRINT, &[TypeId::of::<Map>()], print_value);
+        lib.set_fn_var_args(FN_TO_STRING, &[TypeId::of::<Map>()], print_value);
+        lib.set_fn_var_args(KEYWORD_DEBUG, &[TypeId::of::<Map>()], debug_value);
+        lib.set_fn_var_args(FN_TO_DEBUG, &[TypeId::of::<Map>()], debug_value);
     }
 
+    lib.set_fn_var_args(FN_TO_STRING, &[TypeId::of::<Dynamic>()], type_name_of);
+    lib.set_fn_var_args(FN_TO_DEBUG, &[TypeId::of::<Dynamic>()], type_name_of);
+
     lib.set_fn_2("+", |s: ImmutableString, ch: char| Ok(s + ch));
     lib.set_fn_2_mut("+=", |s: &mut ImmutableString, ch: char| { *s += ch; Ok(()) });
     lib.set_fn_2_mut("append", |s: &mut ImmutableString, ch: char| { *s += ch; Ok(()) });
@@ -1,6 +1,6 @@
 use crate::any::Dynamic;
 use crate::def_package;
-use crate::engine::Engine;
+use crate::fn_native::NativeCallContext;
 use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
 use crate::result::EvalAltResult;
@@ -10,10 +10,13 @@ use crate::utils::StaticVec;
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
 
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
 use crate::stdlib::{
     any::TypeId,
     boxed::Box,
-    fmt::Display,
+    fmt::{Binary, Display, LowerHex, Octal},
     format,
     string::{String, ToString},
     vec::Vec,
@@ -25,6 +28,199 @@ fn prepend<T: Display>(x: T, y: ImmutableString) -> FuncReturn<ImmutableString>
 fn append<T: Display>(x: ImmutableString, y: T) -> FuncReturn<ImmutableString> {
     Ok(format!("{}{}", x, y).into())
 }
+fn to_hex_int<T: LowerHex>(x: T, prefix: bool) -> FuncReturn<ImmutableString> {
+    Ok(if prefix {
+        format!("{:#x}", x)
+    } else {
+        format!("{:x}", x)
+    }
+    .into())
+}
+fn to_octal_int<T: Octal>(x: T, prefix: bool) -> FuncReturn<ImmutableString> {
+    Ok(if prefix {
+        format!("{:#o}", x)
+    } else {
+        format!("{:o}", x)
+    }
+    .into())
+}
+fn to_binary_int<T: Binary>(x: T, prefix: bool) -> FuncReturn<ImmutableString> {
+    Ok(if prefix {
+        format!("{:#b}", x)
+    } else {
+        format!("{:b}", x)
+    }
+    .into())
+}
+#[cfg(not(feature = "no_index"))]
+fn array_to_bytes(list: &Array) -> FuncReturn<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(list.len());
+
+    for (index, item) in list.iter().enumerate() {
+        match item.as_int() {
+            Ok(b) if (0..=0xff).contains(&b) => bytes.push(b as u8),
+            _ => {
+                return Err(Box::new(EvalAltResult::ErrorRuntime(
+                    format!("Array element {} is not a byte: {:?}", index, item),
+                    Position::none(),
+                )))
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+#[cfg(not(feature = "no_index"))]
+fn bytes_to_array(bytes: &[u8]) -> Array {
+    bytes.iter().map(|&b| Dynamic::from(b as INT)).collect()
+}
+fn bytes_to_string(bytes: Vec<u8>) -> FuncReturn<ImmutableString> {
+    String::from_utf8(bytes).map(Into::into).map_err(|err| {
+        Box::new(EvalAltResult::ErrorRuntime(
+            format!("Invalid UTF-8 bytes: {}", err),
+            Position::none(),
+        ))
+    })
+}
+#[cfg(not(feature = "no_index"))]
+fn to_hex(list: &mut Array, upper_case: bool) -> FuncReturn<ImmutableString> {
+    let bytes = array_to_bytes(list)?;
+    let mut result = String::with_capacity(bytes.len() * 2);
+
+    for b in bytes {
+        if upper_case {
+            result.push_str(&format!("{:02X}", b));
+        } else {
+            result.push_str(&format!("{:02x}", b));
+        }
+    }
+
+    Ok(result.into())
+}
+#[cfg(not(feature = "no_index"))]
+fn from_hex(s: &mut ImmutableString) -> FuncReturn<Array> {
+    let error = |msg: String| Box::new(EvalAltResult::ErrorRuntime(msg, Position::none()));
+
+    if s.len() % 2 == 1 {
+        return Err(error(format!("Odd number of hex digits: {}", s.len())));
+    }
+
+    let digits: Vec<_> = s
+        .chars()
+        .map(|ch| {
+            ch.to_digit(16)
+                .ok_or_else(|| error(format!("Invalid hex digit: '{}'", ch)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(digits
+        .chunks(2)
+        .map(|pair| Dynamic::from((pair[0] * 16 + pair[1]) as INT))
+        .collect())
+}
+#[cfg(not(feature = "no_index"))]
+fn from_bytes(list: &mut Array) -> FuncReturn<ImmutableString> {
+    bytes_to_string(array_to_bytes(list)?)
+}
+
+const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_SAFE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode bytes into base64, with padding.
+fn base64_encode(bytes: &[u8], url_safe: bool) -> ImmutableString {
+    let alphabet = if url_safe {
+        BASE64_URL_SAFE
+    } else {
+        BASE64_STANDARD
+    };
+    let mut result = String::with_capacity(bytes.len() * 4 / 3 + 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result.into()
+}
+/// Decode base64 into bytes.  Padding is optional.
+fn base64_decode(s: &str, url_safe: bool) -> FuncReturn<Vec<u8>> {
+    let alphabet = if url_safe {
+        BASE64_URL_SAFE
+    } else {
+        BASE64_STANDARD
+    };
+    let error = |msg: String| Box::new(EvalAltResult::ErrorRuntime(msg, Position::none()));
+
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+
+    if data.len() % 4 == 1 || (padding > 0 && padding != (4 - data.len() % 4) % 4) {
+        return Err(error(format!("Invalid base64 length: {}", s.len())));
+    }
+
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let mut n = 0_u32;
+
+    for (i, ch) in data.bytes().enumerate() {
+        let value = alphabet
+            .iter()
+            .position(|&c| c == ch)
+            .ok_or_else(|| error(format!("Invalid base64 character: '{}'", ch as char)))?;
+
+        n = n << 6 | value as u32;
+
+        if i % 4 == 3 {
+            bytes.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8, n as u8]);
+            n = 0;
+        }
+    }
+
+    match data.len() % 4 {
+        2 => bytes.push((n >> 4) as u8),
+        3 => bytes.extend_from_slice(&[(n >> 10) as u8, (n >> 2) as u8]),
+        _ => (),
+    }
+
+    Ok(bytes)
+}
+// Number of characters (not bytes) in the string
+fn len(s: &mut ImmutableString) -> FuncReturn<INT> {
+    Ok(s.chars().count() as INT)
+}
+fn char_at(s: &mut ImmutableString, index: INT) -> FuncReturn<ImmutableString> {
+    let chars_len = s.chars().count();
+
+    // Negative index counts from the end
+    let offset = if index >= 0 {
+        index
+    } else {
+        chars_len as INT + index
+    };
+
+    if offset >= 0 {
+        if let Some(ch) = s.chars().nth(offset as usize) {
+            return Ok(ch.to_string().into());
+        }
+    }
+
+    Err(Box::new(EvalAltResult::ErrorStringBounds(
+        chars_len,
+        index,
+        Position::none(),
+    )))
+}
 fn sub_string(s: ImmutableString, start: INT, len: INT) -> FuncReturn<ImmutableString> {
     let offset = if s.is_empty() || len <= 0 {
         return Ok("".to_string().into());
@@ -79,12 +275,61 @@ fn crop_string(s: &mut ImmutableString, start: INT, len: INT) -> FuncReturn<()>
 
     Ok(())
 }
+/// Repeat the string argument at `s_index` the number of times given by the integer argument
+/// at `count_index`.
+fn repeat_string(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+    s_index: usize,
+    count_index: usize,
+) -> FuncReturn<ImmutableString> {
+    let count = *args[count_index].downcast_ref::<INT>().unwrap();
+    let s = args[s_index].downcast_ref::<ImmutableString>().unwrap();
+
+    if count < 0 {
+        return Err(Box::new(EvalAltResult::ErrorRuntime(
+            format!(
+                "Cannot repeat a string a negative number of times: {}",
+                count
+            ),
+            Position::none(),
+        )));
+    }
+
+    // Check if string will be over max size limit
+    #[cfg(not(feature = "unchecked"))]
+    {
+        let engine = context.engine();
+        let len = s.len().saturating_mul(count as usize);
+
+        if engine.max_string_size > 0 && len > engine.max_string_size {
+            return Err(Box::new(EvalAltResult::ErrorDataTooLarge(
+                "Length of string".to_string(),
+                engine.max_string_size,
+                len,
+                Position::none(),
+            )));
+        }
+    }
+    #[cfg(feature = "unchecked")]
+    let _ = context;
+
+    Ok(s.repeat(count as usize).into())
+}
 
 macro_rules! reg_op {
     ($lib:expr, $op:expr, $func:ident, $($par:ty),*) => {
         $( $lib.set_fn_2($op, $func::<$par>); )*
     };
 }
+macro_rules! reg_radix {
+    ($lib:expr, $op:expr, $func:ident, $($par:ty),*) => {
+        $(
+            $lib.set_fn_1($op, |x: $par| $func(x, false));
+            $lib.set_fn_2($op, $func::<$par>);
+        )*
+    };
+}
 
 def_package!(crate:MoreStringPackage:"Additional string utilities, including string building.", lib, {
     reg_op!(lib, "+", append, INT, bool, char);
@@ -93,16 +338,26 @@ def_package!(crate:MoreStringPackage:"Additional string utilities, including str
     reg_op!(lib, "+", prepend, INT, bool, char);
     lib.set_fn_2("+", |_: (), y: ImmutableString| Ok(y));
 
+    reg_radix!(lib, "to_hex", to_hex_int, INT);
+    reg_radix!(lib, "to_octal", to_octal_int, INT);
+    reg_radix!(lib, "to_binary", to_binary_int, INT);
+
     #[cfg(not(feature = "only_i32"))]
     #[cfg(not(feature = "only_i64"))]
     {
         reg_op!(lib, "+", append, i8, u8, i16, u16, i32, i64, u32, u64);
         reg_op!(lib, "+", prepend, i8, u8, i16, u16, i32, i64, u32, u64);
+        reg_radix!(lib, "to_hex", to_hex_int, i8, u8, i16, u16, i32, u32, u64);
+        reg_radix!(lib, "to_octal", to_octal_int, i8, u8, i16, u16, i32, u32, u64);
+        reg_radix!(lib, "to_binary", to_binary_int, i8, u8, i16, u16, i32, u32, u64);
 
         #[cfg(not(target_arch = "wasm32"))]
         {
             reg_op!(lib, "+", append, i128, u128);
             reg_op!(lib, "+", prepend, i128, u128);
+            reg_radix!(lib, "to_hex", to_hex_int, i128, u128);
+            reg_radix!(lib, "to_octal", to_octal_int, i128, u128);
+            reg_radix!(lib, "to_binary", to_binary_int, i128, u128);
         }
     }
 
@@ -112,16 +367,70 @@ def_package!(crate:MoreStringPackage:"Additional string utilities, including str
         reg_op!(lib, "+", prepend, f32, f64);
     }
 
+    #[cfg(feature = "decimal")]
+    {
+        reg_op!(lib, "+", append, Decimal);
+        reg_op!(lib, "+", prepend, Decimal);
+    }
+
     #[cfg(not(feature = "no_index"))]
     {
         lib.set_fn_2_mut("+", |x: &mut ImmutableString, y: Array| Ok(format!("{}{:?}", x, y)));
         lib.set_fn_2_mut("+", |x: &mut Array, y: ImmutableString| Ok(format!("{:?}{}", x, y)));
+
+        lib.set_fn_1_mut("bytes", |s: &mut ImmutableString| Ok(bytes_to_array(s.as_bytes())));
+        lib.set_fn_1_mut("from_bytes", from_bytes);
+        lib.set_fn_1_mut(
+            "lines",
+            |s: &mut ImmutableString| Ok(s.lines().map(|line| line.to_string().into()).collect::<Array>()),
+        );
+        lib.set_fn_1_mut("to_hex", |list: &mut Array| to_hex(list, false));
+        lib.set_fn_2_mut("to_hex", to_hex);
+        lib.set_fn_1_mut("from_hex", from_hex);
+
+        lib.set_fn_1_mut(
+            "base64_encode",
+            |list: &mut Array| Ok(base64_encode(&array_to_bytes(list)?, false)),
+        );
+        lib.set_fn_2_mut(
+            "base64_encode",
+            |list: &mut Array, url_safe: bool| Ok(base64_encode(&array_to_bytes(list)?, url_safe)),
+        );
+        lib.set_fn_1_mut(
+            "base64_decode_bytes",
+            |s: &mut ImmutableString| Ok(bytes_to_array(&base64_decode(s, false)?)),
+        );
+        lib.set_fn_2_mut(
+            "base64_decode_bytes",
+            |s: &mut ImmutableString, url_safe: bool| Ok(bytes_to_array(&base64_decode(s, url_safe)?)),
+        );
     }
 
-    lib.set_fn_1_mut("len", |s: &mut ImmutableString| Ok(s.chars().count() as INT));
+    lib.set_fn_1_mut(
+        "base64_encode",
+        |s: &mut ImmutableString| Ok(base64_encode(s.as_bytes(), false)),
+    );
+    lib.set_fn_2_mut(
+        "base64_encode",
+        |s: &mut ImmutableString, url_safe: bool| Ok(base64_encode(s.as_bytes(), url_safe)),
+    );
+    lib.set_fn_1_mut(
+        "base64_decode",
+        |s: &mut ImmutableString| bytes_to_string(base64_decode(s, false)?),
+    );
+    lib.set_fn_2_mut(
+        "base64_decode",
+        |s: &mut ImmutableString, url_safe: bool| bytes_to_string(base64_decode(s, url_safe)?),
+    );
+
+    lib.set_fn_1_mut("len", len);
 
     #[cfg(not(feature = "no_object"))]
-    lib.set_getter_fn("len", |s: &mut ImmutableString| Ok(s.chars().count() as INT));
+    lib.set_getter_fn("len", len);
+
+    // Alias of `len`, for clarity when the distinction from the number of bytes matters
+    lib.set_fn_1_mut("char_count", len);
+    lib.set_fn_2_mut("char_at", char_at);
 
     lib.set_fn_2_mut(
         "contains",
@@ -223,10 +532,26 @@ def_package!(crate:MoreStringPackage:"Additional string utilities, including str
             Ok(())
         },
     );
+    lib.set_fn_var_args(
+        "repeat",
+        &[TypeId::of::<ImmutableString>(), TypeId::of::<INT>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| repeat_string(context, args, 0, 1),
+    );
+    lib.set_fn_var_args(
+        "*",
+        &[TypeId::of::<ImmutableString>(), TypeId::of::<INT>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| repeat_string(context, args, 0, 1),
+    );
+    lib.set_fn_var_args(
+        "*",
+        &[TypeId::of::<INT>(), TypeId::of::<ImmutableString>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| repeat_string(context, args, 1, 0),
+    );
     lib.set_fn_var_args(
         "pad",
         &[TypeId::of::<ImmutableString>(), TypeId::of::<INT>(), TypeId::of::<char>()],
-        |engine: &Engine, args: &mut [&mut Dynamic]| {
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| {
+            let engine = context.engine();
             let len = *args[1].downcast_ref::< INT>().unwrap();
 
             // Check if string will be over max size limit
@@ -1,14 +1,25 @@
 //! Main module defining the lexer and parser.
 
-use crate::any::{Dynamic, Union};
+use crate::any::{Dynamic, Union, DEFAULT_TAG};
 use crate::calc_fn_hash;
 use crate::engine::{make_getter, make_setter, Engine, KEYWORD_THIS};
 use crate::error::{LexError, ParseError, ParseErrorType};
+use crate::fn_native::Shared;
 use crate::module::{Module, ModuleRef};
+
+#[cfg(not(feature = "no_module"))]
+use crate::module::resolvers::StaticModuleResolver;
 use crate::optimize::{optimize_into_ast, OptimizationLevel};
 use crate::scope::{EntryType as ScopeEntryType, Scope};
+use crate::syntax::{FnCustomSyntaxEval, MARKER_BLOCK, MARKER_EXPR, MARKER_IDENT};
 use crate::token::{Position, Token, TokenStream};
-use crate::utils::{StaticVec, StraightHasherBuilder};
+use crate::utils::{calc_ident_hash, IdentHash, StaticVec, StraightHasherBuilder};
+
+#[cfg(not(feature = "no_function"))]
+use crate::token::is_valid_identifier;
+
+#[cfg(not(feature = "no_object"))]
+use crate::engine::Map;
 
 use crate::stdlib::{
     borrow::Cow,
@@ -19,7 +30,7 @@ use crate::stdlib::{
     iter::empty,
     mem,
     num::NonZeroUsize,
-    ops::Add,
+    ops::{Add, AddAssign},
     string::{String, ToString},
     vec,
     vec::Vec,
@@ -43,6 +54,12 @@ pub type INT = i32;
 #[cfg(not(feature = "no_float"))]
 pub type FLOAT = f64;
 
+/// The fixed-point decimal number type.
+///
+/// Only available under the `decimal` feature.
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
 type PERR = ParseErrorType;
 
 pub use crate::utils::ImmutableString;
@@ -56,12 +73,39 @@ pub struct AST(
     Vec<Stmt>,
     /// Script-defined functions.
     Module,
+    /// Embedded modules, keyed by import path, for a self-contained [`AST`].
+    #[cfg(not(feature = "no_module"))]
+    Option<Shared<StaticModuleResolver>>,
 );
 
 impl AST {
     /// Create a new [`AST`].
     pub fn new(statements: Vec<Stmt>, lib: Module) -> Self {
-        Self(statements, lib)
+        #[cfg(not(feature = "no_module"))]
+        return Self(statements, lib, None);
+
+        #[cfg(feature = "no_module")]
+        return Self(statements, lib);
+    }
+
+    /// Get the embedded modules, if any.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn resolver(&self) -> Option<Shared<StaticModuleResolver>> {
+        self.2.clone()
+    }
+
+    /// Set the embedded modules.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn set_resolver(&mut self, resolver: Option<Shared<StaticModuleResolver>>) {
+        self.2 = resolver;
+    }
+
+    /// Are all `import` statements in this [`AST`] pre-resolved by embedded modules?
+    ///
+    /// This is the case for an [`AST`] created by `Engine::compile_into_self_contained`.
+    #[cfg(not(feature = "no_module"))]
+    pub fn is_self_contained(&self) -> bool {
+        self.2.is_some()
     }
 
     /// Get the statements.
@@ -204,7 +248,7 @@ impl AST {
         other: &Self,
         filter: impl Fn(FnAccess, &str, usize) -> bool,
     ) -> Self {
-        let Self(statements, functions) = self;
+        let Self(statements, functions, ..) = self;
 
         let ast = match (statements.is_empty(), other.0.is_empty()) {
             (false, false) => {
@@ -220,7 +264,66 @@ impl AST {
         let mut functions = functions.clone();
         functions.merge_filtered(&other.1, filter);
 
-        Self::new(ast, functions)
+        #[allow(unused_mut)]
+        let mut ast = Self::new(ast, functions);
+
+        #[cfg(not(feature = "no_module"))]
+        ast.set_resolver(merge_resolvers(self.resolver(), other.resolver()));
+
+        ast
+    }
+
+    /// Combine one [`AST`] with another.  The second [`AST`] is consumed.
+    ///
+    /// The second [`AST`] is simply appended to the end of the first _without any processing_.
+    /// Thus, the return value of the first [`AST`] (if using expression-statement syntax) is buried.
+    /// Of course, if the first [`AST`] uses a `return` statement at the end, then
+    /// the second [`AST`] will essentially be dead code.
+    ///
+    /// All script-defined functions in the second [`AST`] overwrite similarly-named functions
+    /// in the first [`AST`] with the same number of parameters.
+    ///
+    /// Unlike [`merge`](AST::merge), the first [`AST`] is modified in place, which avoids cloning
+    /// its statements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let mut ast = engine.compile(r#"
+    ///                 fn foo(x) { 42 + x }
+    ///             "#)?;
+    ///
+    /// let script = engine.compile(r#"
+    ///                 foo(1)
+    ///             "#)?;
+    ///
+    /// ast.combine(script);            // Combine 'script' into 'ast'
+    ///
+    /// // Notice that using the '+=' operator also works:
+    /// // ast += script;
+    ///
+    /// // Evaluate it
+    /// assert_eq!(engine.eval_ast::<i64>(&ast)?, 43);
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn combine(&mut self, other: Self) -> &mut Self {
+        #[cfg(not(feature = "no_module"))]
+        self.set_resolver(merge_resolvers(self.resolver(), other.resolver()));
+
+        let Self(statements, functions, ..) = other;
+
+        self.0.extend(statements);
+        self.1.merge_filtered(&functions, |_, _, _| true);
+        self
     }
 
     /// Filter out the functions, retaining only some based on a filter predicate.
@@ -251,6 +354,45 @@ impl AST {
         self.1.retain_functions(filter);
     }
 
+    /// Get an iterator over all script-defined functions in the [`AST`], in no particular order.
+    ///
+    /// Each item contains the function's access mode, name and parameter names.
+    /// The number of parameters is the length of the list of parameter names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "no_function"))]
+    /// # {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("fn add(x, y) { x + y }")?;
+    ///
+    /// let (_, name, params) = ast.iter_functions().next().unwrap();
+    ///
+    /// assert_eq!(name, "add");
+    /// assert_eq!(params, vec!["x", "y"]);
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_function"))]
+    pub fn iter_functions<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (FnAccess, &'a str, Vec<&'a str>)> + 'a {
+        self.1
+            .iter_fn()
+            .filter(|(_, _, _, _, f)| f.is_script())
+            .map(|(_, _, _, _, f)| {
+                let fn_def = f.get_fn_def();
+                let params = fn_def.params.iter().map(|p| p.as_str()).collect();
+                (fn_def.access, fn_def.name.as_str(), params)
+            })
+    }
+
     /// Clear all function definitions in the [`AST`].
     #[cfg(not(feature = "no_function"))]
     pub fn clear_functions(&mut self) {
@@ -261,6 +403,101 @@ impl AST {
     pub fn clear_statements(&mut self) {
         self.0 = vec![];
     }
+
+    /// Walk all nodes in the [`AST`] (including the bodies of script-defined functions),
+    /// calling a callback on each statement and expression in pre-order.
+    ///
+    /// ## WARNING
+    ///
+    /// This method is volatile and may change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{ASTNode, Engine, Expr};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("let x = foo(1); bar(x);")?;
+    ///
+    /// let mut calls = Vec::new();
+    ///
+    /// ast.walk(&mut |node| {
+    ///     if let ASTNode::Expr(Expr::FnCall(x)) = node {
+    ///         calls.push((x.0).0.to_string());
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(calls, vec!["foo", "bar"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "internals")]
+    pub fn walk(&self, on_node: &mut impl FnMut(&ASTNode)) {
+        self.0.iter().for_each(|stmt| stmt.walk(on_node));
+        self.1
+            .iter_script_fn()
+            .for_each(|fn_def| fn_def.body.walk(on_node));
+    }
+
+    /// Walk all nodes in the [`AST`] (including the bodies of script-defined functions),
+    /// calling a callback on each statement and expression in pre-order.
+    #[cfg(not(feature = "internals"))]
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn walk(&self, on_node: &mut impl FnMut(&ASTNode)) {
+        self.0.iter().for_each(|stmt| stmt.walk(on_node));
+        self.1
+            .iter_script_fn()
+            .for_each(|fn_def| fn_def.body.walk(on_node));
+    }
+
+    /// Render the [`AST`] as an indented tree for debugging, one node per line with its position.
+    ///
+    /// Script-defined functions are listed first (sorted by name), followed by the global statements.
+    /// An [`AST`] produced by `Engine::compile` reflects the result of script optimization.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let ast = engine.compile("let x = 42;")?;
+    ///
+    /// assert_eq!(ast.to_debug_string(), "Let x @ 1:5\n  Integer 42 @ 1:9\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_debug_string(&self) -> String {
+        let mut output = String::new();
+
+        let mut functions: Vec<_> = self.1.iter_script_fn().collect();
+        functions.sort_by(|x, y| {
+            x.name
+                .cmp(&y.name)
+                .then(x.params.len().cmp(&y.params.len()))
+        });
+
+        for fn_def in functions {
+            push_debug_line(&mut output, 0, &format!("fn {}", fn_def), fn_def.pos);
+            fn_def.body.write_debug(&mut output, 1);
+        }
+
+        self.0
+            .iter()
+            .for_each(|stmt| stmt.write_debug(&mut output, 0));
+
+        output
+    }
+}
+
+/// Append one line of the debug rendering of an [`AST`], indented by `level`.
+fn push_debug_line(output: &mut String, level: usize, text: &str, pos: Position) {
+    (0..level).for_each(|_| output.push_str("  "));
+    output.push_str(&format!("{} @ {:?}\n", text, pos));
 }
 
 impl Add<Self> for &AST {
@@ -271,6 +508,31 @@ impl Add<Self> for &AST {
     }
 }
 
+impl AddAssign<AST> for AST {
+    fn add_assign(&mut self, rhs: AST) {
+        self.combine(rhs);
+    }
+}
+
+/// Merge the embedded modules of two [`AST`]'s.  Modules in the second take precedence.
+#[cfg(not(feature = "no_module"))]
+fn merge_resolvers(
+    first: Option<Shared<StaticModuleResolver>>,
+    second: Option<Shared<StaticModuleResolver>>,
+) -> Option<Shared<StaticModuleResolver>> {
+    match (first, second) {
+        (Some(first), Some(second)) => {
+            let mut resolver = (*first).clone();
+            second
+                .iter()
+                .for_each(|(path, module)| resolver.insert(path, module.clone()));
+            Some(Shared::new(resolver))
+        }
+        (first, None) => first,
+        (None, second) => second,
+    }
+}
+
 /// A type representing the access mode of a scripted function.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum FnAccess {
@@ -298,6 +560,8 @@ pub struct ScriptFnDef {
     pub access: FnAccess,
     /// Names of function parameters.
     pub params: StaticVec<String>,
+    /// Hashes of the names of function parameters.
+    pub param_hashes: StaticVec<IdentHash>,
     /// Function body.
     pub body: Stmt,
     /// Position of the function definition.
@@ -332,36 +596,37 @@ pub enum ReturnType {
     Exception,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
-struct ParseState {
+#[derive(Clone)]
+struct ParseState<'e> {
+    /// Reference to the scripting `Engine`.
+    engine: &'e Engine,
     /// Encapsulates a local stack with variable names to simulate an actual runtime scope.
     pub stack: Vec<(String, ScopeEntryType)>,
     /// Encapsulates a local stack with variable names to simulate an actual runtime scope.
     pub modules: Vec<String>,
     /// Maximum levels of expression nesting.
     pub max_expr_depth: usize,
-    /// Maximum length of a string.
-    pub max_string_size: usize,
     /// Maximum length of an array.
     pub max_array_size: usize,
     /// Maximum number of properties in a map.
     pub max_map_size: usize,
 }
 
-impl ParseState {
+impl<'e> ParseState<'e> {
     /// Create a new `ParseState`.
     pub fn new(
+        engine: &'e Engine,
         max_expr_depth: usize,
-        max_string_size: usize,
         max_array_size: usize,
         max_map_size: usize,
     ) -> Self {
         Self {
+            engine,
+            stack: Default::default(),
+            modules: Default::default(),
             max_expr_depth,
-            max_string_size,
             max_array_size,
             max_map_size,
-            ..Default::default()
         }
     }
     /// Find a variable by name in the `ParseState`, searching in reverse.
@@ -376,6 +641,13 @@ impl ParseState {
             .find(|(_, (n, _))| *n == name)
             .and_then(|(i, _)| NonZeroUsize::new(i + 1))
     }
+    /// Get the precedence of a token, taking custom operators into account.
+    pub fn precedence(&self, token: &Token) -> u8 {
+        match token {
+            Token::Custom(op) => self.engine.custom_operators.get(op).cloned().unwrap_or(0),
+            token => token.precedence(),
+        }
+    }
     /// Find a module by name in the `ParseState`, searching in reverse.
     /// The return value is the offset to be deducted from `Stack::len`,
     /// i.e. the top element of the `ParseState` is offset 1.
@@ -427,6 +699,32 @@ impl ParseSettings {
     }
 }
 
+/// A reference to a node in an [`AST`], as visited by [`AST::walk`].
+///
+/// ## WARNING
+///
+/// This type is volatile and may change.
+#[cfg(any(feature = "internals", not(feature = "no_module")))]
+#[cfg_attr(not(feature = "internals"), allow(dead_code))]
+#[derive(Debug, Clone, Copy)]
+pub enum ASTNode<'a> {
+    /// A statement.
+    Stmt(&'a Stmt),
+    /// An expression.
+    Expr(&'a Expr),
+}
+
+#[cfg(feature = "internals")]
+impl ASTNode<'_> {
+    /// Get the `Position` of this node.
+    pub fn position(&self) -> Position {
+        match self {
+            Self::Stmt(stmt) => stmt.position(),
+            Self::Expr(expr) => expr.position(),
+        }
+    }
+}
+
 /// A statement.
 ///
 /// Each variant is at most one pointer in size (for speed),
@@ -439,16 +737,32 @@ pub enum Stmt {
     IfThenElse(Box<(Expr, Stmt, Option<Stmt>)>),
     /// while expr { stmt }
     While(Box<(Expr, Stmt)>),
+    /// while let id = expr { stmt }
+    WhileLet(Box<((String, Position), Expr, Stmt, IdentHash)>),
     /// loop { stmt }
     Loop(Box<Stmt>),
-    /// for id in expr { stmt }
-    For(Box<(String, Expr, Stmt)>),
+    /// for id in expr { stmt } or for (counter, id) in expr { stmt }
+    For(
+        Box<(
+            (String, Option<String>),
+            Expr,
+            Stmt,
+            (IdentHash, Option<IdentHash>),
+        )>,
+    ),
     /// let id = expr
-    Let(Box<((String, Position), Option<Expr>)>),
+    Let(Box<((String, Position), Option<Expr>, IdentHash)>),
+    /// try { stmt; ... } finally { stmt; ... }
+    TryFinally(Box<(Stmt, Stmt, Position)>),
+    /// let (id, ...) = expr
+    #[allow(clippy::type_complexity)]
+    LetTuple(Box<(StaticVec<(String, Position, IdentHash)>, Expr, Position)>),
     /// const id = expr
-    Const(Box<((String, Position), Expr)>),
+    Const(Box<((String, Position), Expr, IdentHash)>),
     /// { stmt; ... }
     Block(Box<(StaticVec<Stmt>, Position)>),
+    /// #[unchecked] { stmt; ... }
+    Unchecked(Box<(Stmt, Position)>),
     /// { stmt }
     Expr(Box<Expr>),
     /// continue
@@ -475,12 +789,16 @@ impl Stmt {
         match self {
             Stmt::Noop(pos) | Stmt::Continue(pos) | Stmt::Break(pos) => *pos,
             Stmt::Let(x) => (x.0).1,
+            Stmt::LetTuple(x) => x.2,
+            Stmt::TryFinally(x) => x.2,
             Stmt::Const(x) => (x.0).1,
             Stmt::ReturnWithVal(x) => (x.0).1,
             Stmt::Block(x) => x.1,
+            Stmt::Unchecked(x) => x.1,
             Stmt::IfThenElse(x) => x.0.position(),
             Stmt::Expr(x) => x.position(),
             Stmt::While(x) => x.1.position(),
+            Stmt::WhileLet(x) => x.2.position(),
             Stmt::Loop(x) => x.position(),
             Stmt::For(x) => x.2.position(),
             Stmt::Import(x) => (x.1).1,
@@ -488,19 +806,172 @@ impl Stmt {
         }
     }
 
+    /// Walk this statement and all nodes within it, in pre-order.
+    #[cfg(any(feature = "internals", not(feature = "no_module")))]
+    fn walk(&self, on_node: &mut impl FnMut(&ASTNode)) {
+        on_node(&ASTNode::Stmt(self));
+
+        match self {
+            Stmt::Noop(_) | Stmt::Continue(_) | Stmt::Break(_) | Stmt::Export(_) => (),
+            Stmt::IfThenElse(x) => {
+                x.0.walk(on_node);
+                x.1.walk(on_node);
+                if let Some(stmt) = &x.2 {
+                    stmt.walk(on_node);
+                }
+            }
+            Stmt::While(x) => {
+                x.0.walk(on_node);
+                x.1.walk(on_node);
+            }
+            Stmt::WhileLet(x) => {
+                x.1.walk(on_node);
+                x.2.walk(on_node);
+            }
+            Stmt::Loop(x) => x.walk(on_node),
+            Stmt::For(x) => {
+                x.1.walk(on_node);
+                x.2.walk(on_node);
+            }
+            Stmt::Let(x) => {
+                if let Some(expr) = &x.1 {
+                    expr.walk(on_node);
+                }
+            }
+            Stmt::LetTuple(x) => x.1.walk(on_node),
+            Stmt::TryFinally(x) => {
+                x.0.walk(on_node);
+                x.1.walk(on_node);
+            }
+            Stmt::Const(x) => x.1.walk(on_node),
+            Stmt::Block(x) => x.0.iter().for_each(|stmt| stmt.walk(on_node)),
+            Stmt::Unchecked(x) => x.0.walk(on_node),
+            Stmt::Expr(x) => x.walk(on_node),
+            Stmt::ReturnWithVal(x) => {
+                if let Some(expr) = &x.1 {
+                    expr.walk(on_node);
+                }
+            }
+            Stmt::Import(x) => x.0.walk(on_node),
+        }
+    }
+
+    /// Write the debug rendering of this statement, indented by `level`.
+    fn write_debug(&self, output: &mut String, level: usize) {
+        let pos = self.position();
+
+        match self {
+            Stmt::Noop(_) => push_debug_line(output, level, "Noop", pos),
+            Stmt::IfThenElse(x) => {
+                push_debug_line(output, level, "If", pos);
+                x.0.write_debug(output, level + 1);
+                x.1.write_debug(output, level + 1);
+                if let Some(stmt) = &x.2 {
+                    push_debug_line(output, level, "Else", stmt.position());
+                    stmt.write_debug(output, level + 1);
+                }
+            }
+            Stmt::While(x) => {
+                push_debug_line(output, level, "While", pos);
+                x.0.write_debug(output, level + 1);
+                x.1.write_debug(output, level + 1);
+            }
+            Stmt::WhileLet(x) => {
+                push_debug_line(output, level, &format!("WhileLet {}", (x.0).0), pos);
+                x.1.write_debug(output, level + 1);
+                x.2.write_debug(output, level + 1);
+            }
+            Stmt::Loop(x) => {
+                push_debug_line(output, level, "Loop", pos);
+                x.write_debug(output, level + 1);
+            }
+            Stmt::For(x) => {
+                let text = match &(x.0).1 {
+                    Some(counter) => format!("For ({}, {})", (x.0).0, counter),
+                    None => format!("For {}", (x.0).0),
+                };
+                push_debug_line(output, level, &text, pos);
+                x.1.write_debug(output, level + 1);
+                x.2.write_debug(output, level + 1);
+            }
+            Stmt::Let(x) => {
+                push_debug_line(output, level, &format!("Let {}", (x.0).0), pos);
+                if let Some(expr) = &x.1 {
+                    expr.write_debug(output, level + 1);
+                }
+            }
+            Stmt::TryFinally(x) => {
+                push_debug_line(output, level, "Try", pos);
+                x.0.write_debug(output, level + 1);
+                push_debug_line(output, level, "Finally", x.1.position());
+                x.1.write_debug(output, level + 1);
+            }
+            Stmt::LetTuple(x) => {
+                let names: Vec<_> = x.0.iter().map(|(name, _, _)| name.as_str()).collect();
+                let text = format!("Let ({})", names.join(", "));
+                push_debug_line(output, level, &text, pos);
+                x.1.write_debug(output, level + 1);
+            }
+            Stmt::Const(x) => {
+                push_debug_line(output, level, &format!("Const {}", (x.0).0), pos);
+                x.1.write_debug(output, level + 1);
+            }
+            Stmt::Block(x) => {
+                push_debug_line(output, level, "Block", pos);
+                x.0.iter()
+                    .for_each(|stmt| stmt.write_debug(output, level + 1));
+            }
+            Stmt::Unchecked(x) => {
+                push_debug_line(output, level, "Unchecked", pos);
+                x.0.write_debug(output, level + 1);
+            }
+            Stmt::Expr(x) => x.write_debug(output, level),
+            Stmt::Continue(_) => push_debug_line(output, level, "Continue", pos),
+            Stmt::Break(_) => push_debug_line(output, level, "Break", pos),
+            Stmt::ReturnWithVal(x) => {
+                let text = match (x.0).0 {
+                    ReturnType::Return => "Return",
+                    ReturnType::Exception => "Throw",
+                };
+                push_debug_line(output, level, text, pos);
+                if let Some(expr) = &x.1 {
+                    expr.write_debug(output, level + 1);
+                }
+            }
+            Stmt::Import(x) => {
+                push_debug_line(output, level, &format!("Import as {}", (x.1).0), pos);
+                x.0.write_debug(output, level + 1);
+            }
+            Stmt::Export(x) => {
+                let names: Vec<_> = x
+                    .iter()
+                    .map(|((name, _), alias)| match alias {
+                        Some((alias, _)) => format!("{} as {}", name, alias),
+                        None => name.clone(),
+                    })
+                    .collect();
+                push_debug_line(output, level, &format!("Export {}", names.join(", ")), pos);
+            }
+        }
+    }
+
     /// Is this statement self-terminated (i.e. no need for a semicolon terminator)?
     pub fn is_self_terminated(&self) -> bool {
         match self {
             Stmt::IfThenElse(_)
             | Stmt::While(_)
+            | Stmt::WhileLet(_)
             | Stmt::Loop(_)
             | Stmt::For(_)
-            | Stmt::Block(_) => true,
+            | Stmt::Block(_)
+            | Stmt::Unchecked(_)
+            | Stmt::TryFinally(_) => true,
 
             // A No-op requires a semicolon in order to know it is an empty statement!
             Stmt::Noop(_) => false,
 
             Stmt::Let(_)
+            | Stmt::LetTuple(_)
             | Stmt::Const(_)
             | Stmt::Import(_)
             | Stmt::Export(_)
@@ -521,10 +992,13 @@ impl Stmt {
             }
             Stmt::IfThenElse(x) => x.1.is_pure(),
             Stmt::While(x) => x.0.is_pure() && x.1.is_pure(),
+            Stmt::WhileLet(x) => x.1.is_pure() && x.2.is_pure(),
             Stmt::Loop(x) => x.is_pure(),
             Stmt::For(x) => x.1.is_pure() && x.2.is_pure(),
-            Stmt::Let(_) | Stmt::Const(_) => false,
+            Stmt::Let(_) | Stmt::LetTuple(_) | Stmt::Const(_) => false,
             Stmt::Block(x) => x.0.iter().all(Stmt::is_pure),
+            Stmt::Unchecked(x) => x.0.is_pure(),
+            Stmt::TryFinally(x) => x.0.is_pure() && x.1.is_pure(),
             Stmt::Continue(_) | Stmt::Break(_) | Stmt::ReturnWithVal(_) => false,
             Stmt::Import(_) => false,
             Stmt::Export(_) => false,
@@ -543,17 +1017,21 @@ pub enum Expr {
     /// Floating-point constant.
     #[cfg(not(feature = "no_float"))]
     FloatConstant(Box<(FLOAT, Position)>),
+    /// Decimal constant.
+    #[cfg(feature = "decimal")]
+    DecimalConstant(Box<(Decimal, Position)>),
     /// Character constant.
     CharConstant(Box<(char, Position)>),
     /// String constant.
     StringConstant(Box<(ImmutableString, Position)>),
-    /// Variable access - ((variable name, position), optional modules, hash, optional index)
+    /// Variable access - ((variable name, position), optional modules, hash, optional index, name hash)
     Variable(
         Box<(
             (String, Position),
             Option<Box<ModuleRef>>,
             u64,
             Option<NonZeroUsize>,
+            IdentHash,
         )>,
     ),
     /// Property access.
@@ -586,6 +1064,13 @@ pub enum Expr {
     Map(Box<(StaticVec<((ImmutableString, Position), Expr)>, Position)>),
     /// lhs in rhs
     In(Box<(Expr, Expr, Position)>),
+    /// lhs < expr < expr ... - ((operands), (operator name, hash, position) of each comparison)
+    CmpChain(
+        Box<(
+            StaticVec<Expr>,
+            StaticVec<(Cow<'static, str>, u64, Position)>,
+        )>,
+    ),
     /// lhs && rhs
     And(Box<(Expr, Expr, Position)>),
     /// lhs || rhs
@@ -596,6 +1081,29 @@ pub enum Expr {
     False(Position),
     /// ()
     Unit(Position),
+    /// Custom syntax
+    Custom(Box<(CustomExpr, Position)>),
+}
+
+/// A custom syntax expression.
+#[derive(Clone)]
+pub struct CustomExpr(StaticVec<Expr>, Shared<FnCustomSyntaxEval>);
+
+impl fmt::Debug for CustomExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl CustomExpr {
+    /// Get the sub-expressions matched by the markers in the custom syntax.
+    pub fn inputs(&self) -> &[Expr] {
+        self.0.as_ref()
+    }
+    /// Get the evaluation function of the custom syntax.
+    pub fn func(&self) -> &FnCustomSyntaxEval {
+        self.1.as_ref()
+    }
 }
 
 impl Default for Expr {
@@ -617,6 +1125,8 @@ impl Expr {
             Self::IntegerConstant(x) => x.0.into(),
             #[cfg(not(feature = "no_float"))]
             Self::FloatConstant(x) => x.0.into(),
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(x) => x.0.into(),
             Self::CharConstant(x) => x.0.into(),
             Self::StringConstant(x) => x.0.clone().into(),
             Self::True(_) => true.into(),
@@ -624,18 +1134,22 @@ impl Expr {
             Self::Unit(_) => ().into(),
 
             #[cfg(not(feature = "no_index"))]
-            Self::Array(x) if x.0.iter().all(Self::is_constant) => Dynamic(Union::Array(Box::new(
-                x.0.iter().map(Self::get_constant_value).collect::<Vec<_>>(),
-            ))),
+            Self::Array(x) if x.0.iter().all(Self::is_constant) => Dynamic(
+                Union::Array(Box::new(
+                    x.0.iter().map(Self::get_constant_value).collect::<Vec<_>>(),
+                )),
+                DEFAULT_TAG,
+            ),
 
             #[cfg(not(feature = "no_object"))]
-            Self::Map(x) if x.0.iter().all(|(_, v)| v.is_constant()) => {
-                Dynamic(Union::Map(Box::new(
+            Self::Map(x) if x.0.iter().all(|(_, v)| v.is_constant()) => Dynamic(
+                Union::Map(Box::new(
                     x.0.iter()
                         .map(|((k, _), v)| (k.clone(), v.get_constant_value()))
-                        .collect::<HashMap<_, _>>(),
-                )))
-            }
+                        .collect::<Map>(),
+                )),
+                DEFAULT_TAG,
+            ),
 
             _ => unreachable!("cannot get value of non-constant expression"),
         }
@@ -652,6 +1166,8 @@ impl Expr {
 
             #[cfg(not(feature = "no_float"))]
             Self::FloatConstant(x) => x.0.to_string(),
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(x) => x.0.to_string(),
 
             Self::IntegerConstant(x) => x.0.to_string(),
             Self::CharConstant(x) => x.0.to_string(),
@@ -673,6 +1189,8 @@ impl Expr {
 
             #[cfg(not(feature = "no_float"))]
             Self::FloatConstant(x) => x.1,
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(x) => x.1,
 
             Self::IntegerConstant(x) => x.1,
             Self::CharConstant(x) => x.1,
@@ -684,15 +1202,113 @@ impl Expr {
             Self::Variable(x) => (x.0).1,
             Self::FnCall(x) => (x.0).2,
             Self::Assignment(x) => x.0.position(),
+            Self::CmpChain(x) => x.1[0].2,
 
             Self::And(x) | Self::Or(x) | Self::In(x) => x.2,
 
             Self::True(pos) | Self::False(pos) | Self::Unit(pos) => *pos,
 
             Self::Dot(x) | Self::Index(x) => x.0.position(),
+
+            Self::Custom(x) => x.1,
         }
     }
 
+    /// Walk this expression and all nodes within it, in pre-order.
+    #[cfg(any(feature = "internals", not(feature = "no_module")))]
+    fn walk(&self, on_node: &mut impl FnMut(&ASTNode)) {
+        on_node(&ASTNode::Expr(self));
+
+        match self {
+            Self::Expr(x) => x.walk(on_node),
+            Self::Stmt(x) => x.0.walk(on_node),
+            Self::FnCall(x) => x.3.iter().for_each(|expr| expr.walk(on_node)),
+            Self::CmpChain(x) => x.0.iter().for_each(|expr| expr.walk(on_node)),
+            Self::Assignment(x) => {
+                x.0.walk(on_node);
+                x.2.walk(on_node);
+            }
+            Self::Dot(x) | Self::Index(x) | Self::In(x) | Self::And(x) | Self::Or(x) => {
+                x.0.walk(on_node);
+                x.1.walk(on_node);
+            }
+            Self::Array(x) => x.0.iter().for_each(|expr| expr.walk(on_node)),
+            Self::Map(x) => x.0.iter().for_each(|(_, expr)| expr.walk(on_node)),
+            Self::Custom(x) => x.0.inputs().iter().for_each(|expr| expr.walk(on_node)),
+            _ => (),
+        }
+    }
+
+    /// Write the debug rendering of this expression, indented by `level`.
+    fn write_debug(&self, output: &mut String, level: usize) {
+        let pos = self.position();
+
+        let (text, children): (String, StaticVec<&Expr>) = match self {
+            // The wrapper only affects optimization, so show the wrapped expression instead
+            Self::Expr(x) => return x.write_debug(output, level),
+
+            Self::IntegerConstant(x) => (format!("Integer {}", x.0), Default::default()),
+            #[cfg(not(feature = "no_float"))]
+            Self::FloatConstant(x) => (format!("Float {}", x.0), Default::default()),
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(x) => (format!("Decimal {}", x.0), Default::default()),
+            Self::CharConstant(x) => (format!("Char {:?}", x.0), Default::default()),
+            Self::StringConstant(x) => (format!("String {:?}", x.0.as_str()), Default::default()),
+            Self::Variable(x) => {
+                let ((name, _), modules, _, _, _) = x.as_ref();
+                let text = match modules {
+                    Some(modules) => format!("Variable {}{}", modules, name),
+                    None => format!("Variable {}", name),
+                };
+                (text, Default::default())
+            }
+            Self::Property(x) => (format!("Property {}", (x.0).0), Default::default()),
+            Self::Stmt(x) => {
+                push_debug_line(output, level, "Stmt", pos);
+                return x.0.write_debug(output, level + 1);
+            }
+            Self::FnCall(x) => {
+                let ((name, _, _), modules, _, args, _) = x.as_ref();
+                let text = match modules {
+                    Some(modules) => format!("FnCall {}{}", modules, name),
+                    None => format!("FnCall {}", name),
+                };
+                (text, args.iter().collect())
+            }
+            Self::Assignment(x) => (
+                format!("Assignment {}", x.1),
+                [&x.0, &x.2].iter().cloned().collect(),
+            ),
+            Self::Dot(x) => ("Dot".into(), [&x.0, &x.1].iter().cloned().collect()),
+            Self::Index(x) => ("Index".into(), [&x.0, &x.1].iter().cloned().collect()),
+            Self::Array(x) => ("Array".into(), x.0.iter().collect()),
+            Self::Map(x) => {
+                push_debug_line(output, level, "Map", pos);
+                for ((name, name_pos), expr) in x.0.iter() {
+                    push_debug_line(output, level + 1, &format!("Property {}", name), *name_pos);
+                    expr.write_debug(output, level + 2);
+                }
+                return;
+            }
+            Self::In(x) => ("In".into(), [&x.0, &x.1].iter().cloned().collect()),
+            Self::CmpChain(x) => {
+                let ops: Vec<_> = x.1.iter().map(|(op, _, _)| op.as_ref()).collect();
+                (format!("CmpChain {}", ops.join(" ")), x.0.iter().collect())
+            }
+            Self::And(x) => ("And".into(), [&x.0, &x.1].iter().cloned().collect()),
+            Self::Or(x) => ("Or".into(), [&x.0, &x.1].iter().cloned().collect()),
+            Self::True(_) => ("true".into(), Default::default()),
+            Self::False(_) => ("false".into(), Default::default()),
+            Self::Unit(_) => ("()".into(), Default::default()),
+            Self::Custom(x) => ("Custom".into(), x.0.inputs().iter().collect()),
+        };
+
+        push_debug_line(output, level, &text, pos);
+        children
+            .iter()
+            .for_each(|expr| expr.write_debug(output, level + 1));
+    }
+
     /// Override the `Position` of the expression.
     pub(crate) fn set_position(mut self, new_pos: Position) -> Self {
         match &mut self {
@@ -703,6 +1319,8 @@ impl Expr {
 
             #[cfg(not(feature = "no_float"))]
             Self::FloatConstant(x) => x.1 = new_pos,
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(x) => x.1 = new_pos,
 
             Self::IntegerConstant(x) => x.1 = new_pos,
             Self::CharConstant(x) => x.1 = new_pos,
@@ -713,6 +1331,7 @@ impl Expr {
             Self::Property(x) => x.1 = new_pos,
             Self::Stmt(x) => x.1 = new_pos,
             Self::FnCall(x) => (x.0).2 = new_pos,
+            Self::CmpChain(x) => x.1[0].2 = new_pos,
             Self::And(x) => x.2 = new_pos,
             Self::Or(x) => x.2 = new_pos,
             Self::In(x) => x.2 = new_pos,
@@ -722,6 +1341,7 @@ impl Expr {
             Self::Assignment(x) => x.3 = new_pos,
             Self::Dot(x) => x.2 = new_pos,
             Self::Index(x) => x.2 = new_pos,
+            Self::Custom(x) => x.1 = new_pos,
         }
 
         self
@@ -756,6 +1376,8 @@ impl Expr {
 
             #[cfg(not(feature = "no_float"))]
             Self::FloatConstant(_) => true,
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(_) => true,
 
             Self::IntegerConstant(_)
             | Self::CharConstant(_)
@@ -788,16 +1410,20 @@ impl Expr {
 
             #[cfg(not(feature = "no_float"))]
             Self::FloatConstant(_) => false,
+            #[cfg(feature = "decimal")]
+            Self::DecimalConstant(_) => false,
 
             Self::IntegerConstant(_)
             | Self::CharConstant(_)
             | Self::In(_)
+            | Self::CmpChain(_)
             | Self::And(_)
             | Self::Or(_)
             | Self::True(_)
             | Self::False(_)
             | Self::Unit(_)
-            | Self::Assignment(_) => false,
+            | Self::Assignment(_)
+            | Self::Custom(_) => false,
 
             Self::StringConstant(_)
             | Self::Stmt(_)
@@ -885,10 +1511,15 @@ fn parse_paren_expr(
     match input.next().unwrap() {
         // ( xxx )
         (Token::RightParen, _) => Ok(expr),
+        // ( xxx <EOF>
+        (Token::EOF, pos) => {
+            Err(PERR::UnclosedDelimiter(Token::LeftParen.into(), settings.pos).into_err(pos))
+        }
         // ( <error>
         (Token::LexError(err), pos) => return Err(err.into_err(pos)),
         // ( xxx ???
-        (_, pos) => Err(PERR::MissingToken(
+        (t, pos) => Err(PERR::UnexpectedToken(
+            t.syntax().into(),
             Token::RightParen.into(),
             "for a matching ( in this expression".into(),
         )
@@ -912,11 +1543,9 @@ fn parse_call_expr(
     match token {
         // id( <EOF>
         Token::EOF => {
-            return Err(PERR::MissingToken(
-                Token::RightParen.into(),
-                format!("to close the arguments list of this function call '{}'", id),
+            return Err(
+                PERR::UnclosedDelimiter(Token::LeftParen.into(), settings.pos).into_err(*token_pos),
             )
-            .into_err(*token_pos))
         }
         // id( <error>
         Token::LexError(err) => return Err(err.into_err(*token_pos)),
@@ -996,17 +1625,16 @@ fn parse_call_expr(
             }
             // id(...args <EOF>
             (Token::EOF, pos) => {
-                return Err(PERR::MissingToken(
-                    Token::RightParen.into(),
-                    format!("to close the arguments list of this function call '{}'", id),
+                return Err(
+                    PERR::UnclosedDelimiter(Token::LeftParen.into(), settings.pos).into_err(*pos),
                 )
-                .into_err(*pos))
             }
             // id(...args <error>
             (Token::LexError(err), pos) => return Err(err.into_err(*pos)),
             // id(...args ???
-            (_, pos) => {
-                return Err(PERR::MissingToken(
+            (t, pos) => {
+                return Err(PERR::UnexpectedToken(
+                    t.syntax().into(),
                     Token::Comma.into(),
                     format!("to separate the arguments to function call '{}'", id),
                 )
@@ -1031,13 +1659,6 @@ fn parse_index_chain(
     // Check type of indexing - must be integer or string
     match &idx_expr {
         // lhs[int]
-        Expr::IntegerConstant(x) if x.0 < 0 => {
-            return Err(PERR::MalformedIndexExpr(format!(
-                "Array access expects non-negative index: {} < 0",
-                x.0
-            ))
-            .into_err(x.1))
-        }
         Expr::IntegerConstant(x) => match lhs {
             Expr::Array(_) | Expr::StringConstant(_) => (),
 
@@ -1117,6 +1738,14 @@ fn parse_index_chain(
             )
             .into_err(x.position()))
         }
+        // lhs[decimal]
+        #[cfg(feature = "decimal")]
+        x @ Expr::DecimalConstant(_) => {
+            return Err(PERR::MalformedIndexExpr(
+                "Array access expects integer index, not a decimal".into(),
+            )
+            .into_err(x.position()))
+        }
         // lhs[char]
         x @ Expr::CharConstant(_) => {
             return Err(PERR::MalformedIndexExpr(
@@ -1186,8 +1815,12 @@ fn parse_index_chain(
                 }
             }
         }
+        (Token::EOF, pos) => {
+            Err(PERR::UnclosedDelimiter(Token::LeftBracket.into(), settings.pos).into_err(*pos))
+        }
         (Token::LexError(err), pos) => return Err(err.into_err(*pos)),
-        (_, pos) => Err(PERR::MissingToken(
+        (t, pos) => Err(PERR::UnexpectedToken(
+            t.syntax().into(),
             Token::RightBracket.into(),
             "for a matching [ in this index expression".into(),
         )
@@ -1205,7 +1838,7 @@ fn parse_array_literal(
 
     let mut arr = StaticVec::new();
 
-    while !input.peek().unwrap().0.is_eof() {
+    loop {
         if state.max_array_size > 0 && arr.len() >= state.max_array_size {
             return Err(PERR::LiteralTooLarge(
                 "Size of array literal".to_string(),
@@ -1219,6 +1852,11 @@ fn parse_array_literal(
                 eat_token(input, Token::RightBracket);
                 break;
             }
+            (Token::EOF, pos) => {
+                return Err(
+                    PERR::UnclosedDelimiter(Token::LeftBracket.into(), settings.pos).into_err(*pos),
+                )
+            }
             _ => {
                 let expr = parse_expr(input, state, settings.level_up())?;
                 arr.push(expr);
@@ -1231,15 +1869,14 @@ fn parse_array_literal(
             }
             (Token::RightBracket, _) => (),
             (Token::EOF, pos) => {
-                return Err(PERR::MissingToken(
-                    Token::RightBracket.into(),
-                    "to end this array literal".into(),
+                return Err(
+                    PERR::UnclosedDelimiter(Token::LeftBracket.into(), settings.pos).into_err(*pos),
                 )
-                .into_err(*pos))
             }
             (Token::LexError(err), pos) => return Err(err.into_err(*pos)),
-            (_, pos) => {
-                return Err(PERR::MissingToken(
+            (t, pos) => {
+                return Err(PERR::UnexpectedToken(
+                    t.syntax().into(),
                     Token::Comma.into(),
                     "to separate the items of this array literal".into(),
                 )
@@ -1261,7 +1898,7 @@ fn parse_map_literal(
 
     let mut map = StaticVec::new();
 
-    while !input.peek().unwrap().0.is_eof() {
+    loop {
         const MISSING_RBRACE: &str = "to end this object map literal";
 
         match input.peek().unwrap() {
@@ -1274,15 +1911,15 @@ fn parse_map_literal(
                     (Token::Identifier(s), pos) => (s, pos),
                     (Token::StringConst(s), pos) => (s, pos),
                     (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-                    (_, pos) if map.is_empty() => {
-                        return Err(PERR::MissingToken(
-                            Token::RightBrace.into(),
-                            MISSING_RBRACE.into(),
+                    (Token::EOF, pos) => {
+                        return Err(
+                            PERR::UnclosedDelimiter(Token::MapStart.into(), settings.pos)
+                                .into_err(pos),
                         )
-                        .into_err(pos))
                     }
-                    (Token::EOF, pos) => {
-                        return Err(PERR::MissingToken(
+                    (t, pos) if map.is_empty() => {
+                        return Err(PERR::UnexpectedToken(
+                            t.syntax().into(),
                             Token::RightBrace.into(),
                             MISSING_RBRACE.into(),
                         )
@@ -1294,8 +1931,9 @@ fn parse_map_literal(
                 match input.next().unwrap() {
                     (Token::Colon, _) => (),
                     (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-                    (_, pos) => {
-                        return Err(PERR::MissingToken(
+                    (t, pos) => {
+                        return Err(PERR::UnexpectedToken(
+                            t.syntax().into(),
                             Token::Colon.into(),
                             format!(
                                 "to follow the property '{}' in this object map literal",
@@ -1324,19 +1962,27 @@ fn parse_map_literal(
                 eat_token(input, Token::Comma);
             }
             (Token::RightBrace, _) => (),
-            (Token::Identifier(_), pos) => {
-                return Err(PERR::MissingToken(
+            (Token::EOF, pos) => {
+                return Err(
+                    PERR::UnclosedDelimiter(Token::MapStart.into(), settings.pos).into_err(*pos),
+                )
+            }
+            (t @ Token::Identifier(_), pos) => {
+                return Err(PERR::UnexpectedToken(
+                    t.syntax().into(),
                     Token::Comma.into(),
                     "to separate the items of this object map literal".into(),
                 )
                 .into_err(*pos))
             }
             (Token::LexError(err), pos) => return Err(err.into_err(*pos)),
-            (_, pos) => {
-                return Err(
-                    PERR::MissingToken(Token::RightBrace.into(), MISSING_RBRACE.into())
-                        .into_err(*pos),
+            (t, pos) => {
+                return Err(PERR::UnexpectedToken(
+                    t.syntax().into(),
+                    Token::RightBrace.into(),
+                    MISSING_RBRACE.into(),
                 )
+                .into_err(*pos))
             }
         }
     }
@@ -1367,6 +2013,9 @@ fn parse_primary(
 
     let (token, _) = match token {
         // { - block statement as expression
+        Token::LeftBrace if settings.allow_stmt_expr && !state.engine.allow_statement_expr => {
+            return Err(PERR::ForbiddenSyntax("Statement expression".into()).into_err(settings.pos))
+        }
         Token::LeftBrace if settings.allow_stmt_expr => {
             return parse_block(input, state, settings.level_up())
                 .map(|block| Expr::Stmt(Box::new((block, settings.pos))))
@@ -1379,11 +2028,17 @@ fn parse_primary(
         Token::IntegerConstant(x) => Expr::IntegerConstant(Box::new((x, settings.pos))),
         #[cfg(not(feature = "no_float"))]
         Token::FloatConstant(x) => Expr::FloatConstant(Box::new((x, settings.pos))),
+        #[cfg(feature = "decimal")]
+        Token::DecimalConstant(x) => Expr::DecimalConstant(Box::new((x, settings.pos))),
         Token::CharConstant(c) => Expr::CharConstant(Box::new((c, settings.pos))),
         Token::StringConst(s) => Expr::StringConstant(Box::new((s.into(), settings.pos))),
+        Token::Identifier(s) if state.engine.custom_syntax.contains_key(&s) => {
+            return parse_custom_syntax(input, state, &s, settings.level_up());
+        }
         Token::Identifier(s) => {
             let index = state.find_var(&s);
-            Expr::Variable(Box::new(((s, settings.pos), None, 0, index)))
+            let hash = calc_ident_hash(&s);
+            Expr::Variable(Box::new(((s, settings.pos), None, 0, index, hash)))
         }
         Token::LeftParen => parse_paren_expr(input, state, settings.level_up())?,
         #[cfg(not(feature = "no_index"))]
@@ -1414,7 +2069,7 @@ fn parse_primary(
         root_expr = match (root_expr, token) {
             // Function call
             (Expr::Variable(x), Token::LeftParen) => {
-                let ((name, pos), modules, _, _) = *x;
+                let ((name, pos), modules, _, _, _) = *x;
                 settings.pos = pos;
                 parse_call_expr(input, state, name, modules, settings.level_up())?
             }
@@ -1422,7 +2077,7 @@ fn parse_primary(
             // module access
             (Expr::Variable(x), Token::DoubleColon) => match input.next().unwrap() {
                 (Token::Identifier(id2), pos2) => {
-                    let ((name, pos), mut modules, _, index) = *x;
+                    let ((name, pos), mut modules, _, index, _) = *x;
 
                     if let Some(ref mut modules) = modules {
                         modules.push((name, pos));
@@ -1432,7 +2087,8 @@ fn parse_primary(
                         modules = Some(Box::new(m));
                     }
 
-                    Expr::Variable(Box::new(((id2, pos2), modules, 0, index)))
+                    let hash = calc_ident_hash(&id2);
+                    Expr::Variable(Box::new(((id2, pos2), modules, 0, index, hash)))
                 }
                 (_, pos2) => return Err(PERR::VariableExpected.into_err(pos2)),
             },
@@ -1453,7 +2109,7 @@ fn parse_primary(
     match &mut root_expr {
         // Cache the hash key for module-qualified variables
         Expr::Variable(x) if x.1.is_some() => {
-            let ((name, _), modules, hash, _) = x.as_mut();
+            let ((name, _), modules, hash, _, _) = x.as_mut();
             let modules = modules.as_mut().unwrap();
 
             // Qualifiers + variable name
@@ -1466,6 +2122,54 @@ fn parse_primary(
     Ok(root_expr)
 }
 
+/// Parse a custom syntax, after its leading keyword.
+fn parse_custom_syntax(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    key: &str,
+    mut settings: ParseSettings,
+) -> Result<Expr, ParseError> {
+    let pos = settings.pos;
+    let syntax = state.engine.custom_syntax.get(key).unwrap();
+    let func = syntax.func.clone();
+    let segments = syntax.segments.clone();
+
+    let mut inputs = StaticVec::new();
+
+    for segment in segments.iter().skip(1) {
+        settings.pos = input.peek().unwrap().1;
+
+        match segment.as_str() {
+            MARKER_EXPR => inputs.push(parse_expr(input, state, settings.level_up())?),
+            MARKER_BLOCK => {
+                let block = parse_block(input, state, settings.level_up())?;
+                inputs.push(Expr::Stmt(Box::new((block, settings.pos))));
+            }
+            MARKER_IDENT => match input.next().unwrap() {
+                (Token::Identifier(s), pos) => {
+                    let hash = calc_ident_hash(&s);
+                    inputs.push(Expr::Variable(Box::new(((s, pos), None, 0, None, hash))));
+                }
+                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+            },
+            keyword => match input.next().unwrap() {
+                (token, _) if token.syntax().as_ref() == keyword => (),
+                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                (_, pos) => {
+                    return Err(PERR::MissingToken(
+                        keyword.into(),
+                        format!("to complete the '{}' syntax", key),
+                    )
+                    .into_err(pos))
+                }
+            },
+        }
+    }
+
+    Ok(Expr::Custom(Box::new((CustomExpr(inputs, func), pos))))
+}
+
 /// Parse a potential unary operator.
 fn parse_unary(
     input: &mut TokenStream,
@@ -1478,6 +2182,9 @@ fn parse_unary(
 
     match token {
         // If statement is allowed to act as expressions
+        Token::If if settings.allow_if_expr && !state.engine.allow_if_expression => {
+            Err(PERR::ForbiddenSyntax("'if' expression".into()).into_err(settings.pos))
+        }
         Token::If if settings.allow_if_expr => Ok(Expr::Stmt(Box::new((
             parse_if(input, state, settings.level_up())?,
             settings.pos,
@@ -1510,6 +2217,10 @@ fn parse_unary(
                 #[cfg(not(feature = "no_float"))]
                 Expr::FloatConstant(x) => Ok(Expr::FloatConstant(Box::new((-x.0, x.1)))),
 
+                // Negative decimal
+                #[cfg(feature = "decimal")]
+                Expr::DecimalConstant(x) => Ok(Expr::DecimalConstant(Box::new((-x.0, x.1)))),
+
                 // Call negative function
                 expr => {
                     let op = "-";
@@ -1536,7 +2247,7 @@ fn parse_unary(
         Token::Bang => {
             let pos = eat_token(input, Token::Bang);
             let mut args = StaticVec::new();
-            let expr = parse_primary(input, state, settings.level_up())?;
+            let expr = parse_unary(input, state, settings.level_up())?;
             args.push(expr);
 
             let op = "!";
@@ -1547,7 +2258,7 @@ fn parse_unary(
                 None,
                 hash,
                 args,
-                Some(false.into()), // NOT operator, when operating on invalid operand, defaults to false
+                None,
             ))))
         }
         // <EOF>
@@ -1571,7 +2282,7 @@ fn make_assignment_stmt<'a>(
         }
         // var (indexed) = rhs
         Expr::Variable(x) => {
-            let ((name, name_pos), _, _, index) = x.as_ref();
+            let ((name, name_pos), _, _, index, _) = x.as_ref();
             match state.stack[(state.stack.len() - index.unwrap().get())].1 {
                 ScopeEntryType::Normal => {
                     Ok(Expr::Assignment(Box::new((lhs, fn_name.into(), rhs, pos))))
@@ -1590,7 +2301,7 @@ fn make_assignment_stmt<'a>(
             }
             // var[???] (indexed) = rhs, var.??? (indexed) = rhs
             Expr::Variable(x) => {
-                let ((name, name_pos), _, _, index) = x.as_ref();
+                let ((name, name_pos), _, _, index, _) = x.as_ref();
                 match state.stack[(state.stack.len() - index.unwrap().get())].1 {
                     ScopeEntryType::Normal => {
                         Ok(Expr::Assignment(Box::new((lhs, fn_name.into(), rhs, pos))))
@@ -1868,9 +2579,12 @@ fn parse_binary_op(
 
     let mut root = lhs;
 
+    // Direction of the comparison chain that `root` currently ends in, if any
+    let mut chain_direction = None;
+
     loop {
         let (current_op, _) = input.peek().unwrap();
-        let precedence = current_op.precedence();
+        let precedence = state.precedence(current_op);
         let bind_right = current_op.is_bind_right();
 
         // Bind left to the parent lhs expression if precedence is higher
@@ -1883,7 +2597,7 @@ fn parse_binary_op(
 
         let rhs = parse_unary(input, state, settings)?;
 
-        let next_precedence = input.peek().unwrap().0.precedence();
+        let next_precedence = state.precedence(&input.peek().unwrap().0);
 
         // Bind to right if the next operator has higher precedence
         // If same precedence, then check if the operator binds right
@@ -1903,6 +2617,40 @@ fn parse_binary_op(
         let hash = calc_fn_hash(empty(), &op, 2, empty());
         let op = (op, true, pos);
 
+        // Chained comparison - a < b < c
+        let direction = comparison_direction(&op_token);
+
+        if let (Some(direction), Some(prev_direction)) = (direction, chain_direction) {
+            if direction != prev_direction {
+                return Err(PERR::BadInput(
+                    "Cannot mix '<'/'<=' with '>'/'>=' in a chained comparison".to_string(),
+                )
+                .into_err(pos));
+            }
+
+            root = match root {
+                Expr::CmpChain(mut x) => {
+                    x.0.push(rhs);
+                    x.1.push((op.0, hash, pos));
+                    Expr::CmpChain(x)
+                }
+                Expr::FnCall(x) => {
+                    let ((first_op, _, first_pos), _, first_hash, mut operands, _) = *x;
+                    operands.push(rhs);
+
+                    let mut ops = StaticVec::new();
+                    ops.push((first_op, first_hash, first_pos));
+                    ops.push((op.0, hash, pos));
+
+                    Expr::CmpChain(Box::new((operands, ops)))
+                }
+                _ => unreachable!(),
+            };
+            continue;
+        }
+
+        chain_direction = direction;
+
         let mut args = StaticVec::new();
         args.push(root);
         args.push(rhs);
@@ -1920,6 +2668,12 @@ fn parse_binary_op(
             | Token::Pipe
             | Token::XOr => Expr::FnCall(Box::new((op, None, hash, args, None))),
 
+            // Custom operators may also be implemented by script-defined functions
+            Token::Custom(_) => {
+                let op = (op.0, false, op.2);
+                Expr::FnCall(Box::new((op, None, hash, args, None)))
+            }
+
             // '!=' defaults to true when passed invalid operands
             Token::NotEqualsTo => Expr::FnCall(Box::new((op, None, hash, args, Some(true.into())))),
 
@@ -1958,6 +2712,16 @@ fn parse_binary_op(
     }
 }
 
+/// Direction of a comparison operator that can be chained -
+/// `Some(true)` for `<` and `<=`, `Some(false)` for `>` and `>=`, otherwise `None`.
+fn comparison_direction(token: &Token) -> Option<bool> {
+    match token {
+        Token::LessThan | Token::LessThanEqualsTo => Some(true),
+        Token::GreaterThan | Token::GreaterThanEqualsTo => Some(false),
+        _ => None,
+    }
+}
+
 /// Parse an expression.
 fn parse_expr(
     input: &mut TokenStream,
@@ -2052,6 +2816,11 @@ fn parse_while(
     settings.pos = eat_token(input, Token::While);
     settings.ensure_level_within_max_limit(state.max_expr_depth)?;
 
+    // while let name = expr { body }
+    if match_token(input, Token::Let)? {
+        return parse_while_let(input, state, settings);
+    }
+
     // while guard { body }
     ensure_not_statement_expr(input, "a boolean")?;
     let guard = parse_expr(input, state, settings.level_up())?;
@@ -2063,6 +2832,53 @@ fn parse_while(
     Ok(Stmt::While(Box::new((guard, body))))
 }
 
+/// Parse a while-let loop, after the `while let` keywords.
+fn parse_while_let(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    mut settings: ParseSettings,
+) -> Result<Stmt, ParseError> {
+    // while let name ...
+    let (name, pos) = match input.next().unwrap() {
+        (Token::Identifier(s), pos) => (s, pos),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+    };
+
+    if name == KEYWORD_THIS {
+        return Err(PERR::BadInput(LexError::MalformedIdentifier(name).to_string()).into_err(pos));
+    }
+
+    // while let name = ...
+    match input.next().unwrap() {
+        (Token::Equals, _) => (),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(PERR::MissingToken(
+                Token::Equals.into(),
+                "after the variable in a while-let loop".into(),
+            )
+            .into_err(pos))
+        }
+    }
+
+    // while let name = expr { body }
+    ensure_not_statement_expr(input, "an expression")?;
+    let expr = parse_expr(input, state, settings.level_up())?;
+    ensure_not_assignment(input)?;
+
+    let prev_stack_len = state.stack.len();
+    state.stack.push((name.clone(), ScopeEntryType::Normal));
+
+    settings.is_breakable = true;
+    let body = parse_block(input, state, settings.level_up())?;
+
+    state.stack.truncate(prev_stack_len);
+
+    let hash = calc_ident_hash(&name);
+    Ok(Stmt::WhileLet(Box::new(((name, pos), expr, body, hash))))
+}
+
 /// Parse a loop statement.
 fn parse_loop(
     input: &mut TokenStream,
@@ -2080,6 +2896,83 @@ fn parse_loop(
     Ok(Stmt::Loop(Box::new(body)))
 }
 
+/// Parse a try-finally statement.
+fn parse_try_finally(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    mut settings: ParseSettings,
+) -> Result<Stmt, ParseError> {
+    // try ...
+    settings.pos = eat_token(input, Token::Try);
+    settings.ensure_level_within_max_limit(state.max_expr_depth)?;
+
+    // try { body }
+    let body = parse_block(input, state, settings.level_up())?;
+
+    // try { body } finally ...
+    match input.next().unwrap() {
+        (Token::Finally, _) => (),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(PERR::MissingToken(
+                Token::Finally.into(),
+                "to follow the 'try' block".into(),
+            )
+            .into_err(pos))
+        }
+    }
+
+    // try { body } finally { cleanup }
+    let finally_block = parse_block(input, state, settings.level_up())?;
+
+    Ok(Stmt::TryFinally(Box::new((
+        body,
+        finally_block,
+        settings.pos,
+    ))))
+}
+
+/// Parse a block of statements preceded by an attribute.
+fn parse_attribute_block(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    mut settings: ParseSettings,
+) -> Result<Stmt, ParseError> {
+    // #[ ...
+    settings.pos = eat_token(input, Token::AttributeStart);
+    settings.ensure_level_within_max_limit(state.max_expr_depth)?;
+
+    let (name, name_pos) = match input.next().unwrap() {
+        (Token::Identifier(s), pos) => (s, pos),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(PERR::BadInput("Expecting the name of an attribute".into()).into_err(pos))
+        }
+    };
+
+    // #[name]
+    match input.next().unwrap() {
+        (Token::RightBracket, _) => (),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(PERR::MissingToken(
+                Token::RightBracket.into(),
+                "to close the attribute".into(),
+            )
+            .into_err(pos))
+        }
+    }
+
+    match name.as_str() {
+        // #[unchecked] { body }
+        "unchecked" => {
+            let body = parse_block(input, state, settings.level_up())?;
+            Ok(Stmt::Unchecked(Box::new((body, settings.pos))))
+        }
+        _ => Err(PERR::BadInput(format!("Unknown attribute '{}'", name)).into_err(name_pos)),
+    }
+}
+
 /// Parse a for loop.
 fn parse_for(
     input: &mut TokenStream,
@@ -2090,16 +2983,40 @@ fn parse_for(
     settings.pos = eat_token(input, Token::For);
     settings.ensure_level_within_max_limit(state.max_expr_depth)?;
 
-    // for name ...
-    let name = match input.next().unwrap() {
-        // Variable name
-        (Token::Identifier(s), _) => s,
-        // Bad identifier
-        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-        // EOF
-        (Token::EOF, pos) => return Err(PERR::VariableExpected.into_err(pos)),
-        // Not a variable name
-        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+    // for (counter, name) ...
+    let (counter, name) = if match_token(input, Token::LeftParen)? {
+        let counter = parse_for_var_name(input)?;
+
+        match input.next().unwrap() {
+            (Token::Comma, _) => (),
+            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+            (_, pos) => {
+                return Err(PERR::MissingToken(
+                    Token::Comma.into(),
+                    "after the counter variable".into(),
+                )
+                .into_err(pos))
+            }
+        }
+
+        let name = parse_for_var_name(input)?;
+
+        match input.next().unwrap() {
+            (Token::RightParen, _) => (),
+            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+            (_, pos) => {
+                return Err(PERR::MissingToken(
+                    Token::RightParen.into(),
+                    "to close the iteration variables".into(),
+                )
+                .into_err(pos))
+            }
+        }
+
+        (Some(counter), name)
+    } else {
+        // for name ...
+        (None, parse_for_var_name(input)?)
     };
 
     // for name in ...
@@ -2120,13 +3037,34 @@ fn parse_for(
 
     let prev_stack_len = state.stack.len();
     state.stack.push((name.clone(), ScopeEntryType::Normal));
+    if let Some(counter) = &counter {
+        state.stack.push((counter.clone(), ScopeEntryType::Normal));
+    }
 
     settings.is_breakable = true;
     let body = parse_block(input, state, settings.level_up())?;
 
     state.stack.truncate(prev_stack_len);
 
-    Ok(Stmt::For(Box::new((name, expr, body))))
+    let hashes = (
+        calc_ident_hash(&name),
+        counter.as_deref().map(calc_ident_hash),
+    );
+    Ok(Stmt::For(Box::new(((name, counter), expr, body, hashes))))
+}
+
+/// Parse the name of an iteration variable in a for loop.
+fn parse_for_var_name(input: &mut TokenStream) -> Result<String, ParseError> {
+    match input.next().unwrap() {
+        // Variable name
+        (Token::Identifier(s), _) => Ok(s),
+        // Bad identifier
+        (Token::LexError(err), pos) => Err(err.into_err(pos)),
+        // EOF
+        (Token::EOF, pos) => Err(PERR::VariableExpected.into_err(pos)),
+        // Not a variable name
+        (_, pos) => Err(PERR::VariableExpected.into_err(pos)),
+    }
 }
 
 /// Parse a variable definition statement.
@@ -2140,6 +3078,14 @@ fn parse_let(
     settings.pos = input.next().unwrap().1;
     settings.ensure_level_within_max_limit(state.max_expr_depth)?;
 
+    // let (name, ...) = expr
+    #[cfg(not(feature = "no_index"))]
+    if var_type == ScopeEntryType::Normal {
+        if let (Token::LeftParen, _) = input.peek().unwrap() {
+            return parse_let_tuple(input, state, settings.level_up());
+        }
+    }
+
     // let name ...
     let (name, pos) = match input.next().unwrap() {
         (Token::Identifier(s), pos) => (s, pos),
@@ -2166,12 +3112,14 @@ fn parse_let(
             // let name = expr
             ScopeEntryType::Normal => {
                 state.stack.push((name.clone(), ScopeEntryType::Normal));
-                Ok(Stmt::Let(Box::new(((name, pos), Some(init_value)))))
+                let hash = calc_ident_hash(&name);
+                Ok(Stmt::Let(Box::new(((name, pos), Some(init_value), hash))))
             }
             // const name = { expr:constant }
             ScopeEntryType::Constant if init_value.is_constant() => {
                 state.stack.push((name.clone(), ScopeEntryType::Constant));
-                Ok(Stmt::Const(Box::new(((name, pos), init_value))))
+                let hash = calc_ident_hash(&name);
+                Ok(Stmt::Const(Box::new(((name, pos), init_value, hash))))
             }
             // const name = expr: error
             ScopeEntryType::Constant => {
@@ -2183,16 +3131,83 @@ fn parse_let(
         match var_type {
             ScopeEntryType::Normal => {
                 state.stack.push((name.clone(), ScopeEntryType::Normal));
-                Ok(Stmt::Let(Box::new(((name, pos), None))))
+                let hash = calc_ident_hash(&name);
+                Ok(Stmt::Let(Box::new(((name, pos), None, hash))))
             }
             ScopeEntryType::Constant => {
                 state.stack.push((name.clone(), ScopeEntryType::Constant));
-                Ok(Stmt::Const(Box::new(((name, pos), Expr::Unit(pos)))))
+                let hash = calc_ident_hash(&name);
+                Ok(Stmt::Const(Box::new(((name, pos), Expr::Unit(pos), hash))))
             }
         }
     }
 }
 
+/// Parse a destructuring variable definition: `let (name, ...) = expr`.
+#[cfg(not(feature = "no_index"))]
+fn parse_let_tuple(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    mut settings: ParseSettings,
+) -> Result<Stmt, ParseError> {
+    // let ( ...
+    settings.pos = eat_token(input, Token::LeftParen);
+    settings.ensure_level_within_max_limit(state.max_expr_depth)?;
+
+    let mut names: StaticVec<(String, Position, IdentHash)> = Default::default();
+
+    loop {
+        // let (name ...
+        let (name, pos) = match input.next().unwrap() {
+            (Token::Identifier(s), pos) if s == KEYWORD_THIS => {
+                return Err(
+                    PERR::BadInput(LexError::MalformedIdentifier(s).to_string()).into_err(pos)
+                )
+            }
+            (Token::Identifier(s), pos) => (s, pos),
+            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+            (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+        };
+
+        let hash = calc_ident_hash(&name);
+        names.push((name, pos, hash));
+
+        // let (name, ... ) or let (name)
+        match input.next().unwrap() {
+            (Token::Comma, _) => (),
+            (Token::RightParen, _) => break,
+            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+            (_, pos) => {
+                return Err(PERR::MissingToken(
+                    Token::RightParen.into(),
+                    "to close the list of variables".into(),
+                )
+                .into_err(pos))
+            }
+        }
+    }
+
+    // let (name, ...) = expr
+    match input.next().unwrap() {
+        (Token::Equals, _) => (),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(
+                PERR::MissingToken(Token::Equals.into(), "for the values to unpack".into())
+                    .into_err(pos),
+            )
+        }
+    }
+
+    let init_value = parse_expr(input, state, settings.level_up())?;
+
+    names
+        .iter()
+        .for_each(|(name, _, _)| state.stack.push((name.clone(), ScopeEntryType::Normal)));
+
+    Ok(Stmt::LetTuple(Box::new((names, init_value, settings.pos))))
+}
+
 /// Parse an import statement.
 fn parse_import(
     input: &mut TokenStream,
@@ -2315,6 +3330,13 @@ fn parse_block(
     let prev_mods_len = state.modules.len();
 
     while !match_token(input, Token::RightBrace)? {
+        // { ... <EOF>
+        if let (Token::EOF, pos) = input.peek().unwrap() {
+            return Err(
+                PERR::UnclosedDelimiter(Token::LeftBrace.into(), settings.pos).into_err(*pos),
+            );
+        }
+
         // Parse statements inside the block
         settings.is_global = false;
         let stmt = parse_stmt(input, state, settings.level_up())?;
@@ -2338,12 +3360,19 @@ fn parse_block(
             (Token::SemiColon, _) if !need_semicolon => (),
             // { ... { stmt } ???
             (_, _) if !need_semicolon => (),
+            // { ... stmt <EOF>
+            (Token::EOF, pos) => {
+                return Err(
+                    PERR::UnclosedDelimiter(Token::LeftBrace.into(), settings.pos).into_err(*pos),
+                )
+            }
             // { ... stmt <error>
             (Token::LexError(err), pos) => return Err(err.into_err(*pos)),
             // { ... stmt ???
-            (_, pos) => {
+            (t, pos) => {
                 // Semicolons are not optional between statements
-                return Err(PERR::MissingToken(
+                return Err(PERR::UnexpectedToken(
+                    t.syntax().into(),
                     Token::SemiColon.into(),
                     "to terminate this statement".into(),
                 )
@@ -2393,6 +3422,9 @@ fn parse_stmt(
 
         Token::LeftBrace => parse_block(input, state, settings.level_up()),
 
+        // #[attribute] { ... }
+        Token::AttributeStart => parse_attribute_block(input, state, settings.level_up()),
+
         // fn ...
         #[cfg(not(feature = "no_function"))]
         Token::Fn if !settings.is_global => Err(PERR::WrongFnDefinition.into_err(settings.pos)),
@@ -2400,10 +3432,16 @@ fn parse_stmt(
         Token::Fn => unreachable!(),
 
         Token::If => parse_if(input, state, settings.level_up()),
+
+        Token::While | Token::Loop | Token::For if !state.engine.allow_looping => {
+            Err(PERR::ForbiddenSyntax(format!("'{}' loop", token.syntax())).into_err(settings.pos))
+        }
         Token::While => parse_while(input, state, settings.level_up()),
         Token::Loop => parse_loop(input, state, settings.level_up()),
         Token::For => parse_for(input, state, settings.level_up()),
 
+        Token::Try => parse_try_finally(input, state, settings.level_up()),
+
         Token::Continue if settings.is_breakable => {
             let pos = eat_token(input, Token::Continue);
             Ok(Stmt::Continue(pos))
@@ -2469,6 +3507,8 @@ fn parse_fn(
 
     let name = match input.next().unwrap() {
         (Token::Identifier(s), _) => s,
+        // Custom operators in identifier form can be implemented as script functions
+        (Token::Custom(s), _) if is_valid_identifier(s.chars()) => s,
         (_, pos) => return Err(PERR::FnMissingName.into_err(pos)),
     };
 
@@ -2538,12 +3578,14 @@ fn parse_fn(
         (_, pos) => return Err(PERR::FnMissingBody(name).into_err(*pos)),
     };
 
-    let params = params.into_iter().map(|(p, _)| p).collect();
+    let params: StaticVec<_> = params.into_iter().map(|(p, _)| p).collect();
+    let param_hashes = params.iter().map(|p| calc_ident_hash(p)).collect();
 
     Ok(ScriptFnDef {
         name,
         access,
         params,
+        param_hashes,
         body,
         pos: settings.pos,
     })
@@ -2557,8 +3599,8 @@ impl Engine {
         optimization_level: OptimizationLevel,
     ) -> Result<AST, ParseError> {
         let mut state = ParseState::new(
+            self,
             self.max_expr_depth,
-            self.max_string_size,
             self.max_array_size,
             self.max_map_size,
         );
@@ -2598,8 +3640,8 @@ impl Engine {
         let mut statements = Vec::<Stmt>::new();
         let mut functions = HashMap::<u64, ScriptFnDef, _>::with_hasher(StraightHasherBuilder);
         let mut state = ParseState::new(
+            self,
             self.max_expr_depth,
-            self.max_string_size,
             self.max_array_size,
             self.max_map_size,
         );
@@ -2615,11 +3657,17 @@ impl Engine {
                 };
 
                 match input.peek().unwrap() {
+                    #[cfg(not(feature = "no_function"))]
+                    (Token::Fn, pos) if !self.allow_fn_definitions => {
+                        return Err(
+                            PERR::ForbiddenSyntax("Function definition".into()).into_err(*pos)
+                        );
+                    }
                     #[cfg(not(feature = "no_function"))]
                     (Token::Fn, pos) => {
                         let mut state = ParseState::new(
+                            self,
                             self.max_function_expr_depth,
-                            self.max_string_size,
                             self.max_array_size,
                             self.max_map_size,
                         );
@@ -2679,9 +3727,10 @@ impl Engine {
                 // stmt <error>
                 (Token::LexError(err), pos) => return Err(err.into_err(*pos)),
                 // stmt ???
-                (_, pos) => {
+                (t, pos) => {
                     // Semicolons are not optional between statements
-                    return Err(PERR::MissingToken(
+                    return Err(PERR::UnexpectedToken(
+                        t.syntax().into(),
                         Token::SemiColon.into(),
                         "to terminate this statement".into(),
                     )
@@ -2716,6 +3765,8 @@ pub fn map_dynamic_to_expr(value: Dynamic, pos: Position) -> Option<Expr> {
     match value.0 {
         #[cfg(not(feature = "no_float"))]
         Union::Float(value) => Some(Expr::FloatConstant(Box::new((value, pos)))),
+        #[cfg(feature = "decimal")]
+        Union::Decimal(value) => Some(Expr::DecimalConstant(Box::new((*value, pos)))),
 
         Union::Unit(_) => Some(Expr::Unit(pos)),
         Union::Int(value) => Some(Expr::IntegerConstant(Box::new((value, pos)))),
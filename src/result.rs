@@ -10,6 +10,7 @@ use crate::stdlib::{
     error::Error,
     fmt,
     string::{String, ToString},
+    vec::Vec,
 };
 
 #[cfg(not(feature = "no_std"))]
@@ -36,6 +37,9 @@ pub enum EvalAltResult {
 
     /// Call to an unknown function. Wrapped value is the name of the function.
     ErrorFunctionNotFound(String, Position),
+    /// Call to a function that matches more than one overload equally well.
+    /// Wrapped values are the signature of the call and the signatures of all matching overloads.
+    ErrorAmbiguousFunctionCall(String, Vec<String>, Position),
     /// An error has occurred inside a called function.
     /// Wrapped values re the name of the function and the interior error.
     ErrorInFunctionCall(String, Box<EvalAltResult>, Position),
@@ -112,6 +116,7 @@ impl EvalAltResult {
             Self::ErrorParsing(p, _) => p.desc(),
             Self::ErrorInFunctionCall(_, _, _) => "Error in called function",
             Self::ErrorFunctionNotFound(_, _) => "Function not found",
+            Self::ErrorAmbiguousFunctionCall(_, _, _) => "Ambiguous function call",
             Self::ErrorUnboundedThis(_) => "'this' is not bounded",
             Self::ErrorBooleanArgMismatch(_, _) => "Boolean operator expects boolean operands",
             Self::ErrorCharMismatch(_) => "Character expected",
@@ -123,14 +128,8 @@ impl EvalAltResult {
                 "Indexing can only be performed on an array, an object map, a string, or a type with an indexer function defined"
             }
             Self::ErrorImportExpr(_) => "Importing a module expects a string path",
-            Self::ErrorArrayBounds(_, index, _) if *index < 0 => {
-                "Array access expects non-negative index"
-            }
             Self::ErrorArrayBounds(0, _, _) => "Empty array has nothing to access",
             Self::ErrorArrayBounds(_, _, _) => "Array index out of bounds",
-            Self::ErrorStringBounds(_, index, _) if *index < 0 => {
-                "Indexing a string expects a non-negative index"
-            }
             Self::ErrorStringBounds(0, _, _) => "Empty string has nothing to index",
             Self::ErrorStringBounds(_, _, _) => "String index out of bounds",
             Self::ErrorLogicGuard(_) => "Boolean value expected",
@@ -182,6 +181,14 @@ impl fmt::Display for EvalAltResult {
             | Self::ErrorVariableNotFound(s, _)
             | Self::ErrorModuleNotFound(s, _) => write!(f, "{}: '{}'", desc, s)?,
 
+            Self::ErrorAmbiguousFunctionCall(s, candidates, _) => write!(
+                f,
+                "{} {}, which can match any of: {}",
+                desc,
+                s,
+                candidates.join("; ")
+            )?,
+
             Self::ErrorDotExpr(s, _) if !s.is_empty() => write!(f, "{} {}", desc, s)?,
 
             Self::ErrorIndexingType(_, _)
@@ -212,9 +219,6 @@ impl fmt::Display for EvalAltResult {
                 write!(f, "{} operator expects boolean operands", op)?
             }
             Self::ErrorCharMismatch(_) => write!(f, "string indexing expects a character value")?,
-            Self::ErrorArrayBounds(_, index, _) if *index < 0 => {
-                write!(f, "{}: {} < 0", desc, index)?
-            }
             Self::ErrorArrayBounds(0, _, _) => write!(f, "{}", desc)?,
             Self::ErrorArrayBounds(1, index, _) => write!(
                 f,
@@ -226,9 +230,6 @@ impl fmt::Display for EvalAltResult {
                 "Array index {} is out of bounds: only {} elements in the array",
                 index, max
             )?,
-            Self::ErrorStringBounds(_, index, _) if *index < 0 => {
-                write!(f, "{}: {} < 0", desc, index)?
-            }
             Self::ErrorStringBounds(0, _, _) => write!(f, "{}", desc)?,
             Self::ErrorStringBounds(1, index, _) => write!(
                 f,
@@ -273,6 +274,7 @@ impl EvalAltResult {
 
             Self::ErrorParsing(_, pos)
             | Self::ErrorFunctionNotFound(_, pos)
+            | Self::ErrorAmbiguousFunctionCall(_, _, pos)
             | Self::ErrorInFunctionCall(_, _, pos)
             | Self::ErrorUnboundedThis(pos)
             | Self::ErrorBooleanArgMismatch(_, pos)
@@ -313,6 +315,7 @@ impl EvalAltResult {
 
             Self::ErrorParsing(_, pos)
             | Self::ErrorFunctionNotFound(_, pos)
+            | Self::ErrorAmbiguousFunctionCall(_, _, pos)
             | Self::ErrorInFunctionCall(_, _, pos)
             | Self::ErrorUnboundedThis(pos)
             | Self::ErrorBooleanArgMismatch(_, pos)
@@ -3,6 +3,7 @@
 use crate::any::{Dynamic, Variant};
 use crate::parser::{map_dynamic_to_expr, Expr};
 use crate::token::Position;
+use crate::utils::{calc_ident_hash, IdentHash};
 
 use crate::stdlib::{borrow::Cow, boxed::Box, iter, string::String, vec::Vec};
 
@@ -20,6 +21,8 @@ pub enum EntryType {
 pub struct Entry<'a> {
     /// Name of the entry.
     pub name: Cow<'a, str>,
+    /// Hash of the name of the entry, used for looking it up.
+    pub hash: IdentHash,
     /// Type of the entry.
     pub typ: EntryType,
     /// Current value of the entry.
@@ -137,6 +140,10 @@ impl<'a> Scope<'a> {
 
     /// Add (push) a new entry to the Scope.
     ///
+    /// The value is converted via `Dynamic::from`, so standard types map to their script
+    /// representations while any other type (registered with the `Engine` or not) is stored
+    /// as an opaque custom value.
+    ///
     /// # Examples
     ///
     /// ```
@@ -219,6 +226,20 @@ impl<'a> Scope<'a> {
         entry_type: EntryType,
         value: Dynamic,
         map_expr: bool,
+    ) {
+        let name = name.into();
+        let hash = calc_ident_hash(&name);
+        self.push_with_hash(name, hash, entry_type, value, map_expr);
+    }
+
+    /// Add (push) a new entry with a `Dynamic` value and an already-calculated name hash to the Scope.
+    pub(crate) fn push_with_hash(
+        &mut self,
+        name: Cow<'a, str>,
+        hash: IdentHash,
+        entry_type: EntryType,
+        value: Dynamic,
+        map_expr: bool,
     ) {
         let expr = if map_expr {
             map_dynamic_to_expr(value.clone(), Position::none()).map(Box::new)
@@ -227,7 +248,8 @@ impl<'a> Scope<'a> {
         };
 
         self.0.push(Entry {
-            name: name.into(),
+            name,
+            hash,
             typ: entry_type,
             alias: None,
             value: value.into(),
@@ -285,19 +307,30 @@ impl<'a> Scope<'a> {
             .any(|Entry { name: key, .. }| name == key)
     }
 
-    /// Find an entry in the Scope, starting from the last.
-    pub(crate) fn get_index(&self, name: &str) -> Option<(usize, EntryType)> {
+    /// Find an entry in the Scope by its name and name hash, starting from the last.
+    pub(crate) fn get_index(&self, name: &str, hash: IdentHash) -> Option<(usize, EntryType)> {
         self.0
             .iter()
             .enumerate()
             .rev() // Always search a Scope in reverse order
-            .find_map(|(index, Entry { name: key, typ, .. })| {
-                if name == key {
-                    Some((index, *typ))
-                } else {
-                    None
-                }
-            })
+            .find_map(
+                |(
+                    index,
+                    Entry {
+                        name: key,
+                        hash: key_hash,
+                        typ,
+                        ..
+                    },
+                )| {
+                    // Compare the hashes first - the names only need comparing on a match
+                    if hash == *key_hash && name == key {
+                        Some((index, *typ))
+                    } else {
+                        None
+                    }
+                },
+            )
     }
 
     /// Get the value of an entry in the Scope, starting from the last.
@@ -342,7 +375,7 @@ impl<'a> Scope<'a> {
     /// assert_eq!(my_scope.get_value::<i64>("x").unwrap(), 0);
     /// ```
     pub fn set_value<T: Variant + Clone>(&mut self, name: &'a str, value: T) {
-        match self.get_index(name) {
+        match self.get_index(name, calc_ident_hash(name)) {
             None => self.push(name, value),
             Some((_, EntryType::Constant)) => panic!("variable {} is constant", name),
             Some((index, EntryType::Normal)) => {
@@ -404,13 +437,18 @@ impl<'a> Scope<'a> {
 
 impl<'a, K: Into<Cow<'a, str>>> iter::Extend<(K, EntryType, Dynamic)> for Scope<'a> {
     fn extend<T: IntoIterator<Item = (K, EntryType, Dynamic)>>(&mut self, iter: T) {
-        self.0
-            .extend(iter.into_iter().map(|(name, typ, value)| Entry {
-                name: name.into(),
+        self.0.extend(iter.into_iter().map(|(name, typ, value)| {
+            let name = name.into();
+            let hash = calc_ident_hash(&name);
+
+            Entry {
+                name,
+                hash,
                 typ,
                 alias: None,
                 value: value.into(),
                 expr: None,
-            }));
+            }
+        }));
     }
 }
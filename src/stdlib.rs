@@ -16,7 +16,7 @@ mod inner {
     pub use core_error as error;
 
     pub mod collections {
-        pub use hashbrown::HashMap;
+        pub use hashbrown::{hash_map, HashMap, HashSet};
     }
 }
 
@@ -0,0 +1,204 @@
+//! Module containing support for custom syntax.
+
+use crate::any::Dynamic;
+use crate::engine::{Engine, Imports, State};
+use crate::error::{ParseError, ParseErrorType as PERR};
+use crate::fn_native::{SendSync, Shared};
+use crate::module::Module;
+use crate::parser::Expr;
+use crate::result::EvalAltResult;
+use crate::scope::Scope;
+use crate::token::{is_valid_identifier, lex_with_engine, Position, Token};
+use crate::utils::StaticVec;
+
+use crate::stdlib::{
+    boxed::Box,
+    fmt, format,
+    string::{String, ToString},
+};
+
+/// Marker in a custom syntax definition for any expression.
+pub const MARKER_EXPR: &str = "$expr$";
+/// Marker in a custom syntax definition for a statement block.
+pub const MARKER_BLOCK: &str = "$block$";
+/// Marker in a custom syntax definition for a variable name.
+pub const MARKER_IDENT: &str = "$ident$";
+
+/// A general custom syntax evaluation function.
+#[cfg(not(feature = "sync"))]
+pub type FnCustomSyntaxEval = dyn Fn(
+    &Engine,
+    &mut EvalContext,
+    &mut Scope,
+    &[Expression],
+) -> Result<Dynamic, Box<EvalAltResult>>;
+/// A general custom syntax evaluation function.
+#[cfg(feature = "sync")]
+pub type FnCustomSyntaxEval = dyn Fn(&Engine, &mut EvalContext, &mut Scope, &[Expression]) -> Result<Dynamic, Box<EvalAltResult>>
+    + Send
+    + Sync;
+
+/// A custom syntax definition.
+#[derive(Clone)]
+pub struct CustomSyntax {
+    /// Segments making up the syntax, starting with the leading keyword.
+    pub segments: StaticVec<String>,
+    /// Function that evaluates the syntax.
+    pub func: Shared<FnCustomSyntaxEval>,
+}
+
+impl fmt::Debug for CustomSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.segments, f)
+    }
+}
+
+/// A sub-expression parsed as part of a custom syntax.
+#[derive(Debug, Clone, Copy)]
+pub struct Expression<'a>(&'a Expr);
+
+impl<'a> From<&'a Expr> for Expression<'a> {
+    fn from(expr: &'a Expr) -> Self {
+        Self(expr)
+    }
+}
+
+impl Expression<'_> {
+    /// If this expression is a variable name, return it.  Otherwise `None`.
+    pub fn get_variable_name(&self) -> Option<&str> {
+        match self.0 {
+            Expr::Variable(x) => Some((x.0).0.as_str()),
+            _ => None,
+        }
+    }
+    /// Get the position of this expression.
+    pub fn position(&self) -> Position {
+        self.0.position()
+    }
+}
+
+/// Context of a custom syntax evaluation.
+pub struct EvalContext<'a, 'm, 's, 't, 'd: 't> {
+    pub(crate) mods: &'a mut Imports<'m>,
+    pub(crate) state: &'s mut State,
+    pub(crate) lib: &'a Module,
+    pub(crate) this_ptr: &'t mut Option<&'d mut Dynamic>,
+    pub(crate) level: usize,
+}
+
+impl Engine {
+    /// Evaluate a sub-expression of a custom syntax.
+    ///
+    /// This is typically called from within the evaluation function of a custom syntax,
+    /// and only sub-expressions actually needed are evaluated (e.g. for short-circuiting).
+    pub fn eval_expression_tree(
+        &self,
+        context: &mut EvalContext,
+        scope: &mut Scope,
+        expr: &Expression,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        self.eval_expr(
+            scope,
+            context.mods,
+            context.state,
+            context.lib,
+            context.this_ptr,
+            expr.0,
+            context.level,
+        )
+    }
+
+    /// Register a custom syntax with the `Engine`.
+    ///
+    /// The syntax is described by a list of segments.  The first segment must be an identifier
+    /// that starts the syntax.  Each subsequent segment is either a keyword or symbol to match,
+    /// or one of the following markers:
+    ///
+    /// * `$expr$` - any expression,
+    /// * `$block$` - a statement block enclosed in `{` .. `}`,
+    /// * `$ident$` - a variable name.
+    ///
+    /// The evaluation function receives the sub-expressions matched by the markers, in order,
+    /// and evaluates them as needed via `Engine::eval_expression_tree`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, INT};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_custom_syntax(&["double", "$expr$"], |engine, context, scope, inputs| {
+    ///     let value = engine.eval_expression_tree(context, scope, &inputs[0])?;
+    ///     Ok((value.as_int().unwrap() * 2).into())
+    /// }).unwrap();
+    ///
+    /// assert_eq!(engine.eval::<INT>("double 40 + 1")?, 82);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_custom_syntax<S: AsRef<str>>(
+        &mut self,
+        segments: &[S],
+        func: impl Fn(
+                &Engine,
+                &mut EvalContext,
+                &mut Scope,
+                &[Expression],
+            ) -> Result<Dynamic, Box<EvalAltResult>>
+            + SendSync
+            + 'static,
+    ) -> Result<&mut Self, ParseError> {
+        let mut keys: StaticVec<String> = Default::default();
+
+        for (index, segment) in segments.iter().map(|s| s.as_ref()).enumerate() {
+            let valid = match segment {
+                MARKER_EXPR | MARKER_BLOCK | MARKER_IDENT => index > 0,
+                // The first segment must be an identifier
+                s if index == 0 => is_valid_identifier(s.chars()) && is_single_token(self, s, true),
+                s => is_single_token(self, s, false),
+            };
+
+            if !valid {
+                return Err(PERR::BadInput(format!(
+                    "Improper segment '{}' in custom syntax",
+                    segment
+                ))
+                .into_err(Position::none()));
+            }
+
+            keys.push(segment.to_string());
+        }
+
+        if keys.is_empty() {
+            return Err(PERR::BadInput("Empty custom syntax".into()).into_err(Position::none()));
+        }
+
+        let syntax = CustomSyntax {
+            segments: keys,
+            func: Shared::new(func),
+        };
+
+        self.custom_syntax
+            .insert(syntax.segments[0].clone(), syntax);
+
+        Ok(self)
+    }
+}
+
+/// Does the text lex to exactly one token?
+/// If `identifier` is true, the token must be a non-keyword identifier.
+fn is_single_token(engine: &Engine, text: &str, identifier: bool) -> bool {
+    let scripts = [text];
+    let mut tokens = lex_with_engine(&scripts, engine)
+        .map(|(token, _)| token)
+        .take_while(|token| !token.is_eof());
+
+    match (tokens.next(), tokens.next()) {
+        (Some(Token::Identifier(_)), None) => true,
+        (Some(Token::LexError(_)), None) => false,
+        (Some(_), None) => !identifier,
+        _ => false,
+    }
+}
@@ -1,5 +1,6 @@
 //! Main module defining the lexer and parser.
 
+use crate::engine::Engine;
 use crate::error::LexError;
 use crate::parser::INT;
 use crate::utils::StaticVec;
@@ -7,11 +8,15 @@ use crate::utils::StaticVec;
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+use crate::parser::Decimal;
+
 use crate::stdlib::{
     borrow::Cow,
     boxed::Box,
     char, fmt,
     iter::Peekable,
+    num::IntErrorKind,
     str::{Chars, FromStr},
     string::{String, ToString},
     vec::Vec,
@@ -135,6 +140,8 @@ pub enum Token {
     IntegerConstant(INT),
     #[cfg(not(feature = "no_float"))]
     FloatConstant(FLOAT),
+    #[cfg(feature = "decimal")]
+    DecimalConstant(Decimal),
     Identifier(String),
     CharConstant(char),
     StringConst(String),
@@ -160,6 +167,7 @@ pub enum Token {
     Comma,
     Period,
     MapStart,
+    AttributeStart,
     Equals,
     True,
     False,
@@ -171,6 +179,8 @@ pub enum Token {
     Loop,
     For,
     In,
+    Try,
+    Finally,
     LessThan,
     GreaterThan,
     LessThanEqualsTo,
@@ -210,6 +220,7 @@ pub enum Token {
     As,
     LexError(Box<LexError>),
     Comment(String),
+    Custom(String),
     EOF,
 }
 
@@ -222,7 +233,10 @@ impl Token {
             IntegerConstant(i) => i.to_string().into(),
             #[cfg(not(feature = "no_float"))]
             FloatConstant(f) => f.to_string().into(),
+            #[cfg(feature = "decimal")]
+            DecimalConstant(d) => format!("{}d", d).into(),
             Identifier(s) => s.clone().into(),
+            Custom(s) => s.clone().into(),
             CharConstant(c) => c.to_string().into(),
             LexError(err) => err.to_string().into(),
 
@@ -246,6 +260,7 @@ impl Token {
                 Comma => ",",
                 Period => ".",
                 MapStart => "#{",
+                AttributeStart => "#[",
                 Equals => "=",
                 True => "true",
                 False => "false",
@@ -257,6 +272,8 @@ impl Token {
                 Loop => "loop",
                 For => "for",
                 In => "in",
+                Try => "try",
+                Finally => "finally",
                 LessThan => "<",
                 GreaterThan => ">",
                 Bang => "!",
@@ -367,6 +384,7 @@ impl Token {
             Throw            |
             PowerOf          |
             In               |
+            Custom(_)        |
             PowerOfAssign => true,
 
             _ => false,
@@ -691,7 +709,7 @@ fn get_next_token_inner(
                             result.push(next_char);
                             eat_next(stream, pos);
                         }
-                        #[cfg(not(feature = "no_float"))]
+                        #[cfg(any(not(feature = "no_float"), feature = "decimal"))]
                         '.' => {
                             result.push(next_char);
                             eat_next(stream, pos);
@@ -705,6 +723,48 @@ fn get_next_token_inner(
                                 }
                             }
                         }
+                        // Exponent - e.g. 1e10, 1.5e-3
+                        #[cfg(any(not(feature = "no_float"), feature = "decimal"))]
+                        'e' | 'E' if radix_base.is_none() => {
+                            result.push(next_char);
+                            eat_next(stream, pos);
+
+                            if let Some(sign @ '+') | Some(sign @ '-') = stream.peek_next() {
+                                result.push(sign);
+                                eat_next(stream, pos);
+                            }
+
+                            let mut has_digits = false;
+
+                            while let Some(next_char_in_exp) = stream.peek_next() {
+                                match next_char_in_exp {
+                                    '0'..='9' => {
+                                        has_digits = true;
+                                        result.push(next_char_in_exp);
+                                        eat_next(stream, pos);
+                                    }
+                                    '_' => {
+                                        result.push(next_char_in_exp);
+                                        eat_next(stream, pos);
+                                    }
+                                    _ => break,
+                                }
+                            }
+
+                            if !has_digits {
+                                if negated {
+                                    result.insert(0, '-');
+                                }
+                                return Some((
+                                    Token::LexError(Box::new(LERR::MalformedNumber(
+                                        result.into_iter().collect(),
+                                    ))),
+                                    start_pos,
+                                ));
+                            }
+
+                            break;
+                        }
                         // 0x????, 0o????, 0b????
                         ch @ 'x' | ch @ 'X' | ch @ 'o' | ch @ 'O' | ch @ 'b' | ch @ 'B' if c == '0' => {
                             result.push(next_char);
@@ -758,17 +818,57 @@ fn get_next_token_inner(
                     return Some((
                         INT::from_str_radix(&out, radix)
                             .map(Token::IntegerConstant)
-                            .unwrap_or_else(|_| {
-                                Token::LexError(Box::new(LERR::MalformedNumber(
-                                    result.into_iter().collect(),
-                                )))
+                            .unwrap_or_else(|err| {
+                                let num = result.into_iter().collect();
+
+                                Token::LexError(Box::new(match err.kind() {
+                                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                                        LERR::IntegerOutOfRange(num)
+                                    }
+                                    _ => LERR::MalformedNumber(num),
+                                }))
                             }),
                         start_pos,
                     ));
                 } else {
                     let out: String = result.iter().filter(|&&c| c != '_').collect();
+
+                    // Decimal number with a 'd' suffix
+                    #[cfg(feature = "decimal")]
+                    {
+                        if stream.peek_next() == Some('d') {
+                            result.push('d');
+                            eat_next(stream, pos);
+
+                            return Some((
+                                Decimal::from_str(&out)
+                                    .or_else(|_| Decimal::from_scientific(&out))
+                                    .map(Token::DecimalConstant)
+                                    .unwrap_or_else(|_| {
+                                        Token::LexError(Box::new(LERR::MalformedNumber(
+                                            result.into_iter().collect(),
+                                        )))
+                                    }),
+                                start_pos,
+                            ));
+                        }
+                    }
+
                     let num = INT::from_str(&out).map(Token::IntegerConstant);
 
+                    // An integer literal that does not fit into INT is an error,
+                    // never silently converted into something else
+                    if let Err(err) = &num {
+                        if let IntErrorKind::PosOverflow | IntErrorKind::NegOverflow = err.kind() {
+                            return Some((
+                                Token::LexError(Box::new(LERR::IntegerOutOfRange(
+                                    result.into_iter().collect(),
+                                ))),
+                                start_pos,
+                            ));
+                        }
+                    }
+
                     // If integer parsing is unnecessary, try float instead
                     #[cfg(not(feature = "no_float"))]
                     let num = num.or_else(|_| FLOAT::from_str(&out).map(Token::FloatConstant));
@@ -826,6 +926,8 @@ fn get_next_token_inner(
                         "throw" => Token::Throw,
                         "for" => Token::For,
                         "in" => Token::In,
+                        "try" => Token::Try,
+                        "finally" => Token::Finally,
                         #[cfg(not(feature = "no_function"))]
                         "private" => Token::Private,
                         #[cfg(not(feature = "no_module"))]
@@ -895,6 +997,12 @@ fn get_next_token_inner(
                 return Some((Token::MapStart, start_pos));
             }
 
+            // Attribute
+            ('#', '[') => {
+                eat_next(stream, pos);
+                return Some((Token::AttributeStart, start_pos));
+            }
+
             // Operators
             ('+', '=') => {
                 eat_next(stream, pos);
@@ -1171,27 +1279,188 @@ impl InputStream for MultiInputsStream<'_> {
     }
 }
 
+/// Get the text of a token that may form part of a custom operator symbol.
+pub(crate) fn symbol_text(token: &Token) -> Option<String> {
+    use Token::*;
+
+    match token {
+        LexError(err) => match err.as_ref() {
+            crate::error::LexError::UnexpectedChar(c) => Some(c.to_string()),
+            _ => None,
+        },
+        IntegerConstant(_) | Identifier(_) | CharConstant(_) | StringConst(_) | Comment(_)
+        | Custom(_) | EOF => None,
+        #[cfg(not(feature = "no_float"))]
+        FloatConstant(_) => None,
+        #[cfg(feature = "decimal")]
+        DecimalConstant(_) => None,
+        token => {
+            let syntax = token.syntax();
+
+            if syntax.chars().all(|c| c.is_ascii_punctuation()) {
+                Some(syntax.into_owned())
+            } else {
+                None
+            }
+        }
+    }
+}
+
 /// An iterator on a `Token` stream.
 pub struct TokenIterator<'a> {
+    /// Reference to the scripting `Engine`, if any, for custom operators and disabled symbols.
+    engine: Option<&'a Engine>,
     /// Current state.
     state: TokenizeState,
     /// Current position.
     pos: Position,
     /// Input character stream.
     stream: MultiInputsStream<'a>,
+    /// Tokens read ahead while matching custom operators, in reverse order.
+    buffer: Vec<(Token, Position)>,
+}
+
+impl<'a> TokenIterator<'a> {
+    /// Get the next raw token, either from the look-ahead buffer or the input stream.
+    fn next_raw(&mut self) -> Option<(Token, Position)> {
+        self.buffer
+            .pop()
+            .or_else(|| get_next_token(&mut self.stream, &mut self.state, &mut self.pos))
+    }
+
+    /// Turn a keyword or symbol disabled in the `Engine` into an error token.
+    fn check_disabled(&self, token: Token) -> Token {
+        use Token::*;
+
+        let disabled_symbols = match self.engine {
+            Some(engine) if !engine.disabled_symbols.is_empty() => &engine.disabled_symbols,
+            _ => return token,
+        };
+
+        match token {
+            IntegerConstant(_) | Identifier(_) | CharConstant(_) | StringConst(_) | LexError(_)
+            | Comment(_) | EOF => token,
+            #[cfg(not(feature = "no_float"))]
+            FloatConstant(_) => token,
+            #[cfg(feature = "decimal")]
+            DecimalConstant(_) => token,
+            token => {
+                let syntax = token.syntax();
+
+                if disabled_symbols.contains(syntax.as_ref()) {
+                    LexError(Box::new(crate::error::LexError::ImproperSymbol(format!(
+                        "'{}' is disabled",
+                        syntax
+                    ))))
+                } else {
+                    token
+                }
+            }
+        }
+    }
+
+    /// Try to combine adjacent symbol tokens into the longest matching custom operator.
+    fn match_custom(
+        &mut self,
+        engine: &Engine,
+        token: Token,
+        text: String,
+        pos: Position,
+    ) -> (Token, Position) {
+        let operators = &engine.custom_operators;
+
+        let mut symbol = text;
+        let mut matched = if operators.contains_key(&symbol) {
+            Some((1, symbol.clone()))
+        } else {
+            None
+        };
+        let mut parts = vec![(token, pos)];
+        let mut end = Position {
+            line: pos.line,
+            pos: pos.pos.saturating_add(symbol.chars().count() as u16),
+        };
+
+        while let Some((next, next_pos)) = self.next_raw() {
+            let text = match symbol_text(&next) {
+                Some(text) if next_pos == end => text,
+                _ => {
+                    self.buffer.push((next, next_pos));
+                    break;
+                }
+            };
+
+            let combined = format!("{}{}", symbol, text);
+
+            if !operators.keys().any(|op| op.starts_with(&combined)) {
+                self.buffer.push((next, next_pos));
+                break;
+            }
+
+            end.pos = end.pos.saturating_add(text.chars().count() as u16);
+            symbol = combined;
+            parts.push((next, next_pos));
+
+            if operators.contains_key(&symbol) {
+                matched = Some((parts.len(), symbol.clone()));
+            }
+        }
+
+        match matched {
+            Some((num, symbol)) => {
+                parts.drain(num..).rev().for_each(|t| self.buffer.push(t));
+
+                if self.buffer.is_empty() {
+                    self.state.non_unary = false;
+                }
+
+                (Token::Custom(symbol), pos)
+            }
+            None => {
+                parts.drain(1..).rev().for_each(|t| self.buffer.push(t));
+                parts.pop().unwrap()
+            }
+        }
+    }
 }
 
 impl<'a> Iterator for TokenIterator<'a> {
     type Item = (Token, Position);
 
     fn next(&mut self) -> Option<Self::Item> {
-        get_next_token(&mut self.stream, &mut self.state, &mut self.pos)
+        let (token, pos) = self.next_raw()?;
+
+        let engine = match self.engine {
+            Some(engine) if !engine.custom_operators.is_empty() => engine,
+            _ => return Some((self.check_disabled(token), pos)),
+        };
+        let operators = &engine.custom_operators;
+
+        let (token, pos) = match token {
+            // Identifiers registered as custom operators
+            Token::Identifier(s) if operators.contains_key(&s) => {
+                if self.buffer.is_empty() {
+                    self.state.non_unary = false;
+                }
+                (Token::Custom(s), pos)
+            }
+            // Symbols that may start a custom operator
+            token => match symbol_text(&token) {
+                Some(text) if operators.keys().any(|op| op.starts_with(&text)) => {
+                    self.match_custom(engine, token, text, pos)
+                }
+                _ => (token, pos),
+            },
+        };
+
+        Some((self.check_disabled(token), pos))
     }
 }
 
 /// Tokenize an input text stream.
 pub fn lex<'a>(input: &'a [&'a str], max_string_size: usize) -> TokenIterator<'a> {
     TokenIterator {
+        engine: None,
         state: TokenizeState {
             max_string_size,
             non_unary: false,
@@ -1203,5 +1472,15 @@ pub fn lex<'a>(input: &'a [&'a str], max_string_size: usize) -> TokenIterator<'a
         stream: MultiInputsStream {
             streams: input.iter().map(|s| s.chars().peekable()).collect(),
         },
+        buffer: Vec::new(),
+    }
+}
+
+/// Tokenize an input text stream, recognizing the custom operators and disabled symbols
+/// registered in an `Engine`.
+pub(crate) fn lex_with_engine<'a>(input: &'a [&'a str], engine: &'a Engine) -> TokenIterator<'a> {
+    TokenIterator {
+        engine: Some(engine),
+        ..lex(input, engine.max_string_size)
     }
 }
@@ -6,6 +6,9 @@
 
 use crate::fn_native::{shared_make_mut, shared_take, Shared};
 
+#[cfg(not(feature = "no_object"))]
+use crate::any::Dynamic;
+
 use crate::stdlib::{
     any::TypeId,
     borrow::Borrow,
@@ -24,6 +27,9 @@ use crate::stdlib::{
 #[cfg(not(feature = "no_std"))]
 use crate::stdlib::collections::hash_map::DefaultHasher;
 
+#[cfg(not(feature = "no_object"))]
+use crate::stdlib::collections::HashMap;
+
 #[cfg(feature = "no_std")]
 use ahash::AHasher;
 
@@ -96,6 +102,25 @@ pub fn calc_fn_spec<'a>(
     s.finish()
 }
 
+/// A `u64` hash of an identifier, calculated once when a script is parsed so that variables can
+/// be looked up by comparing integers instead of strings.
+///
+/// The hash depends only on the name, so it is the same for all engines and scripts.
+/// Different names may (very rarely) share a hash, so a match must still be confirmed by name.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IdentHash(u64);
+
+/// Calculate the `IdentHash` of an identifier.
+pub fn calc_ident_hash(name: &str) -> IdentHash {
+    #[cfg(feature = "no_std")]
+    let mut s: AHasher = Default::default();
+    #[cfg(not(feature = "no_std"))]
+    let mut s = DefaultHasher::new();
+
+    s.write(name.as_bytes());
+    IdentHash(s.finish())
+}
+
 /// A type to hold a number of values in static storage for no-allocation, quick access.
 /// If too many items are stored, it converts into using a `Vec`.
 ///
@@ -908,3 +933,263 @@ impl ImmutableString {
         shared_make_mut(&mut self.0)
     }
 }
+
+/// Storage of a `Map`, which depends on the `ordered_map` feature.
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "ordered_map"))]
+mod map_inner {
+    pub use crate::stdlib::collections::hash_map::{IntoIter, Iter, IterMut};
+    pub type Inner<K, V> = crate::stdlib::collections::HashMap<K, V>;
+}
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "ordered_map")]
+mod map_inner {
+    pub use indexmap::map::{IntoIter, Iter, IterMut};
+    pub type Inner<K, V> = indexmap::IndexMap<K, V>;
+}
+
+/// Hash map of `Dynamic` values with `ImmutableString` keys.
+///
+/// Under the `ordered_map` feature, keys are kept in insertion order. The API is the same with or
+/// without the feature.
+///
+/// Not available under the `no_object` feature.
+#[cfg(not(feature = "no_object"))]
+#[derive(Clone, Default)]
+pub struct Map(map_inner::Inner<ImmutableString, Dynamic>);
+
+#[cfg(not(feature = "no_object"))]
+impl Map {
+    /// Create a new, empty `Map`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Create a new, empty `Map` with space for at least `capacity` properties.
+    #[inline(always)]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(map_inner::Inner::with_capacity(capacity))
+    }
+    /// Number of properties in the `Map`.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Is the `Map` empty?
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Does the `Map` contain a property?
+    #[inline(always)]
+    pub fn contains_key<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> bool
+    where
+        ImmutableString: Borrow<Q>,
+    {
+        self.0.contains_key(key)
+    }
+    /// Get a reference to the value of a property.
+    #[inline(always)]
+    pub fn get<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> Option<&Dynamic>
+    where
+        ImmutableString: Borrow<Q>,
+    {
+        self.0.get(key)
+    }
+    /// Get a mutable reference to the value of a property.
+    #[inline(always)]
+    pub fn get_mut<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<&mut Dynamic>
+    where
+        ImmutableString: Borrow<Q>,
+    {
+        self.0.get_mut(key)
+    }
+    /// Get a mutable reference to the value of a property, adding it as `()` if it does not exist.
+    #[inline(always)]
+    pub(crate) fn get_mut_or_insert(&mut self, key: ImmutableString) -> &mut Dynamic {
+        self.0.entry(key).or_default()
+    }
+    /// Set the value of a property, returning the old value if any.
+    ///
+    /// Under the `ordered_map` feature, a property that already exists keeps its position.
+    #[inline(always)]
+    pub fn insert(&mut self, key: ImmutableString, value: Dynamic) -> Option<Dynamic> {
+        self.0.insert(key, value)
+    }
+    /// Remove a property, returning its value if any.
+    ///
+    /// Under the `ordered_map` feature, the remaining properties stay in order.
+    #[inline(always)]
+    pub fn remove<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<Dynamic>
+    where
+        ImmutableString: Borrow<Q>,
+    {
+        #[cfg(not(feature = "ordered_map"))]
+        return self.0.remove(key);
+        #[cfg(feature = "ordered_map")]
+        return self.0.shift_remove(key);
+    }
+    /// Remove all properties.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+    /// Iterate through all the properties.
+    #[inline(always)]
+    pub fn iter(&self) -> MapIter<'_> {
+        MapIter(self.0.iter())
+    }
+    /// Iterate through all the properties, with mutable references to their values.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> MapIterMut<'_> {
+        MapIterMut(self.0.iter_mut())
+    }
+    /// Iterate through the names of all the properties.
+    #[inline(always)]
+    pub fn keys(&self) -> impl Iterator<Item = &ImmutableString> {
+        self.0.keys()
+    }
+    /// Iterate through the values of all the properties.
+    #[inline(always)]
+    pub fn values(&self) -> impl Iterator<Item = &Dynamic> {
+        self.0.values()
+    }
+    /// Iterate through mutable references to the values of all the properties.
+    #[inline(always)]
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Dynamic> {
+        self.0.values_mut()
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl<Q: Hash + Eq + ?Sized> Index<&Q> for Map
+where
+    ImmutableString: Borrow<Q>,
+{
+    type Output = Dynamic;
+
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.get(key).expect("property not found in Map")
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl FromIterator<(ImmutableString, Dynamic)> for Map {
+    fn from_iter<T: IntoIterator<Item = (ImmutableString, Dynamic)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl Extend<(ImmutableString, Dynamic)> for Map {
+    fn extend<T: IntoIterator<Item = (ImmutableString, Dynamic)>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl From<HashMap<ImmutableString, Dynamic>> for Map {
+    fn from(value: HashMap<ImmutableString, Dynamic>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl IntoIterator for Map {
+    type Item = (ImmutableString, Dynamic);
+    type IntoIter = MapIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MapIntoIter(self.0.into_iter())
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a ImmutableString, &'a Dynamic);
+    type IntoIter = MapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl<'a> IntoIterator for &'a mut Map {
+    type Item = (&'a ImmutableString, &'a mut Dynamic);
+    type IntoIter = MapIterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over the properties of a `Map`.
+#[cfg(not(feature = "no_object"))]
+pub struct MapIter<'a>(map_inner::Iter<'a, ImmutableString, Dynamic>);
+
+#[cfg(not(feature = "no_object"))]
+impl<'a> Iterator for MapIter<'a> {
+    type Item = (&'a ImmutableString, &'a Dynamic);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl ExactSizeIterator for MapIter<'_> {}
+
+/// Iterator over the properties of a `Map`, with mutable references to their values.
+#[cfg(not(feature = "no_object"))]
+pub struct MapIterMut<'a>(map_inner::IterMut<'a, ImmutableString, Dynamic>);
+
+#[cfg(not(feature = "no_object"))]
+impl<'a> Iterator for MapIterMut<'a> {
+    type Item = (&'a ImmutableString, &'a mut Dynamic);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl ExactSizeIterator for MapIterMut<'_> {}
+
+/// Iterator consuming the properties of a `Map`.
+#[cfg(not(feature = "no_object"))]
+pub struct MapIntoIter(map_inner::IntoIter<ImmutableString, Dynamic>);
+
+#[cfg(not(feature = "no_object"))]
+impl Iterator for MapIntoIter {
+    type Item = (ImmutableString, Dynamic);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[cfg(not(feature = "no_object"))]
+impl ExactSizeIterator for MapIntoIter {}
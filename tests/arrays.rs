@@ -1,5 +1,9 @@
 #![cfg(not(feature = "no_index"))]
-use rhai::{Array, Engine, EvalAltResult, RegisterFn, INT};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, RegisterFn, Scope, INT};
+use std::sync::{Arc, RwLock};
+
+#[cfg(not(feature = "no_object"))]
+use rhai::Map;
 
 #[test]
 fn test_arrays() -> Result<(), Box<EvalAltResult>> {
@@ -19,8 +23,8 @@ fn test_arrays() -> Result<(), Box<EvalAltResult>> {
         engine.eval::<INT>(
             r"
                 let x = [2, 9];
-                x.insert(-1, 1);
-                x.insert(999, 3);
+                x.insert(0, 1);
+                x.insert(3, 3);
 
                 let r = x.remove(2);
 
@@ -58,6 +62,768 @@ fn test_arrays() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+fn test_array_append_concat() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // `append` and `+=` extend the array in place
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2]; let y = [3, 4]; x.append(y); len(x) * 10 + len(y)")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2]; let y = [3, 4]; x += y; len(x) * 10 + len(y)")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2]; append(x, [3]); x[2]")?,
+        3
+    );
+
+    // `+` returns a new array, leaving both operands untouched
+    assert_eq!(
+        engine.eval::<INT>(
+            "let x = [1, 2]; let y = [3]; let z = x + y; len(x) * 100 + len(y) * 10 + len(z)"
+        )?,
+        213
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2]; let z = x + x; z[3]")?,
+        2
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_array_homogeneous_mixed() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Arrays behave the same whether or not all elements share a type
+    let script = r#"
+        let x = [];
+        for i in range(0, 1000) { x.push(i); }
+        let before = x[999];
+        x.push("hello");
+        x.insert(0, true);
+        x[1] = 42;
+        let sum = 0;
+        for v in x { if type_of(v) == type_of(0) { sum += v; } }
+        [before, len(x), sum, x[0], x[len(x) - 1]]
+    "#;
+
+    let r = engine.eval::<Array>(script)?;
+
+    assert_eq!(r[0].clone().cast::<INT>(), 999);
+    assert_eq!(r[1].clone().cast::<INT>(), 1002);
+    assert_eq!(r[2].clone().cast::<INT>(), 999 * 1000 / 2 + 42);
+    assert!(r[3].clone().cast::<bool>());
+    assert_eq!(r[4].clone().cast::<String>(), "hello");
+
+    let r = engine.eval::<Array>("let x = [1, 2, 3]; x.truncate(1); x.push(true); x")?;
+    assert_eq!(r.len(), 2);
+    assert_eq!(r[0].clone().cast::<INT>(), 1);
+    assert!(r[1].clone().cast::<bool>());
+
+    // Homogeneous arrays are still readable by reference as an Array
+    let x = engine.eval::<Dynamic>("[1, 2, 3]")?;
+    assert!(x.is::<Array>());
+    assert_eq!(x.downcast_ref::<Array>().map(|a| a.len()), Some(3));
+
+    let mut scope = Scope::new();
+    engine.consume_with_scope(&mut scope, "let x = [1, 2, 3];")?;
+    assert_eq!(scope.get_value::<Array>("x").map(|a| a.len()), Some(3));
+
+    Ok(())
+}
+
+#[test]
+fn test_array_destructure() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let (a, b, c) = [1, 2, 3]; a * 100 + b * 10 + c")?,
+        123
+    );
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let x = 40;
+                let (x, y) = [x + 1, x];
+                x + y - 39
+            "
+        )?,
+        42
+    );
+    assert_eq!(engine.eval::<String>(r#"let (s) = ["hello"]; s"#)?, "hello");
+
+    // Unpacked variables are block-scoped like any other
+    assert_eq!(
+        engine.eval::<INT>("let a = 1; { let (a, b) = [10, 20]; a += b; } a")?,
+        1
+    );
+
+    // The array must hold exactly as many elements as there are variables
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let (a, b) = [1, 2, 3]; a")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let (a, b, c) = [1, 2]; a")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let (a, b) = 42; a")
+            .expect_err("should error"),
+        EvalAltResult::ErrorMismatchOutputType(_, _)
+    ));
+
+    assert!(engine.compile("let (a, b) [1, 2];").is_err());
+    assert!(engine.compile("let (a, 1) = [1, 2];").is_err());
+    assert!(engine.compile("let (a b) = [1, 2];").is_err());
+    assert!(engine.compile("const (a, b) = [1, 2];").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_array_negative_index() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x[-1]")?, 3);
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x[-3]")?, 1);
+    assert_eq!(engine.eval::<INT>("[1, 2, 3][-2]")?, 2);
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3]; x[-1] = 42; x[2]")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [[1, 2], [3, 4]]; x[-1][-2]")?,
+        3
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let x = [1, 2, 3]; x[-4]")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(3, -4, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let x = []; x[-1]")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(0, -1, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_array_from_rust() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let array: Dynamic = (1..=3).map(|x| x as INT).collect();
+    scope.push_dynamic("x", array);
+    scope.push_dynamic("y", Dynamic::from_array(vec!["hello".into(), true.into()]));
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x[2]")?, 3);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x.len()")?, 3);
+    assert_eq!(
+        engine.eval_with_scope::<String>(&mut scope, "type_of(x)")?,
+        "array"
+    );
+    assert_eq!(
+        engine.eval_with_scope::<String>(&mut scope, "y[0]")?,
+        "hello"
+    );
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "x[0] + x[1] + x[2] == 6")?);
+
+    engine.eval_with_scope::<()>(&mut scope, "x.push(4); y += x;")?;
+    assert_eq!(
+        scope.get_value::<Array>("y").expect("y should exist").len(),
+        6
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_array_clone_deep() -> Result<(), Box<EvalAltResult>> {
+    let inner: Dynamic = vec![Dynamic::from(1 as INT), Dynamic::from(2 as INT)].into();
+    let mut original = Dynamic::from_array(vec![inner, "hello".to_string().into()]);
+
+    let copy = original.clone_deep();
+
+    // Mutate the nested array of the original
+    let nested = &mut original
+        .downcast_mut::<Array>()
+        .expect("should be an array")[0];
+    nested
+        .downcast_mut::<Array>()
+        .expect("should be an array")
+        .push(Dynamic::from(3 as INT));
+
+    let copy = copy.cast::<Array>();
+    assert_eq!(copy[0].clone().cast::<Array>().len(), 2);
+    assert_eq!(copy[1].clone().cast::<String>(), "hello");
+
+    let original = original.cast::<Array>();
+    assert_eq!(original[0].clone().cast::<Array>().len(), 3);
+
+    // The deep copy is usable from script like any other array
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+    scope.push_dynamic("x", Dynamic::from_array(copy).clone_deep());
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x[0][1]")?, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_array_insert_remove() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let x = [2, 3]; insert(x, 0, 1); x[0] * 100 + x[1] * 10 + x[2]")?,
+        123
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 3]; insert(x, 1, 2); x[0] * 100 + x[1] * 10 + x[2]")?,
+        123
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2]; insert(x, 2, 3); x[0] * 100 + x[1] * 10 + x[2]")?,
+        123
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = []; insert(x, 0, 42); x[0]")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3]; let r = remove(x, 1); r * 10 + len(x)")?,
+        22
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = [1, 2]; insert(x, 3, 42)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(2, 3, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = [1, 2]; insert(x, -1, 42)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(2, -1, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let x = [1, 2, 3]; remove(x, 3)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(3, 3, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let x = []; remove(x, 0)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(0, 0, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_array_stack_queue() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // LIFO
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let x = [];
+                x.push(1);
+                x.push(2);
+                x.push(3);
+                x.pop() * 100 + x.pop() * 10 + x.pop()
+            "
+        )?,
+        321
+    );
+
+    // FIFO
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let x = [];
+                x.unshift(1);
+                x.unshift(2);
+                x.unshift(3);
+                x.pop() * 100 + x.pop() * 10 + x.pop()
+            "
+        )?,
+        123
+    );
+
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3]; shift(x) * 10 + len(x)")?,
+        12
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"let x = [1, 2]; x.unshift("a"); len(x)"#)?,
+        3
+    );
+
+    engine.eval::<()>("let x = []; pop(x)")?;
+    engine.eval::<()>("let x = []; shift(x)")?;
+
+    Ok(())
+}
+
+#[test]
+fn test_array_resize_fill() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2]; resize(x, 5, 42); len(x) + x[1] + x[4]")?,
+        49
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; resize(x, 1, 42); len(x) + x[0]")?,
+        2
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3]; resize(x, 0, 42); len(x)")?,
+        0
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3]; fill(x, 7); len(x) + x[0] + x[1] + x[2]")?,
+        24
+    );
+    assert_eq!(engine.eval::<INT>("let x = []; fill(x, 7); len(x)")?, 0);
+
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = [1, 2]; resize(x, -1, 42)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(2, -1, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_array_zip() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let pairs = engine.eval::<Array>(r#"zip([1, 2, 3], ["a", "b"])"#)?;
+    assert_eq!(pairs.len(), 2);
+
+    let first = pairs[0].clone().cast::<Array>();
+    assert_eq!(first[0].clone().cast::<INT>(), 1);
+    assert_eq!(first[1].clone().cast::<String>(), "a");
+
+    let second = pairs[1].clone().cast::<Array>();
+    assert_eq!(second[0].clone().cast::<INT>(), 2);
+    assert_eq!(second[1].clone().cast::<String>(), "b");
+
+    assert_eq!(engine.eval::<INT>("len(zip([], [1, 2, 3]))")?, 0);
+    assert_eq!(
+        engine.eval::<INT>("let x = zip([1, 2], [3, 4, 5]); x[1][0] * 10 + x[1][1]")?,
+        24
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_array_predicates() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    let checked = Arc::new(RwLock::new(Vec::<INT>::new()));
+    let log = checked.clone();
+    engine.register_fn("is_small", move |x: INT| {
+        log.write().unwrap().push(x);
+        x < 10
+    });
+
+    assert!(engine.eval::<bool>(r#"[1, 2, 3].all(Fn("is_small"))"#)?);
+    assert_eq!(*checked.read().unwrap(), vec![1, 2, 3]);
+
+    // Stops on first false
+    checked.write().unwrap().clear();
+    assert!(!engine.eval::<bool>(r#"[1, 20, 3].all(Fn("is_small"))"#)?);
+    assert_eq!(*checked.read().unwrap(), vec![1, 20]);
+
+    // Stops on first true
+    checked.write().unwrap().clear();
+    assert!(engine.eval::<bool>(r#"[20, 2, 30].any(Fn("is_small"))"#)?);
+    assert_eq!(*checked.read().unwrap(), vec![20, 2]);
+
+    checked.write().unwrap().clear();
+    assert!(!engine.eval::<bool>(r#"[20, 2, 30].none(Fn("is_small"))"#)?);
+    assert!(engine.eval::<bool>(r#"[20, 30].none(Fn("is_small"))"#)?);
+    assert_eq!(*checked.read().unwrap(), vec![20, 2, 20, 30]);
+
+    // Empty arrays
+    checked.write().unwrap().clear();
+    assert!(engine.eval::<bool>(r#"[].all(Fn("is_small"))"#)?);
+    assert!(!engine.eval::<bool>(r#"[].any(Fn("is_small"))"#)?);
+    assert!(engine.eval::<bool>(r#"[].none(Fn("is_small"))"#)?);
+    assert!(checked.read().unwrap().is_empty());
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        assert!(engine.eval::<bool>(
+            r#"
+                fn is_even(x) { x % 2 == 0 }
+                [2, 4, 6].all(Fn("is_even")) && !any([1, 3], Fn("is_even"))
+            "#
+        )?);
+
+        // Errors in the predicate are propagated
+        assert!(engine
+            .eval::<bool>(
+                r#"
+                    fn fail(x) { throw "oops"; }
+                    [1, 2].any(Fn("fail"))
+                "#
+            )
+            .is_err());
+
+        assert!(matches!(
+            *engine
+                .eval::<bool>(
+                    r#"
+                        fn not_bool(x) { x }
+                        [1, 2].all(Fn("not_bool"))
+                    "#
+                )
+                .expect_err("should error"),
+            EvalAltResult::ErrorMismatchOutputType(_, _)
+        ));
+
+        // Recursion through a predicate is still bounded by the call stack limit
+        #[cfg(not(feature = "unchecked"))]
+        assert!(engine
+            .eval::<bool>(r#"fn f(x) { [x].all(Fn("f")) } f(1)"#)
+            .is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_array_count() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("is_even", |x: INT| x % 2 == 0);
+
+    assert_eq!(
+        engine.eval::<INT>(r#"[1, 2, 3, 4, 6].count(Fn("is_even"))"#)?,
+        3
+    );
+    assert_eq!(engine.eval::<INT>(r#"count([], Fn("is_even"))"#)?, 0);
+
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                fn is_odd(x) { x % 2 == 1 }
+                [1, 2, 3, 4, 5].count(Fn("is_odd"))
+            "#
+        )?,
+        3
+    );
+
+    assert_eq!(engine.eval::<INT>("[1, 2, 1, 3, 1].count(1)")?, 3);
+    assert_eq!(
+        engine.eval::<INT>(r#"[1, "hello", true, "hello"].count("hello")"#)?,
+        2
+    );
+    assert_eq!(engine.eval::<INT>("[1, 2, 3].count(true)")?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_array_min_max_by() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Ties return the first element encountered
+    assert_eq!(
+        engine.eval::<String>(r#"["a", "abc", "xy", "def"].max_by(Fn("len"))"#)?,
+        "abc"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"min_by(["ab", "c", "xyz", "d"], Fn("len"))"#)?,
+        "c"
+    );
+    assert_eq!(engine.eval::<()>(r#"[].max_by(Fn("len"))"#)?, ());
+    assert_eq!(engine.eval::<()>(r#"[].min_by(Fn("abs"))"#)?, ());
+    assert_eq!(engine.eval::<INT>(r#"[3, -7, 5].max_by(Fn("abs"))"#)?, -7);
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        assert_eq!(
+            engine.eval::<INT>(
+                r#"
+                    fn neg(x) { -x }
+                    [3, 1, 2].min_by(Fn("neg"))
+                "#
+            )?,
+            3
+        );
+
+        assert!(engine
+            .eval::<INT>(
+                r#"
+                    fn fail(x) { throw "oops"; }
+                    [1, 2].max_by(Fn("fail"))
+                "#
+            )
+            .is_err());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_array_group_by() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("parity", |x: INT| {
+        let key = if x % 2 == 0 { "even" } else { "odd" };
+        key.to_string()
+    });
+
+    let groups = engine.eval::<Map>(r#"[1, 2, 3, 4, 5, 6, 7].group_by(Fn("parity"))"#)?;
+
+    assert_eq!(groups.len(), 2);
+
+    let evens: Vec<INT> = groups["even"]
+        .clone()
+        .cast::<Array>()
+        .into_iter()
+        .map(|v| v.cast::<INT>())
+        .collect();
+    let odds: Vec<INT> = groups["odd"]
+        .clone()
+        .cast::<Array>()
+        .into_iter()
+        .map(|v| v.cast::<INT>())
+        .collect();
+
+    assert_eq!(evens, vec![2, 4, 6]);
+    assert_eq!(odds, vec![1, 3, 5, 7]);
+
+    // Under `ordered_map`, groups are in the order of their first elements
+    #[cfg(feature = "ordered_map")]
+    {
+        let groups = engine.eval::<Map>(r#"[2, 1, 4, 3].group_by(Fn("parity"))"#)?;
+        let keys: Vec<_> = groups.keys().map(|k| k.to_string()).collect();
+        assert_eq!(keys, vec!["even", "odd"]);
+
+        let groups = engine.eval::<Map>(r#"[1, 2, 3, 4].group_by(Fn("parity"))"#)?;
+        let keys: Vec<_> = groups.keys().map(|k| k.to_string()).collect();
+        assert_eq!(keys, vec!["odd", "even"]);
+    }
+
+    assert_eq!(
+        engine.eval::<INT>(r#"group_by([], Fn("parity")).len()"#)?,
+        0
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<Map>(r#"[1, 2].group_by(Fn("abs"))"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorMismatchOutputType(_, _)
+    ));
+
+    #[cfg(not(feature = "no_function"))]
+    assert!(engine
+        .eval::<Map>(
+            r#"
+                fn fail(x) { throw "oops"; }
+                [1, 2].group_by(Fn("fail"))
+            "#
+        )
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_array_chunk_windows() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let to_vecs = |arr: Array| -> Vec<Vec<INT>> {
+        arr.into_iter()
+            .map(|v| {
+                v.cast::<Array>()
+                    .into_iter()
+                    .map(|x| x.cast::<INT>())
+                    .collect()
+            })
+            .collect()
+    };
+
+    // The last chunk may be shorter
+    assert_eq!(
+        to_vecs(engine.eval::<Array>("[1, 2, 3, 4, 5].chunk(2)")?),
+        vec![vec![1, 2], vec![3, 4], vec![5]]
+    );
+    assert_eq!(
+        to_vecs(engine.eval::<Array>("chunk([1, 2, 3, 4, 5], 5)")?),
+        vec![vec![1, 2, 3, 4, 5]]
+    );
+    assert_eq!(engine.eval::<INT>("len(chunk([], 3))")?, 0);
+
+    // Windows are always exactly the requested size
+    assert_eq!(
+        to_vecs(engine.eval::<Array>("[1, 2, 3, 4, 5].windows(3)")?),
+        vec![vec![1, 2, 3], vec![2, 3, 4], vec![3, 4, 5]]
+    );
+    assert_eq!(
+        to_vecs(engine.eval::<Array>("windows([1, 2, 3, 4, 5], 1)")?),
+        vec![vec![1], vec![2], vec![3], vec![4], vec![5]]
+    );
+
+    // Too short for a single window gives an empty array
+    assert_eq!(engine.eval::<INT>("len(windows([1, 2, 3, 4, 5], 6))")?, 0);
+
+    assert!(matches!(
+        *engine
+            .eval::<Array>("[1, 2, 3, 4, 5].chunk(0)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<Array>("[1, 2, 3, 4, 5].windows(0)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(engine.eval::<Array>("[1, 2, 3].chunk(-1)").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_array_truncate() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; truncate(x, 2); len(x)")?,
+        2
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; truncate(x, 10); len(x)")?,
+        4
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; keep_first(x, 0); len(x)")?,
+        0
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; keep_first(x, 3); x[2]")?,
+        3
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; keep_last(x, 2); x[0] * 10 + x[1]")?,
+        34
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; keep_last(x, 5); x[0] + len(x)")?,
+        5
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3, 4]; keep_last(x, 0); len(x)")?,
+        0
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = [1, 2, 3]; truncate(x, -1)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(3, -1, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = [1, 2, 3]; keep_last(x, -2)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArrayBounds(3, -2, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_array_get() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // In range
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x.get(0)")?, 1);
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; get(x, 2)")?, 3);
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x.get(1, 42)")?, 2);
+
+    // Out of range never errors
+    engine.eval::<()>("let x = [1, 2, 3]; x.get(3)")?;
+    engine.eval::<()>("let x = []; x.get(0)")?;
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x.get(100, 42)")?, 42);
+    assert_eq!(
+        engine.eval::<String>(r#"let x = [1, 2, 3]; x.get(5, "none")"#)?,
+        "none"
+    );
+
+    // Negative indices count from the end
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x.get(-1)")?, 3);
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x.get(-3)")?, 1);
+    engine.eval::<()>("let x = [1, 2, 3]; x.get(-4)")?;
+    assert_eq!(engine.eval::<INT>("let x = [1, 2, 3]; x.get(-4, 0)")?, 0);
+    assert_eq!(
+        engine.eval::<INT>(&format!("let x = [1, 2, 3]; x.get({}, 0)", INT::MIN + 1))?,
+        0
+    );
+
+    // The array is not modified
+    assert_eq!(
+        engine.eval::<INT>("let x = [1, 2, 3]; x.get(-1); x.get(10); len(x)")?,
+        3
+    );
+    assert_eq!(
+        engine.eval::<INT>("const x = [1, 2, 3]; x.get(1) + x.get(9, 40)")?,
+        42
+    );
+
+    // The default can be any value, including function pointers and custom types
+    assert_eq!(
+        engine.eval::<String>(r#"let x = []; let f = x.get(0, Fn("abs")); f.name"#)?,
+        "abs"
+    );
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point(INT, INT);
+
+    let mut engine = Engine::new();
+    engine.register_fn("point", |x: INT, y: INT| Point(x, y));
+
+    assert_eq!(
+        engine.eval::<Point>("let x = []; x.get(0, point(1, 2))")?,
+        Point(1, 2)
+    );
+
+    Ok(())
+}
+
 #[test]
 #[cfg(not(feature = "no_object"))]
 fn test_array_with_structs() -> Result<(), Box<EvalAltResult>> {
@@ -0,0 +1,46 @@
+use rhai::{Engine, EvalAltResult, Position, INT};
+
+#[test]
+fn test_assert() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    engine.eval::<()>("assert(true)")?;
+    engine.eval::<()>("let x = 42; assert(x > 0)")?;
+    assert_eq!(engine.eval::<INT>("assert(1 < 2); 42")?, 42);
+
+    assert!(matches!(
+        *engine.eval::<()>("let x = 0;\nassert(x > 0)").expect_err("expects error"),
+        EvalAltResult::ErrorRuntime(s, pos) if s == "Assertion failed" && pos == Position::new(2, 1)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_assert_eq() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    engine.eval::<()>("assert_eq(40 + 2, 42)")?;
+    engine.eval::<()>(r#"assert_eq("hello" + " world", "hello world")"#)?;
+    engine.eval::<()>("let x = [1, 2, 3]; assert_eq(x.len(), 3)")?;
+
+    assert!(matches!(
+        *engine.eval::<()>("let x = 41; assert_eq(x, 42)").expect_err("expects error"),
+        EvalAltResult::ErrorRuntime(s, _)
+            if s.contains("41") && s.contains("42") && s == "Assertion failed: left = 41, right = 42"
+    ));
+
+    assert!(matches!(
+        *engine.eval::<()>(r#"assert_eq("foo", 'x')"#).expect_err("expects error"),
+        EvalAltResult::ErrorRuntime(s, _) if s.contains(r#""foo""#) && s.contains("'x'")
+    ));
+
+    // A script can define its own assertion helpers
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval::<INT>("fn assert_eq(a, b) { 42 } assert_eq(1, 2)")?,
+        42
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, RegisterFn, Scope, INT};
 
 #[test]
 fn test_binary_ops() -> Result<(), Box<EvalAltResult>> {
@@ -20,3 +20,127 @@ fn test_binary_ops() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(not(feature = "only_i32"))]
+#[cfg(not(feature = "only_i64"))]
+fn test_binary_ops_mixed_int() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("a", 5_i32);
+    scope.push("b", 200_u8);
+    scope.push("c", u64::MAX);
+    scope.push("d", -1_i8);
+
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "a == 5")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "5 == a")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "a != 6")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "a < 6 && 6 > a")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "a <= 5 && a >= 5")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "b > 100")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "d < 0 && 0 > d")?);
+
+    // Values beyond the range of INT still compare correctly
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "c > 9223372036854775807")?);
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "c != -1")?);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        scope.push("e", -3_i128);
+        scope.push("f", u128::MAX);
+        scope.push("g", 7_u128);
+
+        assert!(engine.eval_with_scope::<bool>(&mut scope, "e == -3 && -3 == e")?);
+        assert!(engine.eval_with_scope::<bool>(&mut scope, "e < 0 && 0 > e")?);
+        assert!(engine.eval_with_scope::<bool>(&mut scope, "f > 9223372036854775807")?);
+        assert!(engine.eval_with_scope::<bool>(&mut scope, "f > -1 && -1 < f")?);
+        assert!(engine.eval_with_scope::<bool>(&mut scope, "g == 7 && 7 == g")?);
+        assert!(engine.eval_with_scope::<bool>(&mut scope, "g != -7 && g >= 7 && g <= 7")?);
+        assert_eq!(
+            engine.eval_with_scope::<INT>(&mut scope, "compare(g, 8)")?,
+            -1
+        );
+        assert_eq!(
+            engine.eval_with_scope::<INT>(&mut scope, "compare(-1, f)")?,
+            -1
+        );
+        assert_eq!(
+            engine.eval_with_scope::<INT>(&mut scope, "compare(f, -1)")?,
+            1
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_binary_ops_custom_type() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Vec2 {
+        x: INT,
+        y: INT,
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_type_with_name::<Vec2>("Vec2");
+    engine.register_fn("vec2", |x: INT, y: INT| Vec2 { x, y });
+    engine.register_fn("+", |a: Vec2, b: Vec2| Vec2 {
+        x: a.x + b.x,
+        y: a.y + b.y,
+    });
+    engine.register_fn("==", |a: Vec2, b: Vec2| a == b);
+
+    assert_eq!(
+        engine.eval::<Vec2>("vec2(1, 2) + vec2(3, 4)")?,
+        Vec2 { x: 4, y: 6 }
+    );
+    assert_eq!(
+        engine.eval::<Vec2>("let a = vec2(1, 2); a += vec2(1, 1); a")?,
+        Vec2 { x: 2, y: 3 }
+    );
+    assert!(engine.eval::<bool>("vec2(1, 2) + vec2(3, 4) == vec2(4, 6)")?);
+    assert!(!engine.eval::<bool>("vec2(1, 2) == vec2(2, 1)")?);
+
+    // Comparing with a different type is always false
+    assert!(!engine.eval::<bool>("vec2(1, 2) == 42")?);
+
+    // Missing operators are errors
+    assert!(matches!(
+        *engine.eval::<Vec2>("vec2(1, 2) - vec2(3, 4)").expect_err("expects error"),
+        EvalAltResult::ErrorFunctionNotFound(ref f, _) if f == "- (Vec2, Vec2)"
+    ));
+    assert!(matches!(
+        *engine.eval::<bool>("vec2(1, 2) < vec2(3, 4)").expect_err("expects error"),
+        EvalAltResult::ErrorFunctionNotFound(ref f, _) if f == "< (Vec2, Vec2)"
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+fn test_binary_ops_custom_type_in() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Debug, Clone)]
+    struct Opaque(INT);
+
+    let mut engine = Engine::new();
+
+    engine.register_type_with_name::<Opaque>("Opaque");
+    engine.register_fn("opaque", |x: INT| Opaque(x));
+
+    // Without a registered `==`, `in` finds nothing instead of failing
+    assert!(!engine.eval::<bool>("let x = opaque(1); x in [x, opaque(2), 42]")?);
+
+    // ... while comparing directly is an error
+    assert!(matches!(
+        *engine.eval::<bool>("let x = opaque(1); x == x").expect_err("expects error"),
+        EvalAltResult::ErrorFunctionNotFound(ref f, _) if f == "== (Opaque, Opaque)"
+    ));
+
+    engine.register_fn("==", |a: Opaque, b: Opaque| a.0 == b.0);
+    assert!(engine.eval::<bool>("opaque(2) in [opaque(1), opaque(2)]")?);
+
+    Ok(())
+}
@@ -23,3 +23,63 @@ fn test_chars() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_chars_index_multibyte() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Indexing is by character position, not byte offset
+    assert_eq!(engine.eval::<char>(r#"let s = "héllo wörld"; s[1]"#)?, 'é');
+    assert_eq!(engine.eval::<char>(r#"let s = "héllo wörld"; s[7]"#)?, 'ö');
+    assert_eq!(engine.eval::<String>(r#"type_of("日本"[1])"#)?, "char");
+
+    // Replacing characters of different byte widths
+    assert_eq!(
+        engine.eval::<String>(r#"let s = "héllo"; s[1] = 'e'; s"#)?,
+        "hello"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"let s = "hello"; s[0] = '日'; s[4] = '❤'; s"#)?,
+        "日ell❤"
+    );
+
+    // Out-of-range indices report the index and the length in characters
+    assert!(matches!(
+        *engine
+            .eval::<char>(r#"let s = "héllo"; s[5]"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorStringBounds(5, 5, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<()>(r#"let s = "日本"; s[2] = 'x';"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorStringBounds(2, 2, _)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_chars_negative_index() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<char>(r#"let s = "héllo"; s[-1]"#)?, 'o');
+    assert_eq!(engine.eval::<char>(r#"let s = "héllo"; s[-4]"#)?, 'é');
+    assert_eq!(engine.eval::<char>(r#""日本"[-2]"#)?, '日');
+    assert_eq!(
+        engine.eval::<String>(r#"let s = "hello"; s[-1] = '❤'; s"#)?,
+        "hell❤"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<char>(r#"let s = "héllo"; s[-6]"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorStringBounds(5, -6, _)
+    ));
+
+    Ok(())
+}
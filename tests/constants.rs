@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, ParseErrorType, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, ParseErrorType, Scope, INT};
 
 #[test]
 fn test_constant() -> Result<(), Box<EvalAltResult>> {
@@ -21,3 +21,90 @@ fn test_constant() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_global_constant() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_global_constant("MAX_HP", 100 as INT);
+
+    // Separate evaluations all see the same constant
+    assert_eq!(engine.eval::<INT>("MAX_HP")?, 100);
+    assert_eq!(engine.eval::<INT>("let hp = MAX_HP - 58; hp")?, 42);
+
+    let mut scope = Scope::new();
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "MAX_HP / 2")?, 50);
+    assert!(scope.is_empty());
+
+    // Visible inside functions
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval::<INT>("fn heal(hp) { MAX_HP - hp } heal(10)")?,
+        90
+    );
+
+    // Cannot be modified
+    assert!(matches!(
+        *engine.eval::<()>("MAX_HP = 1;").expect_err("expects error"),
+        EvalAltResult::ErrorAssignmentToConstant(x, _) if x == "MAX_HP"
+    ));
+    assert!(matches!(
+        *engine.eval::<()>("MAX_HP += 1;").expect_err("expects error"),
+        EvalAltResult::ErrorAssignmentToConstant(x, _) if x == "MAX_HP"
+    ));
+    assert_eq!(engine.eval::<INT>("MAX_HP")?, 100);
+
+    // Variables in scope or declared by the script take precedence
+    scope.push("MAX_HP", 1 as INT);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "MAX_HP")?, 1);
+    assert_eq!(engine.eval::<INT>("let MAX_HP = 2; MAX_HP")?, 2);
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(engine.eval::<INT>("fn f(MAX_HP) { MAX_HP } f(3)")?, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_global_constant_runtime_scope() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_global_constant("X", 100 as INT);
+
+    let ast = engine.compile("X + 1")?;
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 101);
+
+    // A variable in the scope at runtime shadows the constant
+    let mut scope = Scope::new();
+    scope.push("X", 1 as INT);
+    assert_eq!(engine.eval_ast_with_scope::<INT>(&mut scope, &ast)?, 2);
+
+    // Replacing the constant affects scripts already compiled
+    engine.register_global_constant("X", 41 as INT);
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 42);
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_global_constant_array() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_global_constant(
+        "LEVELS",
+        vec![Dynamic::from(1 as INT), Dynamic::from(5 as INT)],
+    );
+
+    assert_eq!(engine.eval::<INT>("LEVELS[1]")?, 5);
+    assert_eq!(engine.eval::<INT>("LEVELS.len()")?, 2);
+
+    assert!(matches!(
+        *engine.eval::<()>("LEVELS[0] = 42;").expect_err("expects error"),
+        EvalAltResult::ErrorAssignmentToConstant(x, _) if x == "LEVELS"
+    ));
+
+    // Mutating methods only work on a copy
+    assert_eq!(engine.eval::<INT>("LEVELS.push(9); LEVELS.len()")?, 2);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use rhai::{Engine, EvalAltResult, RegisterFn, INT};
+
+#[test]
+fn test_custom_operators() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_custom_operator("<=>", 60)?;
+    engine.register_fn("<=>", |x: INT, y: INT| -> INT {
+        if x < y {
+            -1
+        } else if x > y {
+            1
+        } else {
+            0
+        }
+    });
+
+    assert_eq!(engine.eval::<INT>("1 <=> 2")?, -1);
+    assert_eq!(engine.eval::<INT>("2<=>2")?, 0);
+    assert_eq!(engine.eval::<INT>("3 <=> -2")?, 1);
+    assert_eq!(engine.eval::<INT>("1 + 2 <=> 2 * 2")?, -1);
+    assert!(engine.eval::<bool>("1 <= 2")?);
+    assert!(engine.eval::<bool>("1 <=> 2 == -1")?);
+
+    engine.register_custom_operator("mix", 90)?;
+    engine.register_fn("mix", |x: INT, y: INT| x * 10 + y);
+
+    assert_eq!(engine.eval::<INT>("1 + 2 mix 3")?, 24);
+    assert_eq!(engine.eval::<INT>("let x = 4; x mix x mix x")?, 444);
+    assert!(engine.eval::<INT>("let mix = 4; mix").is_err());
+
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(engine.eval::<INT>("fn mix(x, y) { x + y } 1 mix 2")?, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_operators_reserved() {
+    let mut engine = Engine::new();
+
+    assert!(engine.register_custom_operator("+", 80).is_err());
+    assert!(engine.register_custom_operator("==", 60).is_err());
+    assert!(engine.register_custom_operator("if", 60).is_err());
+    assert!(engine.register_custom_operator("a+", 60).is_err());
+    assert!(engine.register_custom_operator("", 60).is_err());
+    assert!(engine.register_custom_operator("<=>", 0).is_err());
+}
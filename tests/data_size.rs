@@ -48,6 +48,13 @@ fn test_max_string_size() -> Result<(), Box<EvalAltResult>> {
         EvalAltResult::ErrorDataTooLarge(_, 10, 100, _)
     ));
 
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#""hello" * 3"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10, 15, _)
+    ));
+
     engine.set_max_string_size(0);
 
     assert_eq!(
@@ -92,6 +99,28 @@ fn test_max_array_size() -> Result<(), Box<EvalAltResult>> {
             .expect_err("should error"),
         EvalAltResult::ErrorDataTooLarge(_, 10, 12, _)
     ));
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r"
+                    let x = [1,2,3,4,5,6];
+                    x.append([7,8,9,10,11,12]);
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10, 12, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r"
+                    let x = [1,2,3,4,5,6];
+                    x += [7,8,9,10,11,12];
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10, 12, pos) if !pos.is_none()
+    ));
     assert!(matches!(
         *engine
             .eval::<Array>(
@@ -104,6 +133,18 @@ fn test_max_array_size() -> Result<(), Box<EvalAltResult>> {
             .expect_err("should error"),
         EvalAltResult::ErrorDataTooLarge(_, 10, 100, _)
     ));
+    assert!(matches!(
+        *engine
+            .eval::<Array>(
+                r"
+                    let x = [1,2,3,4,5,6];
+                    x.resize(11, 42);
+                    x
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10, 11, _)
+    ));
 
     assert!(matches!(
         *engine
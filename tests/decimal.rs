@@ -0,0 +1,64 @@
+#![cfg(feature = "decimal")]
+use rhai::{Decimal, Engine, EvalAltResult, INT};
+use std::str::FromStr;
+
+#[test]
+fn test_decimal() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<Decimal>("0.1d + 0.2d")?,
+        Decimal::from_str("0.3").unwrap()
+    );
+    assert!(engine.eval::<bool>("0.1d + 0.2d == 0.3d")?);
+    assert_eq!(
+        engine.eval::<Decimal>("let x = 19.99d; x * 3d - 0.97d")?,
+        Decimal::from_str("59.00").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>("10d / 4d")?,
+        Decimal::from_str("2.5").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>("-1_000.25d % 1d")?,
+        Decimal::from_str("-0.25").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>("abs(-1.5d)")?,
+        Decimal::from_str("1.5").unwrap()
+    );
+    assert_eq!(engine.eval::<Decimal>("1.5e2d")?, Decimal::from(150));
+    assert!(engine.eval::<bool>("1.10d > 1.09d")?);
+    assert_eq!(engine.eval::<String>("type_of(42d)")?, "decimal");
+
+    Ok(())
+}
+
+#[test]
+fn test_decimal_conversions() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<Decimal>("to_decimal(42)")?, Decimal::from(42));
+    assert_eq!(engine.eval::<INT>("to_int(-42.9d)")?, -42);
+    assert_eq!(
+        engine.eval::<String>(r#""total: " + 1.50d"#)?,
+        "total: 1.50"
+    );
+    assert_eq!(engine.eval::<String>("(0.1d + 0.2d).to_string()")?, "0.3");
+
+    Ok(())
+}
+
+#[test]
+fn test_decimal_errors() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(matches!(
+        *engine.eval::<Decimal>("1d / 0d").expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    assert!(engine.compile("1.2.3d").is_err());
+
+    Ok(())
+}
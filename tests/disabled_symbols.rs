@@ -0,0 +1,120 @@
+use rhai::{Engine, EvalAltResult, ParseErrorType, INT};
+
+#[test]
+fn test_disabled_symbols() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.disable_symbol("while");
+    engine.disable_symbol("~");
+
+    assert_eq!(
+        engine.eval::<INT>("let x = 0; if x < 10 { x = 42; } x")?,
+        42
+    );
+
+    let err = engine
+        .compile("let x = 0; while x < 10 { x += 1; }")
+        .expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::BadInput("'while' is disabled".to_string())
+    );
+    assert_eq!(err.1.position(), Some(12));
+
+    assert!(engine.compile("2 ~ 3").is_err());
+    assert!(engine.compile("let x = 2; x ~= 3;").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_disabled_symbols_structural() {
+    let script = r#"
+        let x = [1, 2, 3];
+        fn foo(a, b) { if a > b { a } else { b } }
+        for y in x { x[0] += foo(y, 2); }
+        loop { break; }
+        x.len
+    "#;
+
+    for symbol in &[
+        "let", "fn", "if", "else", "for", "in", "loop", "break", "(", ")", "{", "}", "[", "]", ",",
+        ";", ".", "=", "+=", ">",
+    ] {
+        let mut engine = Engine::new();
+        engine.disable_symbol(symbol);
+
+        assert!(engine.compile(script).is_err(), "{}", symbol);
+    }
+}
+
+#[test]
+fn test_disallowed_syntax() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    let script = "let x = 0; while x < 10 { x += 1; } x";
+
+    assert_eq!(engine.eval::<INT>(script)?, 10);
+
+    engine.set_allow_looping(false);
+
+    for (script, pos) in &[
+        (script, 12),
+        ("loop { break; }", 1),
+        ("for x in range(0, 10) {}", 1),
+    ] {
+        let err = engine.compile(script).expect_err("should error");
+        assert!(matches!(&*err.0, ParseErrorType::ForbiddenSyntax(_)));
+        assert_eq!(err.1.position(), Some(*pos));
+    }
+    assert_eq!(
+        engine
+            .compile(script)
+            .expect_err("should error")
+            .to_string(),
+        "'while' loop is not allowed (line 1, position 12)"
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = 0; if x < 10 { x = 42; } x")?,
+        42
+    );
+
+    engine.set_allow_if_expression(false);
+    engine.set_allow_statement_expr(false);
+
+    assert!(matches!(
+        *engine
+            .compile("let x = if true { 1 } else { 2 };")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::ForbiddenSyntax(_)
+    ));
+    assert!(matches!(
+        *engine
+            .compile("let x = { 42 };")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::ForbiddenSyntax(_)
+    ));
+    assert_eq!(
+        engine.eval::<INT>("let x = 1; if true { x = 2; } { x }")?,
+        2
+    );
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        assert_eq!(engine.eval::<INT>("fn foo() { 42 } foo()")?, 42);
+
+        engine.set_allow_fn_definitions(false);
+
+        assert!(matches!(
+            *engine
+                .compile("fn foo() { 42 } foo()")
+                .expect_err("should error")
+                .0,
+            ParseErrorType::ForbiddenSyntax(_)
+        ));
+    }
+
+    Ok(())
+}
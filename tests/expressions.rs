@@ -26,6 +26,36 @@ fn test_expressions() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+fn test_expressions_reject_statements() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval_expression::<INT>("1 + 2 * 3")?, 7);
+    engine.compile_expression("1 + 2 * 3")?;
+
+    for script in &[
+        "let x = 1; x",
+        "const x = 1",
+        "while true {}",
+        "loop { break; }",
+        "for x in [1, 2] {}",
+        "return 1",
+        "throw 1",
+        "fn foo() { 42 }",
+        "1; 2",
+    ] {
+        assert!(engine.compile_expression(script).is_err(), "{}", script);
+        assert!(matches!(
+            *engine
+                .eval_expression::<INT>(script)
+                .expect_err("should error"),
+            EvalAltResult::ErrorParsing(_, _)
+        ));
+    }
+
+    Ok(())
+}
+
 /// This example taken from https://github.com/jonathandturner/rhai/issues/115
 #[test]
 #[cfg(not(feature = "no_object"))]
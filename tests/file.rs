@@ -0,0 +1,55 @@
+#![cfg(not(feature = "no_std"))]
+#![cfg(not(target_arch = "wasm32"))]
+use rhai::{Engine, EvalAltResult, Scope, INT};
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_script(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rhai-{}-{}.rhai", name, std::process::id()));
+    fs::write(&path, contents).expect("should write script file");
+    path
+}
+
+#[test]
+fn test_eval_file() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let path = temp_script("eval_file", "let x = 40; x + 2");
+
+    let result = engine.eval_file::<INT>(path.clone());
+    let _ = fs::remove_file(&path);
+    assert_eq!(result?, 42);
+
+    Ok(())
+}
+
+#[test]
+fn test_eval_file_with_scope() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+    scope.push("y", 1 as INT);
+
+    let path = temp_script("eval_file_with_scope", "let x = y + 41; x");
+
+    let result = engine.eval_file_with_scope::<INT>(&mut scope, path.clone());
+    let _ = fs::remove_file(&path);
+    assert_eq!(result?, 42);
+    assert_eq!(scope.get_value::<INT>("x"), Some(42));
+
+    Ok(())
+}
+
+#[test]
+fn test_eval_file_missing() {
+    let engine = Engine::new();
+    let path = std::env::temp_dir().join("rhai-this-file-does-not-exist.rhai");
+
+    let err = engine
+        .eval_file::<INT>(path.clone())
+        .expect_err("should error");
+
+    assert!(matches!(
+        *err,
+        EvalAltResult::ErrorReadingScriptFile(ref p, _, ref e)
+            if *p == path && e.kind() == std::io::ErrorKind::NotFound
+    ));
+}
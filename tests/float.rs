@@ -1,5 +1,5 @@
 #![cfg(not(feature = "no_float"))]
-use rhai::{Engine, EvalAltResult, RegisterFn, FLOAT};
+use rhai::{Engine, EvalAltResult, ParseErrorType, Position, RegisterFn, FLOAT, INT};
 
 const EPSILON: FLOAT = 0.000_000_000_1;
 
@@ -77,3 +77,180 @@ fn test_float_func() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_float_int_comparison() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>("let count = 4; count > 3.5")?);
+    assert!(engine.eval::<bool>("let count = 3; count < 3.5")?);
+    assert!(engine.eval::<bool>("3.5 < 4 && 3.5 <= 4 && 4.5 > 4 && 4.5 >= 4")?);
+    assert!(engine.eval::<bool>("42 == 42.0 && 42.0 == 42")?);
+    assert!(engine.eval::<bool>("42 != 42.5 && 42.5 != 42")?);
+    assert!(engine.eval::<bool>("42 <= 42.0 && 42 >= 42.0")?);
+
+    // Large integers lose precision when promoted to floating-point
+    #[cfg(not(feature = "only_i32"))]
+    {
+        assert!(engine.eval::<bool>("9007199254740993 == 9007199254740992.0")?);
+        assert!(!engine.eval::<bool>("9007199254740993 > 9007199254740992.0")?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_float_exponent_literals() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!((engine.eval::<FLOAT>("6.022e23")? - 6.022e23).abs() < 1e10);
+    assert!((engine.eval::<FLOAT>("1.6e-19")? - 1.6e-19).abs() < 1e-30);
+    assert_eq!(engine.eval::<FLOAT>("1E+3")?, 1000.0);
+    assert_eq!(engine.eval::<FLOAT>("-2e2")?, -200.0);
+    assert_eq!(engine.eval::<FLOAT>("let x = 5e0; x")?, 5.0);
+
+    let err = engine.compile("let x = 1e;").expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::BadInput("Invalid number: '1e'".to_string())
+    );
+    assert_eq!(err.1, Position::new(1, 9));
+
+    assert!(engine.compile("1e+").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_float_special_values() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>("nan() != nan()")?);
+    assert!(!engine.eval::<bool>("let x = nan(); x == x")?);
+    assert!(engine.eval::<bool>("is_nan(nan())")?);
+
+    assert_eq!(engine.eval::<FLOAT>("inf()")?, FLOAT::INFINITY);
+    assert_eq!(engine.eval::<FLOAT>("-inf()")?, FLOAT::NEG_INFINITY);
+    assert!(engine.eval::<bool>("-inf() < -1e300 && 1e300 < inf()")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_float_floor_ceil_int() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("floor_int(2.7)")?, 2);
+    assert_eq!(engine.eval::<INT>("floor_int(-2.3)")?, -3);
+    assert_eq!(engine.eval::<INT>("ceil_int(2.3)")?, 3);
+    assert_eq!(engine.eval::<INT>("ceil_int(-2.7)")?, -2);
+    assert_eq!(engine.eval::<INT>("floor_int(42.0)")?, 42);
+
+    // The float-returning versions are unchanged
+    assert_eq!(engine.eval::<FLOAT>("floor(2.7)")?, 2.0);
+
+    assert!(matches!(
+        *engine.eval::<INT>("floor_int(1e100)").expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(ref msg, _) if msg.starts_with("Integer overflow")
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("ceil_int(-1e100)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("floor_int(inf())")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    assert!(matches!(
+        *engine.eval::<INT>("ceil_int(nan())").expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(ref msg, _) if msg.contains("NaN")
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_float_mul_add() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<FLOAT>("mul_add(2.0, 3.0, 4.0)")?, 10.0);
+
+    // The product 0.1 * 10.0 rounds to exactly 1.0 before the addition,
+    // but the fused operation keeps the representation error of 0.1
+    assert_eq!(engine.eval::<FLOAT>("0.1 * 10.0 - 1.0")?, 0.0);
+    assert_eq!(
+        engine.eval::<FLOAT>("mul_add(0.1, 10.0, -1.0)")?,
+        (0.1 as FLOAT).mul_add(10.0, -1.0)
+    );
+    assert!(engine.eval::<FLOAT>("mul_add(0.1, 10.0, -1.0)")? > 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_float_copysign_signum() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<FLOAT>("copysign(3.0, -1.0)")?, -3.0);
+    assert_eq!(engine.eval::<FLOAT>("copysign(-3.0, 2.0)")?, 3.0);
+    assert_eq!(engine.eval::<FLOAT>("copysign(3.0, -0.0)")?, -3.0);
+    assert_eq!(engine.eval::<FLOAT>("copysign(3.0, 0.0)")?, 3.0);
+
+    assert_eq!(engine.eval::<FLOAT>("signum(-42.5)")?, -1.0);
+    assert_eq!(engine.eval::<FLOAT>("signum(0.001)")?, 1.0);
+    assert!(engine.eval::<bool>("is_nan(signum(nan()))")?);
+
+    // The sign of zero is kept
+    assert_eq!(engine.eval::<FLOAT>("signum(0.0)")?, 1.0);
+    assert_eq!(engine.eval::<FLOAT>("signum(-0.0)")?, -1.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_float_round_to() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<FLOAT>("round_to(1.23456, 2)")?, 1.23);
+    assert_eq!(engine.eval::<FLOAT>("round_to(-1.23456, 3)")?, -1.235);
+    assert_eq!(engine.eval::<FLOAT>("round_to(2.5, 0)")?, 3.0);
+    assert_eq!(engine.eval::<FLOAT>("round_to(1234.5, -2)")?, 1200.0);
+    assert_eq!(engine.eval::<FLOAT>("round_to(1250.0, -2)")?, 1300.0);
+
+    // Extreme digits never produce infinities or NaN
+    assert_eq!(engine.eval::<FLOAT>("round_to(1.23456, 400)")?, 1.23456);
+    assert_eq!(engine.eval::<FLOAT>("round_to(1e300, 20)")?, 1e300);
+    assert_eq!(engine.eval::<FLOAT>("round_to(1.23456, -400)")?, 0.0);
+    assert_eq!(
+        engine.eval::<FLOAT>(&format!("round_to(1.5, {} - 1)", INT::MIN + 1))?,
+        0.0
+    );
+    assert_eq!(
+        engine.eval::<FLOAT>(&format!("round_to(1.5, {})", INT::MAX))?,
+        1.5
+    );
+    assert!((engine.eval::<FLOAT>("round_to(1.6e300, -300)")? / 2e300 - 1.0).abs() < EPSILON);
+    assert_eq!(engine.eval::<FLOAT>("round_to(inf(), 2)")?, FLOAT::INFINITY);
+
+    Ok(())
+}
+
+#[test]
+fn test_float_constants() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>("(sqrt_2() * sqrt_2() - 2.0).abs() < epsilon() * 4.0")?);
+    assert_eq!(engine.eval::<FLOAT>("pi()")?, std::f64::consts::PI);
+    assert_eq!(engine.eval::<FLOAT>("e()")?, std::f64::consts::E);
+    assert_eq!(engine.eval::<FLOAT>("ln_2()")?, std::f64::consts::LN_2);
+    assert_eq!(engine.eval::<FLOAT>("ln_10()")?, std::f64::consts::LN_10);
+    assert_eq!(engine.eval::<FLOAT>("epsilon()")?, FLOAT::EPSILON);
+    assert!(engine.eval::<bool>("(ln(e()) - 1.0).abs() < epsilon()")?);
+    assert!(engine.eval::<bool>("(exp(ln_2()) - 2.0).abs() < epsilon() * 4.0")?);
+
+    Ok(())
+}
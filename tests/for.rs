@@ -30,6 +30,106 @@ fn test_for_array() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_for_counter() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r"
+        let sum = 0;
+        let inputs = [5, 4, 3, 2, 1];
+
+        for (i, x) in inputs {
+            sum += i * x;
+        }
+
+        sum
+    ";
+
+    assert_eq!(engine.eval::<INT>(script)?, 20);
+
+    // The counter works for any iterable, and both variables go out of scope after the loop
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let i = 100;
+                let last = 0;
+                for (i, x) in range(10, 20) {
+                    if x > 12 { break; }
+                    last = i;
+                }
+                i + last
+            "
+        )?,
+        102
+    );
+
+    assert!(engine.compile("for (i x) in [1] {}").is_err());
+    assert!(engine.compile("for (i, x in [1] {}").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_for_range() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let sum = 0;
+                for i in range(0, 10000) { sum += i; }
+                sum
+            "
+        )?,
+        49995000
+    );
+
+    // `break` and `continue` behave the same in the fast path
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let sum = 0;
+                for i in range(0, 100) {
+                    if i % 2 == 1 { continue; }
+                    if i > 10 { break; }
+                    sum += i;
+                }
+                sum
+            "
+        )?,
+        30
+    );
+
+    // The loop variable can be reassigned, even to another type, without affecting the loop
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                let count = 0;
+                for i in range(0, 5) {
+                    count += i;
+                    i = "hello";
+                }
+                count
+            "#
+        )?,
+        10
+    );
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let sum = 0;
+                for (i, x) in range(10, 14) { sum += i * 100 + x; }
+                sum
+            "
+        )?,
+        646
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_for_string() -> Result<(), Box<EvalAltResult>> {
     let engine = Engine::new();
@@ -75,3 +175,31 @@ fn test_for_object() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_for_object_entries() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r#"
+        let sum = 0;
+        let keys = "";
+        let map = #{a: 1, b: 2, c: 3};
+
+        for (key, value) in map {
+            keys += key;
+            sum += value;
+        }
+
+        for pair in map {
+            sum += pair[1] * 10;
+        }
+
+        keys.len + sum
+    "#;
+
+    assert_eq!(engine.eval::<INT>(script)?, 69);
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 #![cfg(not(feature = "no_function"))]
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, FnAccess, RegisterFn, INT};
+
+#[cfg(not(feature = "no_float"))]
+use rhai::FLOAT;
 
 #[test]
 fn test_functions() -> Result<(), Box<EvalAltResult>> {
@@ -91,3 +94,173 @@ fn test_function_pointers() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_function_overloads() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("log", |msg: &str| format!("info: {}", msg));
+    engine.register_fn("log", |level: INT, msg: &str| format!("{}: {}", level, msg));
+    engine.register_fn("log", |level: bool, msg: &str| {
+        format!("{}! {}", level, msg)
+    });
+
+    assert_eq!(engine.eval::<String>(r#"log("hello")"#)?, "info: hello");
+    assert_eq!(engine.eval::<String>(r#"log(3, "hello")"#)?, "3: hello");
+    assert_eq!(
+        engine.eval::<String>(r#"log(true, "hello")"#)?,
+        "true! hello"
+    );
+
+    assert!(matches!(
+        *engine.eval::<String>(r#"log("hello", "world", "!")"#).expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(sig, _) if sig.starts_with("log (")
+    ));
+    assert!(matches!(
+        *engine.eval::<String>(r#"log('x', "hello")"#).expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(sig, _) if sig.starts_with("log (char, ")
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_float"))]
+fn test_function_overloads_ambiguous() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("scale", |x: INT, y: FLOAT| x as FLOAT * y);
+    engine.register_fn("scale", |x: FLOAT, y: INT| x * y as FLOAT);
+
+    assert_eq!(engine.eval::<FLOAT>("scale(2, 1.5)")?, 3.0);
+
+    // Either argument can be promoted, so no overload is a better match than the other
+    let err = engine
+        .eval::<FLOAT>("scale(2, 3)")
+        .expect_err("should be ambiguous");
+
+    match *err {
+        EvalAltResult::ErrorAmbiguousFunctionCall(ref call, ref candidates, pos) => {
+            assert!(call.starts_with("scale ("));
+            assert_eq!(candidates.len(), 2);
+            assert!(candidates.iter().all(|c| c.starts_with("scale (")));
+            assert_eq!(pos.line(), Some(1));
+        }
+        ref err => panic!("expected ambiguous function call, got {:?}", err),
+    }
+
+    let msg = err.to_string();
+    assert!(msg.starts_with("Ambiguous function call scale ("));
+    assert!(msg.contains("which can match any of: "));
+
+    Ok(())
+}
+
+#[test]
+fn test_functions_merge_ast() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let lib = engine.compile(
+        r#"
+            fn helper(x) { x * 2 }
+            fn greet() { "library" }
+        "#,
+    )?;
+
+    let script = engine.compile(
+        r#"
+            fn greet() { "script" }
+            helper(21)
+        "#,
+    )?;
+
+    // The library is untouched and can be reused
+    let ast = lib.merge(&script);
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 42);
+    assert_eq!(engine.eval_ast::<INT>(&(&lib + &script))?, 42);
+
+    // Later definitions win
+    let mut ast = lib.clone();
+    ast += engine.compile("greet()")?;
+    assert_eq!(engine.eval_ast::<String>(&ast)?, "library");
+
+    ast.combine(script);
+    ast += engine.compile("greet()")?;
+    assert_eq!(engine.eval_ast::<String>(&ast)?, "script");
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_function_pointer_apply() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                fn add(x, y) { x + y }
+                let args = [40];
+                args.push(2);
+                apply(Fn("add"), args)
+            "#
+        )?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                fn add(x, y) { x + y }
+                Fn("add").apply([1, 2])
+            "#
+        )?,
+        3
+    );
+    assert_eq!(engine.eval::<INT>(r#"apply(Fn("abs"), [-42])"#)?, 42);
+    assert_eq!(
+        engine.eval::<String>(r#"let f = Fn("sub_string"); f.apply(["hello", 1, 3])"#)?,
+        "ell"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(
+                r#"
+                    fn add(x, y) { x + y }
+                    apply(Fn("add"), [1, 2, 3])
+                "#
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(ref f, _) if f.starts_with("add (")
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_function_iter_ast() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast = engine.compile(
+        r#"
+            fn init() { 0 }
+            private fn update(state, delta) { state + delta }
+            init()
+        "#,
+    )?;
+
+    let mut functions: Vec<_> = ast.iter_functions().collect();
+    functions.sort_by(|a, b| a.1.cmp(b.1));
+
+    assert_eq!(functions.len(), 2);
+
+    assert_eq!(functions[0].0, FnAccess::Public);
+    assert_eq!(functions[0].1, "init");
+    assert!(functions[0].2.is_empty());
+
+    assert_eq!(functions[1].0, FnAccess::Private);
+    assert_eq!(functions[1].1, "update");
+    assert_eq!(functions[1].2, vec!["state", "delta"]);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 #![cfg(not(feature = "no_object"))]
 
-use rhai::{Engine, EvalAltResult, Map, Scope, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Position, RegisterFn, Scope, INT};
 
 #[test]
 fn test_map_indexing() -> Result<(), Box<EvalAltResult>> {
@@ -88,6 +88,122 @@ fn test_map_indexing() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+fn test_map_get_set() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(r#"let x = #{a: 1, b: 2}; x.get("a")"#)?,
+        1
+    );
+    engine.eval::<()>(r#"let x = #{a: 1, b: 2}; x.get("z")"#)?;
+    assert_eq!(
+        engine.eval::<INT>(r#"let x = #{a: 1, b: 2}; x.get("b", 42)"#)?,
+        2
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"let x = #{a: 1, b: 2}; x.get("z", 42)"#)?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"let x = #{}; get(x, "name", "anonymous")"#)?,
+        "anonymous"
+    );
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"let x = #{a: 1}; x.set("a", 10); x.set("b", 20); x.a + x.b + x.len()"#
+        )?,
+        32
+    );
+    assert!(engine.eval::<bool>(r#"let x = #{}; x.set("flag", true); x.get("flag", false)"#)?);
+    assert_eq!(
+        engine.eval::<INT>(r#"const x = #{a: 40}; x.get("a") + x.get("z", 2)"#)?,
+        42
+    );
+
+    // Values and defaults can be of any type, including function pointers and custom types
+    assert_eq!(
+        engine
+            .eval::<String>(r#"let x = #{}; x.set("f", Fn("abs")); let f = x.get("f"); f.name"#)?,
+        "abs"
+    );
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point(INT, INT);
+
+    let mut engine = Engine::new();
+    engine.register_fn("point", |x: INT, y: INT| Point(x, y));
+
+    assert_eq!(
+        engine.eval::<Point>(r#"let x = #{}; x.get("p", point(1, 2))"#)?,
+        Point(1, 2)
+    );
+    assert_eq!(
+        engine.eval::<Point>(r#"let x = #{}; x.set("p", point(3, 4)); x.p"#)?,
+        Point(3, 4)
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+fn test_map_entries() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let m = engine
+        .eval::<Map>(r#"let m = #{a: 1, b: true, "c$": "hello"}; from_entries(entries(m))"#)?;
+
+    assert_eq!(m.len(), 3);
+    assert_eq!(m["a"].clone().cast::<INT>(), 1);
+    assert!(m["b"].clone().cast::<bool>());
+    assert_eq!(m["c$"].clone().cast::<String>(), "hello");
+
+    assert!(engine.eval::<bool>(
+        r#"
+            let m = #{a: 1, b: 2, c: 3};
+            let r = from_entries(entries(m));
+            r.len() == m.len() && r.a == m.a && r.b == m.b && r.c == m.c
+        "#
+    )?);
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                let m = #{a: 1, b: 2, c: 3};
+                let out = [];
+                for entry in m.entries() {
+                    if entry[1] > 1 { out.push([entry[0], entry[1] * 10]); }
+                }
+                let r = from_entries(out);
+                r.len() * 100 + r.b + r.c
+            "#
+        )?,
+        250
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<Map>("from_entries([[\"a\", 1], 42])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<Map>("from_entries([[\"a\", 1, 2]])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<Map>("from_entries([[1, 2]])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn test_map_assign() -> Result<(), Box<EvalAltResult>> {
     let engine = Engine::new();
@@ -176,6 +292,58 @@ fn test_map_for() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "ordered_map")]
+#[cfg(not(feature = "no_index"))]
+fn test_map_insertion_order() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r#"
+        let map = #{z: 1, a: 2};
+        map.m = 3;
+        map["b"] = 4;
+        map.a = 42;         // overwriting keeps the original position
+        map
+    "#;
+
+    let map = engine.eval::<Map>(script)?;
+    let keys: Vec<_> = map.keys().map(|k| k.to_string()).collect();
+    assert_eq!(keys, ["z", "a", "m", "b"]);
+
+    assert_eq!(
+        engine.eval::<String>(&format!(
+            r#"let map = {{ {} }}; let s = ""; for k in keys(map) {{ s += k; }} s"#,
+            script
+        ))?,
+        "zamb"
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!(
+            r#"let map = {{ {} }}; let v = values(map); v[0] * 1000 + v[1] * 10 + v[3]"#,
+            script
+        ))?,
+        1424
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!(
+            r#"let map = {{ {} }}; let s = ""; for kv in map {{ s += kv[0]; }} s"#,
+            script
+        ))?,
+        "zamb"
+    );
+
+    // Removing a key keeps the order of the rest
+    assert_eq!(
+        engine.eval::<String>(&format!(
+            r#"let map = {{ {} }}; map.remove("a"); map.to_string()"#,
+            script
+        ))?,
+        "#{z: 1, m: 3, b: 4}"
+    );
+
+    Ok(())
+}
+
 #[test]
 /// Because a Rhai object map literal is almost the same as JSON,
 /// it is possible to convert from JSON into a Rhai object map.
@@ -272,3 +440,97 @@ fn test_map_oop() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_map_from_rust() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let mut map = Map::new();
+    map.insert("a".into(), Dynamic::from(1 as INT));
+    map.insert("b c".into(), "hello".into());
+    scope.push_dynamic("x", Dynamic::from_map(map));
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x.a")?, 1);
+    assert_eq!(
+        engine.eval_with_scope::<String>(&mut scope, r#"x["b c"]"#)?,
+        "hello"
+    );
+    assert_eq!(
+        engine.eval_with_scope::<String>(&mut scope, "type_of(x)")?,
+        "map"
+    );
+
+    engine.eval_with_scope::<()>(&mut scope, "x.d = 42;")?;
+    assert_eq!(
+        scope.get_value::<Map>("x").expect("x should exist").len(),
+        3
+    );
+
+    // Map has the same API with or without `ordered_map`
+    let mut map = engine.eval::<Map>("#{a: 1, b: 2, c: 3}")?;
+    assert!(map.contains_key("b"));
+    assert_eq!(map["a"].clone().cast::<INT>(), 1);
+    assert_eq!(map.remove("b").map(|v| v.cast::<INT>()), Some(2));
+    assert!(map.get("b").is_none());
+    for (_, value) in map.iter_mut() {
+        *value = Dynamic::from(value.as_int().unwrap() * 10);
+    }
+    let mut pairs: Vec<(String, INT)> = map
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.cast::<INT>()))
+        .collect();
+    pairs.sort();
+    assert_eq!(pairs, [("a".to_string(), 10), ("c".to_string(), 30)]);
+
+    let mut hash_map = std::collections::HashMap::new();
+    hash_map.insert("x".into(), Dynamic::from(true));
+    let map = Map::from(hash_map);
+    assert!(map["x"].as_bool().unwrap());
+    assert!(Dynamic::from(map).is::<Map>());
+
+    Ok(())
+}
+
+#[test]
+fn test_map_missing_property() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    // By default, missing properties read as ()
+    assert!(!engine.fail_on_invalid_map_property());
+    engine.eval::<()>("let x = #{a: 1}; x.b")?;
+    engine.eval::<()>("#{a: 1}.b")?;
+    #[cfg(not(feature = "no_index"))]
+    engine.eval::<()>(r#"let x = #{a: 1}; x["b"]"#)?;
+
+    engine.set_fail_on_invalid_map_property(true);
+
+    assert_eq!(engine.eval::<INT>("let x = #{a: 1}; x.a")?, 1);
+
+    let err = engine
+        .eval::<()>("let x = #{a: 1};\nx.b")
+        .expect_err("should error");
+    assert!(matches!(
+        *err,
+        EvalAltResult::ErrorDotExpr(ref s, pos) if s.contains("'b'") && pos == Position::new(2, 3)
+    ));
+
+    assert!(matches!(
+        *engine.eval::<()>("#{a: 1}.b").expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(s, _) if s.contains("'b'")
+    ));
+    assert!(matches!(
+        *engine.eval::<()>("let x = #{a: #{}}; x.a.b").expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(s, _) if s.contains("'b'")
+    ));
+    #[cfg(not(feature = "no_index"))]
+    assert!(matches!(
+        *engine.eval::<()>(r#"let x = #{a: 1}; x["b"]"#).expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(s, _) if s.contains("'b'")
+    ));
+
+    // Missing properties can still be added
+    assert_eq!(engine.eval::<INT>("let x = #{a: 1}; x.b = 42; x.b")?, 42);
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, ParseErrorType, Scope, INT};
 
 #[test]
 fn test_math() -> Result<(), Box<EvalAltResult>> {
@@ -99,3 +99,99 @@ fn test_math() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_math_unchecked_block() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+    scope.push("x", INT::MAX);
+
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine
+            .eval_with_scope::<INT>(&mut scope, "x + 1")
+            .expect_err("expects overflow"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "#[unchecked] { x + 1 }")?,
+        INT::MIN
+    );
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "let y = x; #[unchecked] { y *= 2; } y")?,
+        -2
+    );
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "#[unchecked] { 2 ~ 100 }")?,
+        0
+    );
+
+    // Division by zero is still an error
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine
+            .eval_with_scope::<INT>(&mut scope, "#[unchecked] { x / 0 }")
+            .expect_err("expects division by zero"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    // Functions called from within the block are still checked
+    #[cfg(not(feature = "no_function"))]
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine
+            .eval::<INT>(&format!(
+                "fn inc(n) {{ n + 1 }} #[unchecked] {{ inc({}) }}",
+                INT::MAX
+            ))
+            .expect_err("expects overflow"),
+        EvalAltResult::ErrorInFunctionCall(_, _, _)
+    ));
+
+    assert!(matches!(
+        *engine
+            .compile("#[wrapping] { 1 + 1 }")
+            .expect_err("expects unknown attribute")
+            .0,
+        ParseErrorType::BadInput(_)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_math_euclidean() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("-7 % 3")?, -1);
+    assert_eq!(engine.eval::<INT>("rem_euclid(-7, 3)")?, 2);
+    assert_eq!(engine.eval::<INT>("rem_euclid(7, -3)")?, 1);
+    assert_eq!(engine.eval::<INT>("rem_euclid(7, 3)")?, 1);
+    assert_eq!(engine.eval::<INT>("div_euclid(-7, 3)")?, -3);
+    assert_eq!(engine.eval::<INT>("div_euclid(7, -3)")?, -2);
+    assert_eq!(engine.eval::<INT>("div_euclid(7, 3)")?, 2);
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>("rem_euclid(7, 0)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("div_euclid(7, 0)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    #[cfg(not(feature = "only_i32"))]
+    assert!(matches!(
+        *engine
+            .eval::<INT>("div_euclid(-9223372036854775807 - 1, -1)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    Ok(())
+}
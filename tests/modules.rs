@@ -1,7 +1,7 @@
 #![cfg(not(feature = "no_module"))]
 use rhai::{
     module_resolvers::StaticModuleResolver, Engine, EvalAltResult, Module, ParseError,
-    ParseErrorType, Scope, INT,
+    ParseErrorType, RegisterFn, Scope, INT,
 };
 
 #[test]
@@ -64,6 +64,41 @@ fn test_module_sub_module() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+fn test_module_static() -> Result<(), Box<EvalAltResult>> {
+    let mut clock = Module::new();
+    clock.set_fn_0("now", || Ok(1 as INT));
+    clock.set_var("ticks", 100 as INT);
+
+    let mut calendar = Module::new();
+    calendar.set_fn_0("now", || Ok(2 as INT));
+
+    let mut engine = Engine::new();
+    engine.register_static_module("clock", clock);
+    engine.register_static_module("calendar", calendar);
+    engine.register_fn("now", || 3 as INT);
+
+    assert_eq!(engine.eval::<INT>("clock::now()")?, 1);
+    assert_eq!(engine.eval::<INT>("calendar::now()")?, 2);
+    assert_eq!(engine.eval::<INT>("now()")?, 3);
+    assert_eq!(engine.eval::<INT>("clock::ticks + calendar::now()")?, 102);
+
+    assert!(matches!(
+        *engine.eval::<INT>("calendar::ticks").expect_err("should error"),
+        EvalAltResult::ErrorVariableNotFound(name, _) if name == "calendar::ticks"
+    ));
+    assert!(matches!(
+        *engine.eval::<INT>("clock::later()").expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(name, _) if name == "clock::later"
+    ));
+    assert!(matches!(
+        *engine.eval::<INT>("watch::now()").expect_err("should error"),
+        EvalAltResult::ErrorModuleNotFound(name, _) if name == "watch"
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn test_module_resolver() -> Result<(), Box<EvalAltResult>> {
     let mut resolver = StaticModuleResolver::new();
@@ -258,3 +293,57 @@ fn test_module_export() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+fn test_module_self_contained() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    let helper_ast = engine.compile(
+        r#"
+            fn double(x) { x * 2 }
+            let base = 21;
+            export base;
+        "#,
+    )?;
+    let helper = Module::eval_ast_as_new(Scope::new(), &helper_ast, &engine)?;
+
+    let mut resolver = StaticModuleResolver::new();
+    resolver.insert("helper", helper);
+    engine.set_module_resolver(Some(resolver));
+
+    let script = r#"
+        import "helper" as h;
+        let x = h::double(h::base);
+        {
+            import "helper" as h2;
+            x = h2::double(x) / 2;
+        }
+        x
+    "#;
+
+    let ast = engine.compile_into_self_contained(&Scope::new(), script)?;
+    assert!(ast.is_self_contained());
+    assert!(!engine.compile(script)?.is_self_contained());
+
+    // No module resolver is needed to evaluate the bundle
+    engine.set_module_resolver(None::<StaticModuleResolver>);
+
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 42);
+    assert!(matches!(
+        *engine.eval::<INT>(script).expect_err("should error"),
+        EvalAltResult::ErrorModuleNotFound(path, _) if path == "helper"
+    ));
+
+    // A failed import while bundling reports the path
+    engine.set_module_resolver(Some(StaticModuleResolver::new()));
+
+    assert!(matches!(
+        *engine
+            .compile_into_self_contained(&Scope::new(), r#"import "missing" as m;"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorModuleNotFound(path, _) if path == "missing"
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,610 @@
+use rhai::{
+    Dynamic, Engine, EvalAltResult, FnPtr, ImmutableString, NativeCallContext, Position,
+    RegisterFallibleFn, RegisterFn, RegisterResultFn, Scope, INT,
+};
+
+#[cfg(not(feature = "no_float"))]
+use rhai::FLOAT;
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+fn clamp_checked(value: INT, lo: INT, hi: INT) -> Result<Dynamic, Box<EvalAltResult>> {
+    if lo > hi {
+        Err(format!("invalid range: {} > {}", lo, hi).into())
+    } else {
+        Ok(value.max(lo).min(hi).into())
+    }
+}
+
+#[test]
+fn test_native_result_fn() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_result_fn("clamp_checked", clamp_checked);
+    engine.register_result_fn("sum4", |a: INT, b: INT, c: INT, d: INT| {
+        a.checked_add(b)
+            .and_then(|x| x.checked_add(c))
+            .and_then(|x| x.checked_add(d))
+            .map(Dynamic::from)
+            .ok_or_else(|| "overflow".into())
+    });
+    engine.register_result_fn("double_checked", |x: INT| Ok((x * 2).into()));
+
+    assert_eq!(engine.eval::<INT>("clamp_checked(42, 0, 10)")?, 10);
+    assert_eq!(engine.eval::<INT>("clamp_checked(-1, 0, 10)")?, 0);
+    assert_eq!(engine.eval::<INT>("sum4(1, 2, 3, 36)")?, 42);
+    assert_eq!(engine.eval::<INT>("double_checked(21)")?, 42);
+
+    let err = engine
+        .eval::<INT>("let x = 1;\nclamp_checked(x, 10, 0)")
+        .expect_err("should error");
+
+    assert!(matches!(
+        *err,
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "invalid range: 10 > 0"
+    ));
+    assert_eq!(err.position(), Position::new(2, 1));
+
+    Ok(())
+}
+
+#[test]
+fn test_native_unit_fn() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let config: Arc<Mutex<HashMap<String, INT>>> = Default::default();
+
+    let c = config.clone();
+    engine.register_fn("set_config", move |key: &str, value: INT| {
+        c.lock().unwrap().insert(key.to_string(), value);
+    });
+
+    let c = config.clone();
+    engine.register_fallible_fn("set_config_checked", move |key: &str, value: INT| {
+        if value < 0 {
+            return Err(format!("invalid value for '{}': {}", key, value).into());
+        }
+        c.lock().unwrap().insert(key.to_string(), value);
+        Ok(())
+    });
+
+    engine.consume(r#"set_config("width", 80); set_config_checked("height", 24);"#)?;
+
+    assert_eq!(config.lock().unwrap().get("width"), Some(&80));
+    assert_eq!(config.lock().unwrap().get("height"), Some(&24));
+    assert!(engine.eval::<()>(r#"set_config("depth", 1)"#).is_ok());
+    assert_eq!(
+        engine.eval::<String>(r#"type_of(set_config_checked("depth", 2))"#)?,
+        "()"
+    );
+
+    assert!(matches!(
+        *engine
+            .consume(r#"set_config_checked("width", -1)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "invalid value for 'width': -1"
+    ));
+    assert_eq!(config.lock().unwrap().get("width"), Some(&80));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_float"))]
+#[test]
+fn test_native_option_fn() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    let mut data = HashMap::new();
+    data.insert("answer".to_string(), 42 as INT);
+
+    engine.register_fn("find", move |key: &str| data.get(key).cloned());
+    engine.register_fn("first_word", |s: &str| {
+        s.split_whitespace().next().map(|w| w.to_string())
+    });
+    engine.register_fallible_fn("checked_find", |key: INT| {
+        if key < 0 {
+            Err("negative key".into())
+        } else {
+            Ok(if key == 0 { None } else { Some(key * 2) })
+        }
+    });
+
+    assert_eq!(engine.eval::<INT>(r#"find("answer")"#)?, 42);
+    assert!(engine.eval::<bool>(r#"find("question") == ()"#)?);
+    assert_eq!(engine.eval::<String>(r#"type_of(find("question"))"#)?, "()");
+    assert_eq!(engine.eval::<INT>(r#"find("answer") + 1"#)?, 43);
+
+    assert_eq!(
+        engine.eval::<String>(r#"first_word("hello world")"#)?,
+        "hello"
+    );
+    assert_eq!(engine.eval::<String>(r#"type_of(first_word(""))"#)?, "()");
+
+    assert_eq!(engine.eval::<INT>("checked_find(21)")?, 42);
+    assert_eq!(engine.eval::<String>("type_of(checked_find(0))")?, "()");
+    assert!(engine.eval::<INT>("checked_find(-1)").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_native_string_result_fn() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("parse_int", |s: &str| -> Result<INT, String> {
+        s.trim().parse::<INT>().map_err(|_| "bad input".to_string())
+    });
+    engine.register_fn("check", |x: INT| -> Result<(), String> {
+        if x < 0 {
+            Err(format!("negative number: {}", x))
+        } else {
+            Ok(())
+        }
+    });
+    engine.register_fn("wrap", |x: INT| -> Result<Dynamic, String> {
+        Ok(Dynamic::from(x + 1))
+    });
+
+    assert_eq!(engine.eval::<INT>(r#"parse_int(" 42 ")"#)?, 42);
+    assert_eq!(engine.eval::<INT>("wrap(41)")?, 42);
+    assert_eq!(engine.eval::<String>("type_of(check(1))")?, "()");
+
+    let err = engine
+        .eval::<INT>("let s = \"oops\";\nlet x = parse_int(s);")
+        .expect_err("should error");
+
+    assert!(matches!(
+        *err,
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "bad input"
+    ));
+    assert_eq!(err.position(), Position::new(2, 9));
+
+    assert!(matches!(
+        *engine.consume("check(-1)").expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "negative number: -1"
+    ));
+
+    // The error propagates through 'try' after the 'finally' block runs
+    let mut scope = Scope::new();
+    scope.push("cleaned", false);
+
+    assert!(matches!(
+        *engine
+            .consume_with_scope(
+                &mut scope,
+                r#"try { parse_int("x"); } finally { cleaned = true; }"#
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "bad input"
+    ));
+    assert_eq!(scope.get_value::<bool>("cleaned"), Some(true));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_native_vec_param() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("sum_all", |list: Vec<INT>| list.iter().sum::<INT>());
+    engine.register_fn("join_all", |list: Vec<String>, sep: &str| list.join(sep));
+    engine.register_fn("count_true", |list: Vec<bool>| {
+        list.into_iter().filter(|&x| x).count() as INT
+    });
+
+    assert_eq!(engine.eval::<INT>("sum_all([1, 2, 3, 36])")?, 42);
+    assert_eq!(engine.eval::<INT>("sum_all([])")?, 0);
+    assert_eq!(
+        engine.eval::<INT>("let x = [40, 2]; sum_all(x) + len(x)")?,
+        44
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"join_all(["a", "b", "c"], "-")"#)?,
+        "a-b-c"
+    );
+    assert_eq!(engine.eval::<INT>("count_true([true, false, true])")?, 2);
+
+    // Every standard type is supported as the element type, including arrays and `Dynamic`
+    engine.register_fn("rows", |list: Vec<rhai::Array>| list.len() as INT);
+    engine.register_fn("mixed", |list: Vec<Dynamic>| list.len() as INT);
+    engine.register_fn("first_row", |list: Vec<rhai::Array>| {
+        list.into_iter().next()
+    });
+
+    assert_eq!(engine.eval::<INT>("rows([[1], [2, 3], []])")?, 3);
+    assert_eq!(engine.eval::<INT>(r#"mixed([1, "x", true])"#)?, 3);
+    assert_eq!(engine.eval::<INT>("len(first_row([[1, 2], [3]]))")?, 2);
+    assert!(engine.eval::<bool>("first_row([]) == ()")?);
+    assert!(engine.eval::<INT>("rows([[1], 2])").is_err());
+
+    let err = engine
+        .eval::<INT>(r#"sum_all([1, "two", 3])"#)
+        .expect_err("should error");
+
+    match *err {
+        EvalAltResult::ErrorRuntime(ref msg, pos) => {
+            assert_eq!(
+                msg,
+                &format!(
+                    "Array element 1 is string, expecting {}",
+                    std::any::type_name::<INT>()
+                )
+            );
+            assert!(!pos.is_none());
+        }
+        ref err => panic!("wrong error: {:?}", err),
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_native_hashmap_param() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("sum_values", |map: HashMap<String, INT>| {
+        map.values().sum::<INT>()
+    });
+    engine.register_fn("make_counts", |text: &str| {
+        let mut counts: HashMap<String, INT> = HashMap::new();
+        for word in text.split_whitespace() {
+            *counts.entry(word.to_string()).or_insert(0) += 1;
+        }
+        counts
+    });
+    engine.register_fallible_fn("checked_counts", |n: INT| {
+        if n < 0 {
+            return Err("negative count".into());
+        }
+        let mut map = HashMap::new();
+        map.insert("n".to_string(), n);
+        Ok(map)
+    });
+
+    assert_eq!(engine.eval::<INT>("sum_values(#{a: 1, b: 2, c: 39})")?, 42);
+    assert_eq!(engine.eval::<INT>("sum_values(#{})")?, 0);
+    assert_eq!(
+        engine.eval::<INT>("let m = #{x: 40}; m.y = 2; sum_values(m) + len(m)")?,
+        44
+    );
+
+    assert_eq!(
+        engine.eval::<String>(r#"type_of(make_counts("a b a"))"#)?,
+        "map"
+    );
+    assert_eq!(engine.eval::<INT>(r#"make_counts("a b a").a"#)?, 2);
+    assert_eq!(
+        engine.eval::<INT>(r#"sum_values(make_counts("x y z x"))"#)?,
+        4
+    );
+    assert_eq!(engine.eval::<INT>("checked_counts(7).n")?, 7);
+
+    let err = engine
+        .eval::<INT>(r#"sum_values(#{a: 1, b: "two"})"#)
+        .expect_err("should error");
+
+    match *err {
+        EvalAltResult::ErrorRuntime(ref msg, pos) => {
+            assert_eq!(
+                msg,
+                &format!(
+                    "Property 'b' is string, expecting {}",
+                    std::any::type_name::<INT>()
+                )
+            );
+            assert!(!pos.is_none());
+        }
+        ref err => panic!("wrong error: {:?}", err),
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_float"))]
+fn test_native_overload_resolution() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("describe", |x: INT| format!("int {}", x));
+    engine.register_fn("describe", |x: FLOAT| format!("float {}", x));
+    engine.register_fn("half", |x: FLOAT| x / 2.0);
+    engine.register_fn("scale", |x: INT, y: FLOAT| format!("int-float {} {}", x, y));
+    engine.register_fn("scale", |x: FLOAT, y: INT| format!("float-int {} {}", x, y));
+
+    // Exact matches are always preferred
+    assert_eq!(engine.eval::<String>("describe(3)")?, "int 3");
+    assert_eq!(engine.eval::<String>("describe(3.5)")?, "float 3.5");
+    assert_eq!(engine.eval::<String>("scale(2, 1.5)")?, "int-float 2 1.5");
+    assert_eq!(engine.eval::<String>("scale(1.5, 2)")?, "float-int 1.5 2");
+
+    // Integers are promoted to floating-point when there is no exact match
+    assert_eq!(engine.eval::<FLOAT>("half(3)")?, 1.5);
+    assert_eq!(engine.eval::<FLOAT>("let x = 5; x.half()")?, 2.5);
+
+    // Two candidates promoting the same number of arguments are ambiguous
+    assert!(matches!(
+        *engine.eval::<String>("scale(1, 2)").expect_err("should be ambiguous"),
+        EvalAltResult::ErrorAmbiguousFunctionCall(ref call, ref candidates, _)
+            if call.starts_with("scale (")
+                && candidates.len() == 2
+                && candidates.iter().all(|c| c.starts_with("scale ("))
+    ));
+
+    // No promotion applies to other types
+    assert!(matches!(
+        *engine
+            .eval::<FLOAT>(r#"half("x")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_native_raw_fn_scope() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    // Read a variable from the scope of the calling script by name
+    engine.register_raw_fn(
+        "var",
+        &[TypeId::of::<ImmutableString>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| {
+            let name = args[0].clone().cast::<ImmutableString>();
+
+            Ok(context
+                .scope()
+                .and_then(|scope| scope.get_value::<Dynamic>(&name))
+                .unwrap_or_default())
+        },
+    );
+    engine.register_raw_fn(
+        "has_scope",
+        &[TypeId::of::<INT>()],
+        |context: NativeCallContext, _: &mut [&mut Dynamic]| Ok(context.scope().is_some()),
+    );
+
+    let mut scope = Scope::new();
+    scope.push("answer", 42 as INT);
+
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, r#"var("answer")"#)?,
+        42
+    );
+    assert_eq!(
+        engine.eval_with_scope::<INT>(
+            &mut scope,
+            r#"let x = 1; { let y = 41; var("x") + var("y") }"#
+        )?,
+        42
+    );
+    assert!(engine.eval::<()>(r#"var("missing")"#).is_ok());
+
+    // The first argument, if a variable, is passed by reference and is not visible in the scope
+    assert!(engine.eval::<bool>(r#"let s = "s"; var(s) == ()"#)?);
+    assert_eq!(engine.eval::<String>(r#"let s = "s"; var(s); s"#)?, "s");
+
+    assert!(engine.eval::<bool>("has_scope(1)")?);
+    assert!(!engine.eval::<bool>("let x = 1; x.has_scope()")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_native_raw_fn_dynamic_param() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_raw_fn(
+        "describe",
+        &[TypeId::of::<Dynamic>()],
+        |_: NativeCallContext, args: &mut [&mut Dynamic]| Ok(format!("a {}", args[0].type_name())),
+    );
+    engine.register_fn("describe", |x: INT| format!("the number {}", x));
+    engine.register_raw_fn(
+        "pair",
+        &[TypeId::of::<ImmutableString>(), TypeId::of::<Dynamic>()],
+        |_: NativeCallContext, args: &mut [&mut Dynamic]| Ok(format!("{}={}", args[0], args[1])),
+    );
+
+    assert_eq!(engine.eval::<String>("describe(42)")?, "the number 42");
+    assert_eq!(engine.eval::<String>("describe(true)")?, "a bool");
+    assert_eq!(engine.eval::<String>(r#"describe("x")"#)?, "a string");
+    assert_eq!(engine.eval::<String>(r#"pair("x", 1)"#)?, "x=1");
+    assert_eq!(engine.eval::<String>(r#""x".pair('y')"#)?, "x=y");
+
+    assert!(matches!(
+        *engine.eval::<String>("pair(1, 2)").expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(ref f, _) if f.starts_with("pair (")
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_native_raw_fn_callback() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_raw_fn(
+        "apply_twice",
+        &[TypeId::of::<FnPtr>(), TypeId::of::<INT>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| {
+            let fp = args[0].clone().cast::<FnPtr>();
+            let value = args[1].clone();
+            let value = context.call_fn_ptr(&fp, &mut [&mut value.clone()])?;
+            context.call_fn_ptr(&fp, &mut [&mut value.clone()])
+        },
+    );
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                fn inc(x) { x + 1 }
+                apply_twice(Fn("inc"), 40)
+            "#
+        )?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                fn double(x) { x * 2 }
+                let f = Fn("double");
+                f.apply_twice(3)
+            "#
+        )?,
+        12
+    );
+
+    // Errors in the callback are propagated
+    assert!(matches!(
+        *engine
+            .eval::<INT>(
+                r#"
+                    fn fail(x) { throw "oops"; }
+                    apply_twice(Fn("fail"), 1)
+                "#
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorInFunctionCall(_, _, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_native_context_position() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_raw_fn(
+        "where_am_i",
+        &[],
+        |context: NativeCallContext, _: &mut [&mut Dynamic]| {
+            let pos = context.position();
+            Ok(format!(
+                "{}:{}",
+                pos.line().unwrap(),
+                pos.position().unwrap()
+            ))
+        },
+    );
+    engine.register_raw_fn(
+        "check_positive",
+        &[TypeId::of::<INT>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| {
+            let x = args[0].as_int().unwrap();
+
+            if x > 0 {
+                Ok(x)
+            } else {
+                Err(Box::new(EvalAltResult::ErrorRuntime(
+                    format!("{} is not positive", x),
+                    context.position(),
+                )))
+            }
+        },
+    );
+
+    assert_eq!(engine.eval::<String>("where_am_i()")?, "1:1");
+    assert_eq!(
+        engine.eval::<String>("let x = 1;\n  let y = where_am_i();\ny")?,
+        "2:11"
+    );
+
+    let err = engine
+        .eval::<INT>("let x = -1;\nx.check_positive()")
+        .expect_err("should error");
+    assert!(!err.position().is_none());
+    assert_eq!(err.position(), Position::new(2, 3));
+
+    let err = engine
+        .eval::<INT>("let x = 0;\n  check_positive(x)")
+        .expect_err("should error");
+    assert_eq!(err.position(), Position::new(2, 3));
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct Point {
+    x: INT,
+    y: INT,
+}
+
+#[test]
+fn test_native_fn_signatures() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("add", |x: i64, y: i64| x + y);
+    engine.register_fn("greet", |name: ImmutableString| format!("hello, {}", name));
+    engine.register_result_fn("clamp_checked", clamp_checked);
+
+    engine.register_type_with_name::<Point>("Point");
+    engine.register_fn("distance", |p: &mut Point| p.x.abs() + p.y.abs());
+
+    let signatures = engine.gen_fn_signatures();
+    let has = |sig: &str| signatures.iter().any(|s| s == sig);
+
+    assert!(has("add(i64, i64) -> i64"));
+    assert!(has("greet(string) -> string"));
+    assert!(has(&format!(
+        "clamp_checked({0}, {0}, {0}) -> Dynamic",
+        std::any::type_name::<INT>()
+    )));
+    assert!(has(&format!(
+        "distance(Point) -> {}",
+        std::any::type_name::<INT>()
+    )));
+
+    // Built-in package functions are included
+    assert!(has(&format!(
+        "abs({0}) -> {0}",
+        std::any::type_name::<INT>()
+    )));
+    assert!(has(&format!(
+        "sub_string(string, {0}, {0}) -> string",
+        std::any::type_name::<INT>()
+    )));
+
+    let mut sorted = signatures.clone();
+    sorted.sort();
+    assert_eq!(signatures, sorted);
+}
+
+#[test]
+fn test_native_fn_alias() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("area", |w: INT, h: INT| w * h);
+    engine.register_fn("area", |side: INT| side * side);
+    engine.register_global_fn_alias("area", "surface")?;
+
+    // Both names call the same implementation, including all overloads
+    assert_eq!(engine.eval::<INT>("area(6, 7)")?, 42);
+    assert_eq!(engine.eval::<INT>("surface(6, 7)")?, 42);
+    assert_eq!(engine.eval::<INT>("surface(5)")?, 25);
+    assert_eq!(engine.eval::<INT>("let x = 3; x.surface(4)")?, 12);
+
+    // Only functions registered with the `Engine` can be aliased
+    assert!(matches!(
+        *engine
+            .register_global_fn_alias("volume", "capacity")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(ref name, _) if name == "volume"
+    ));
+    assert!(matches!(
+        *engine
+            .register_global_fn_alias("abs", "absolute")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+    assert!(matches!(
+        *engine.eval::<INT>("capacity(1)").expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    Ok(())
+}
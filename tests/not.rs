@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult};
+use rhai::{Engine, EvalAltResult, INT};
 
 #[test]
 fn test_not() -> Result<(), Box<EvalAltResult>> {
@@ -17,3 +17,23 @@ fn test_not() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_not_bitwise() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("!0")?, -1);
+    assert_eq!(engine.eval::<INT>("let x = 42; !x")?, -43);
+    assert_eq!(engine.eval::<INT>("!!42")?, 42);
+    assert_eq!(engine.eval::<INT>("!0b1010 & 0b1111")?, 0b0101);
+
+    // Logical NOT still applies to booleans
+    assert_eq!(engine.eval::<bool>("!(1 > 2)")?, true);
+
+    assert!(matches!(
+        *engine.eval::<bool>(r#"!"hello""#).expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(f, _) if f.starts_with("! (")
+    ));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, ParseErrorType, INT};
 
 #[test]
 fn test_number_literal() -> Result<(), Box<EvalAltResult>> {
@@ -41,3 +41,43 @@ fn test_binary_literal() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_integer_literal_out_of_range() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    #[cfg(feature = "only_i32")]
+    {
+        assert_eq!(engine.eval::<INT>("2147483647")?, INT::MAX);
+        assert_eq!(engine.eval::<INT>("-2147483648")?, INT::MIN);
+
+        let err = engine
+            .compile("let x = 1;\nlet y = 5000000000;")
+            .expect_err("should error");
+        assert!(matches!(&*err.0, ParseErrorType::BadInput(msg) if msg.contains("'5000000000'")));
+        assert_eq!(err.1.line(), Some(2));
+        assert_eq!(err.1.position(), Some(9));
+
+        assert!(matches!(
+            &*engine.compile("0x1_0000_0000").expect_err("should error").0,
+            ParseErrorType::BadInput(msg) if msg.contains("out of range")
+        ));
+    }
+
+    #[cfg(not(feature = "only_i32"))]
+    {
+        assert_eq!(engine.eval::<INT>("9223372036854775807")?, INT::MAX);
+        assert_eq!(engine.eval::<INT>("-9223372036854775808")?, INT::MIN);
+
+        assert!(matches!(
+            &*engine.compile("let x = 10000000000000000000;").expect_err("should error").0,
+            ParseErrorType::BadInput(msg) if msg.contains("'10000000000000000000'")
+        ));
+        assert!(matches!(
+            &*engine.compile("0x1_0000_0000_0000_0000").expect_err("should error").0,
+            ParseErrorType::BadInput(msg) if msg.contains("out of range")
+        ));
+    }
+
+    Ok(())
+}
@@ -128,3 +128,49 @@ fn test_max_operations_progress() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+#[cfg(not(feature = "no_index"))]
+fn test_max_operations_callbacks() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_max_operations(500);
+
+    engine.eval::<bool>(
+        r#"
+            fn small(x) { let n = 0; while n < 5 { n += 1; } true }
+            [1, 2, 3].all(Fn("small"))
+        "#,
+    )?;
+
+    // Each callback stays under the limit, but together they exceed it
+    assert!(matches!(
+        *engine
+            .eval::<bool>(
+                r#"
+                    fn check(x) { let n = 0; while n < 20 { n += 1; } true }
+                    [1, 2, 3, 4, 5, 6, 7, 8, 9, 10].all(Fn("check"))
+                "#
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorInFunctionCall(fn_name, err, _)
+            if fn_name == "check" && matches!(*err, EvalAltResult::ErrorTooManyOperations(_))
+    ));
+
+    // Operations performed in callbacks count towards the rest of the script
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r#"
+                    fn check(x) { let n = 0; while n < 20 { n += 1; } true }
+                    [1, 2].all(Fn("check"));
+                    let n = 0;
+                    while n < 60 { n += 1; }
+                "#
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorTooManyOperations(_)
+    ));
+
+    Ok(())
+}
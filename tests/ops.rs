@@ -1,4 +1,6 @@
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, ParseErrorType, RegisterFn, INT};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[test]
 fn test_ops() -> Result<(), Box<EvalAltResult>> {
@@ -21,3 +23,122 @@ fn test_op_precedence() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_ops_compare() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("compare(1, 2)")?, -1);
+    assert_eq!(engine.eval::<INT>("compare(2, 2)")?, 0);
+    assert_eq!(engine.eval::<INT>("compare(3, 2)")?, 1);
+    assert_eq!(engine.eval::<INT>(r#"compare("abc", "abd")"#)?, -1);
+    assert_eq!(engine.eval::<INT>(r#""b".compare("a")"#)?, 1);
+    assert_eq!(engine.eval::<INT>("compare('x', 'x')")?, 0);
+    assert_eq!(engine.eval::<INT>("compare(false, true)")?, -1);
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        assert_eq!(engine.eval::<INT>("compare(1.5, 2.5)")?, -1);
+        assert_eq!(engine.eval::<INT>("compare(2, 1.5)")?, 1);
+        assert_eq!(engine.eval::<INT>("compare(2.0, 2)")?, 0);
+
+        // NaN sorts before all other numbers
+        assert_eq!(engine.eval::<INT>("compare(nan(), -inf())")?, -1);
+        assert_eq!(engine.eval::<INT>("compare(1.0, nan())")?, 1);
+        assert_eq!(engine.eval::<INT>("compare(nan(), nan())")?, 0);
+    }
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"compare(42, "42")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(f, _) if f.starts_with("compare ")
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_ops_chained_comparison() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    assert!(engine.eval::<bool>("0 < 5 < 10")?);
+    assert!(!engine.eval::<bool>("0 < 50 < 10")?);
+    assert!(!engine.eval::<bool>("0 < -5 < 10")?);
+    assert!(engine.eval::<bool>("0 <= 0 < 1 <= 1")?);
+    assert!(engine.eval::<bool>("let x = 7; 10 > x >= 7 > 0")?);
+    assert!(engine.eval::<bool>("let x = 3; 1 + 1 < x < 2 * 2 && x != 0")?);
+
+    // Comparisons other than '<', '<=', '>' and '>=' do not chain
+    assert!(engine.eval::<bool>("1 < 2 == true")?);
+
+    // The middle operand is evaluated only once, and evaluation stops at the first failure
+    let counter = Arc::new(AtomicUsize::new(0));
+    let c = counter.clone();
+    engine.register_fn("tick", move |x: INT| {
+        c.fetch_add(1, Ordering::SeqCst);
+        x
+    });
+
+    assert!(engine.eval::<bool>("0 < tick(5) < 10")?);
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+    assert!(!engine.eval::<bool>("0 > tick(5) > tick(1)")?);
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    // Mixing directions is ambiguous
+    assert!(matches!(
+        *engine.compile("1 < 2 > 0").expect_err("should error").0,
+        ParseErrorType::BadInput(_)
+    ));
+    assert!(matches!(
+        *engine
+            .compile("let x = 1; 3 >= x <= 2")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::BadInput(_)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_ops_mixed_types_same_site() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Debug, Clone)]
+    struct TestStruct(INT);
+
+    let mut engine = Engine::new();
+
+    engine.register_type::<TestStruct>();
+    engine.register_fn("new_ts", |x: INT| TestStruct(x));
+    engine.register_fn("+", |a: TestStruct, b: TestStruct| {
+        TestStruct(a.0 + b.0 + 1000)
+    });
+    engine.register_fn("value", |x: &mut TestStruct| x.0);
+
+    // The same `+` call site sees different operand types on each iteration
+    let ast = engine.compile(
+        r#"
+            let result = "";
+            for i in range(0, 6) {
+                let v = if i % 3 == 0 { i } else if i % 3 == 1 { "x" } else { new_ts(i) };
+                let r = v + v;
+                result += if type_of(r) == "string" { r } else if type_of(r) == type_of(0) { r.to_string() } else { value(r).to_string() };
+                result += ",";
+            }
+            result
+        "#,
+    )?;
+
+    assert_eq!(engine.eval_ast::<String>(&ast)?, "0,xx,1004,6,xx,1010,");
+
+    // A different engine evaluating the same AST resolves operators on its own
+    let ast = engine.compile("let x = 1; for i in range(1, 4) { x = x + i; } x")?;
+
+    let mut engine2 = Engine::new();
+    engine2.register_fn("+", |a: INT, b: INT| a * b);
+
+    assert_eq!(engine2.eval_ast::<INT>(&ast)?, 6);
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 7);
+
+    Ok(())
+}
@@ -48,3 +48,82 @@ fn test_optimizer_parse() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_optimizer_debug_string() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.set_optimization_level(OptimizationLevel::None);
+
+    let ast = engine.compile("let x = 1 + 2 * 3;")?;
+
+    assert_eq!(
+        ast.to_debug_string(),
+        "\
+Let x @ 1:5
+  FnCall + @ 1:11
+    Integer 1 @ 1:9
+    FnCall * @ 1:15
+      Integer 2 @ 1:13
+      Integer 3 @ 1:17
+"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+fn test_optimizer_debug_string_functions() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.set_optimization_level(OptimizationLevel::Full);
+
+    let ast = engine.compile(
+        r#"
+            fn f(a) { a * (2 + 3) }
+            let x = 1 + 2 * 3;
+            if x > 5 { print(f(x)); }
+        "#,
+    )?;
+
+    assert_eq!(
+        ast.to_debug_string(),
+        "\
+fn f(a) @ 2:13
+  FnCall * @ 2:25
+    Variable a @ 2:23
+    Integer 5 @ 2:30
+Let x @ 3:17
+  Integer 7 @ 3:23
+If @ 4:18
+  FnCall > @ 4:18
+    Variable x @ 4:16
+    Integer 5 @ 4:20
+  FnCall print @ 4:24
+    FnCall f @ 4:30
+      Variable x @ 4:32
+"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_optimizer_global_constant() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_global_constant("LIMIT", 10 as INT);
+    engine.set_optimization_level(OptimizationLevel::Full);
+
+    // The constant is resolved at runtime, so it is never folded into the AST
+    let ast = engine.compile("if LIMIT > 5 { 42 } else { 0 }")?;
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 42);
+    assert!(format!("{:?}", ast).contains("LIMIT"));
+
+    // Shadowed names are left alone
+    let ast = engine.compile("let LIMIT = 1; if LIMIT > 5 { 42 } else { 0 }")?;
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 0);
+
+    Ok(())
+}
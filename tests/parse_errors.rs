@@ -0,0 +1,90 @@
+use rhai::{Engine, ParseErrorType, Position};
+
+#[test]
+fn test_parse_errors_unexpected_token() {
+    let engine = Engine::new();
+
+    let err = engine.compile("let x = 1 2").expect_err("should error");
+    assert!(matches!(
+        *err.0,
+        ParseErrorType::UnexpectedToken(ref found, ref expected, _) if found == "2" && expected == ";"
+    ));
+    assert_eq!(err.1, Position::new(1, 11));
+
+    let err = engine.compile("(1 2)").expect_err("should error");
+    assert!(matches!(
+        *err.0,
+        ParseErrorType::UnexpectedToken(ref found, ref expected, _) if found == "2" && expected == ")"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Unexpected '2': expecting ')' for a matching ( in this expression (line 1, position 4)"
+    );
+
+    let err = engine.compile("foo(1 2)").expect_err("should error");
+    assert!(matches!(
+        *err.0,
+        ParseErrorType::UnexpectedToken(ref found, ref expected, _) if found == "2" && expected == ","
+    ));
+}
+
+#[test]
+fn test_parse_errors_unclosed_delimiter() {
+    let engine = Engine::new();
+
+    let err = engine
+        .compile("let x = 1;\nif x > 0 {\n    x = 2;\n")
+        .expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::UnclosedDelimiter("{".to_string(), Position::new(2, 10))
+    );
+    assert_eq!(
+        err.to_string(),
+        "Expecting '}' to close the '{' opened at line 2, position 10 (line 4, position 1)"
+    );
+
+    let err = engine.compile("let x = (1 + 2").expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::UnclosedDelimiter("(".to_string(), Position::new(1, 9))
+    );
+
+    let err = engine.compile("foo(1, 2").expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::UnclosedDelimiter("(".to_string(), Position::new(1, 1))
+    );
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        let err = engine.compile("let x = [1, 2,").expect_err("should error");
+        assert_eq!(
+            *err.0,
+            ParseErrorType::UnclosedDelimiter("[".to_string(), Position::new(1, 9))
+        );
+    }
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        let err = engine.compile("let x = #{a: 1,").expect_err("should error");
+        assert_eq!(
+            *err.0,
+            ParseErrorType::UnclosedDelimiter("#{".to_string(), Position::new(1, 9))
+        );
+    }
+}
+
+#[test]
+fn test_parse_errors_unterminated_string() {
+    let engine = Engine::new();
+
+    let err = engine
+        .compile(r#"let x = "hello"#)
+        .expect_err("should error");
+    assert_eq!(*err.0, ParseErrorType::UnterminatedString);
+    assert_eq!(
+        err.to_string(),
+        "Open string is not terminated (line 1, position 14)"
+    );
+}
@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult};
+use rhai::{Engine, EvalAltResult, RegisterFn, INT};
 use std::sync::{Arc, RwLock};
 
 #[test]
@@ -29,3 +29,123 @@ fn test_print() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_print_containers() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Clone)]
+    struct Point;
+
+    let mut engine = Engine::new();
+
+    let logbook = Arc::new(RwLock::new(Vec::<String>::new()));
+
+    let log = logbook.clone();
+    engine.on_print(move |s| log.write().unwrap().push(s.to_string()));
+
+    let log = logbook.clone();
+    engine.on_debug(move |s| log.write().unwrap().push(s.to_string()));
+
+    engine.register_type_with_name::<Point>("Point");
+    engine.register_fn("point", || Point);
+
+    engine.consume(
+        r#"
+            let x = [#{b: "hello", a: [1, 2]}, #{c: ()}, 'x', true];
+            print(x);
+            debug(x);
+            print(#{p: point()});
+        "#,
+    )?;
+
+    let logbook = logbook.read().unwrap();
+
+    #[cfg(not(feature = "ordered_map"))]
+    {
+        assert_eq!(logbook[0], "[#{a: [1, 2], b: hello}, #{c: ()}, x, true]");
+        #[cfg(not(feature = "only_i32"))]
+        assert_eq!(
+            logbook[1],
+            r#"[#{"a": [i64(1), i64(2)], "b": string("hello")}, #{"c": ()}, char('x'), bool(true)]"#
+        );
+    }
+    // Properties are printed in insertion order
+    #[cfg(feature = "ordered_map")]
+    {
+        assert_eq!(logbook[0], "[#{b: hello, a: [1, 2]}, #{c: ()}, x, true]");
+        #[cfg(not(feature = "only_i32"))]
+        assert_eq!(
+            logbook[1],
+            r#"[#{"b": string("hello"), "a": [i64(1), i64(2)]}, #{"c": ()}, char('x'), bool(true)]"#
+        );
+    }
+    assert_eq!(logbook[2], "#{p: Point}");
+
+    assert_eq!(
+        engine.eval::<String>(r#"[[1, "a"], #{}].to_string()"#)?,
+        "[[1, a], #{}]"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_to_string_to_debug() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Clone)]
+    struct Point(INT, INT);
+    #[derive(Clone)]
+    struct Opaque;
+
+    let mut engine = Engine::new();
+
+    engine.register_type_with_name::<Point>("Point");
+    engine.register_fn("point", |x: INT, y: INT| Point(x, y));
+    engine.register_fn("to_string", |p: &mut Point| format!("({}, {})", p.0, p.1));
+    engine.register_type_with_name::<Opaque>("Opaque");
+    engine.register_fn("opaque", || Opaque);
+
+    assert_eq!(engine.eval::<String>("to_string(42)")?, "42");
+    assert_eq!(engine.eval::<String>("to_debug(42)")?, "42");
+    assert_eq!(engine.eval::<String>(r#"to_debug("hi")"#)?, r#""hi""#);
+    assert_eq!(engine.eval::<String>("'x'.to_debug()")?, "'x'");
+
+    // Custom types use their registered `to_string`, otherwise their names
+    assert_eq!(engine.eval::<String>("to_string(point(1, 2))")?, "(1, 2)");
+    assert_eq!(engine.eval::<String>("to_string(opaque())")?, "Opaque");
+    assert_eq!(engine.eval::<String>("to_debug(opaque())")?, "Opaque");
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        assert_eq!(
+            engine.eval::<String>(r#"to_string(#{name: "Bob", pos: point(3, 4)})"#)?,
+            "#{name: Bob, pos: (3, 4)}"
+        );
+        #[cfg(not(feature = "only_i32"))]
+        #[cfg(not(feature = "ordered_map"))]
+        assert_eq!(
+            engine.eval::<String>(r#"to_debug(#{name: "Bob", age: 42})"#)?,
+            r#"#{"age": i64(42), "name": string("Bob")}"#
+        );
+        #[cfg(not(feature = "only_i32"))]
+        #[cfg(feature = "ordered_map")]
+        assert_eq!(
+            engine.eval::<String>(r#"to_debug(#{name: "Bob", age: 42})"#)?,
+            r#"#{"name": string("Bob"), "age": i64(42)}"#
+        );
+    }
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert_eq!(
+            engine.eval::<String>("to_string([1, [opaque(), [point(0, 0)]], ()])")?,
+            "[1, [Opaque, [(0, 0)]], ()]"
+        );
+        assert_eq!(
+            engine.eval::<String>(r#"to_debug([["a"], [true]])"#)?,
+            r#"[[string("a")], [bool(true)]]"#
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "rand")]
+use rhai::{Engine, EvalAltResult, INT};
+
+#[cfg(not(feature = "no_optimize"))]
+use rhai::OptimizationLevel;
+
+#[cfg(not(feature = "no_index"))]
+use rhai::Array;
+
+#[test]
+fn test_rand_seed() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let draw = |seed: INT| {
+        engine.eval::<String>(&format!(
+            r#"
+                seed({});
+                let s = "";
+                for i in range(0, 10) {{ s += rand_int(1, 100) + ","; }}
+                s
+            "#,
+            seed
+        ))
+    };
+
+    // Seeding makes the sequence reproducible
+    let first = draw(42)?;
+    assert_eq!(draw(42)?, first);
+    assert_ne!(draw(43)?, first);
+
+    // Seeding within a script restarts the sequence
+    assert!(engine.eval::<bool>(
+        "seed(1); let a = rand_int(0, 1000000); seed(1); a == rand_int(0, 1000000)"
+    )?);
+
+    // Separate engines have separate generators
+    let engine2 = Engine::new();
+    engine2.eval::<INT>("seed(42); rand_int(1, 100); rand_int(1, 100)")?;
+    assert_eq!(draw(42)?, first);
+
+    Ok(())
+}
+
+#[test]
+fn test_rand_int() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Both bounds are inclusive
+    assert!(engine.eval::<bool>(
+        r"
+            let seen_lo = false;
+            let seen_hi = false;
+            for i in range(0, 200) {
+                let x = rand_int(-2, 2);
+                if x < -2 || x > 2 { return false; }
+                if x == -2 { seen_lo = true; }
+                if x == 2 { seen_hi = true; }
+            }
+            seen_lo && seen_hi
+        "
+    )?);
+    assert_eq!(engine.eval::<INT>("rand_int(7, 7)")?, 7);
+
+    // The full range does not overflow
+    engine.eval::<INT>(&format!("rand_int({}, {})", INT::MIN + 1, INT::MAX))?;
+
+    assert!(matches!(
+        *engine.eval::<INT>("rand_int(10, 1)").expect_err("should error"),
+        EvalAltResult::ErrorRuntime(s, _) if s.contains("10") && s.contains("1")
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_float"))]
+fn test_rand_float() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>(
+        r"
+            seed(123);
+            let ok = true;
+            for i in range(0, 1000) {
+                let x = rand();
+                if x < 0.0 || x >= 1.0 { ok = false; }
+            }
+            ok
+        "
+    )?);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+fn test_rand_shuffle() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let shuffle = |seed: INT| {
+        engine.eval::<Array>(&format!(
+            "seed({}); let x = [1, 2, 3, 4, 5, 6, 7, 8]; shuffle(x); x",
+            seed
+        ))
+    };
+
+    let to_ints = |a: Array| a.into_iter().map(|v| v.cast::<INT>()).collect::<Vec<_>>();
+
+    let first = to_ints(shuffle(7)?);
+    assert_eq!(to_ints(shuffle(7)?), first);
+
+    // Shuffling keeps all the elements
+    let mut sorted = first.clone();
+    sorted.sort();
+    assert_eq!(sorted, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+    engine.eval::<()>("let x = []; x.shuffle();")?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_optimize"))]
+fn test_rand_full_optimization() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::Full);
+
+    // Random functions must not be evaluated at compile time
+    let x = engine
+        .eval::<Array>("let x = []; for i in range(0, 3) { x.push(rand_int(0, 1000000)); } x")?;
+    let x: Vec<_> = x.into_iter().map(|v| v.cast::<INT>()).collect();
+    assert!(x[0] != x[1] || x[1] != x[2]);
+
+    // Seeding happens when the script runs, not when it is compiled
+    let ast = engine.compile("seed(1); rand_int(0, 1000000)")?;
+    let first = engine.eval_ast::<INT>(&ast)?;
+    engine.eval::<INT>("rand_int(0, 1000000)")?;
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, first);
+    assert_eq!(engine.eval::<INT>("seed(1); rand_int(0, 1000000)")?, first);
+
+    Ok(())
+}
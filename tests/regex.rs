@@ -0,0 +1,129 @@
+#![cfg(feature = "regex")]
+use rhai::{Engine, EvalAltResult};
+
+#[cfg(not(feature = "no_index"))]
+use rhai::Array;
+
+#[test]
+fn test_regex_match() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>(r#"regex_match("hello world", "^h\\w+")"#)?);
+    assert!(!engine.eval::<bool>(r#""hello world".regex_match("^world")"#)?);
+
+    assert_eq!(
+        engine.eval::<String>(r#"regex_find("order #1234 shipped", "\\d+")"#)?,
+        "1234"
+    );
+    assert!(engine
+        .eval::<()>(r#"regex_find("no digits", "\\d+")"#)
+        .is_ok());
+
+    // Patterns used repeatedly in a loop are compiled only once
+    assert_eq!(
+        engine.eval::<rhai::INT>(
+            r#"
+                let count = 0;
+                for word in ["apple", "banana", "avocado", "cherry"] {
+                    if regex_match(word, "^a") { count += 1; }
+                }
+                count
+            "#
+        )?,
+        2
+    );
+
+    // Patterns built at run time, far more than are cached, still match after being evicted
+    assert_eq!(
+        engine.eval::<rhai::INT>(
+            r#"
+                let count = 0;
+                for round in range(0, 2) {
+                    for i in range(0, 200) {
+                        if regex_match("x" + i + "y", "^x" + i + "y$") { count += 1; }
+                    }
+                }
+                count
+            "#
+        )?,
+        400
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_regex_captures() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let caps =
+        engine.eval::<Array>(r#"regex_captures("2020-07-15", "(\\d{4})-(\\d{2})-(\\d{2})")"#)?;
+    let caps: Vec<String> = caps.into_iter().map(|v| v.cast::<String>()).collect();
+    assert_eq!(caps, ["2020-07-15", "2020", "07", "15"]);
+
+    // Unmatched optional groups are ()
+    let caps = engine.eval::<Array>(r#"regex_captures("ab", "a(x)?(b)")"#)?;
+    assert_eq!(caps.len(), 3);
+    assert!(caps[1].is::<()>());
+    assert_eq!(caps[2].clone().cast::<String>(), "b");
+
+    // No match gives an empty array
+    assert!(engine
+        .eval::<Array>(r#"regex_captures("abc", "\\d")"#)?
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_regex_invalid_pattern() {
+    let engine = Engine::new();
+
+    assert!(matches!(
+        *engine
+            .eval::<bool>(r#"regex_match("abc", "(unclosed")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+}
+
+#[test]
+fn test_regex_replace() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Swap capture groups
+    assert_eq!(
+        engine.eval::<String>(r#"regex_replace("Doe, John", "(\\w+), (\\w+)", "$2 $1")"#)?,
+        "John Doe"
+    );
+    assert_eq!(
+        engine.eval::<String>(
+            r#"regex_replace("2020-07-15", "(?P<y>\\d+)-(?P<m>\\d+)-(?P<d>\\d+)", "${d}/${m}/${y}")"#
+        )?,
+        "15/07/2020"
+    );
+
+    // Replace all, or only the first few matches
+    assert_eq!(
+        engine.eval::<String>(r#""a1b2c3".regex_replace("\\d", "*")"#)?,
+        "a*b*c*"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#""a1b2c3".regex_replace("\\d", "*", 2)"#)?,
+        "a*b*c3"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#""a1b2c3".regex_replace("\\d", "*", 0)"#)?,
+        "a1b2c3"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"regex_replace("abc", "[", "x", 1)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use rhai::{Engine, EvalAltResult, Scope, INT};
+
+#[test]
+fn test_return_top_level() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let cond = true; if cond { return 1; } 2")?,
+        1
+    );
+    assert_eq!(
+        engine.eval::<INT>("let cond = false; if cond { return 1; } 2")?,
+        2
+    );
+
+    // A bare 'return' yields ()
+    engine.eval::<()>("return; 42")?;
+
+    // Returning from inside a loop stops the whole script
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                let x = 0;
+                loop {
+                    x += 1;
+                    if x == 5 { return x * 10; }
+                }
+                x
+            "#
+        )?,
+        50
+    );
+
+    // Statements after the 'return' are not run
+    let mut scope = Scope::new();
+    scope.push("x", 0 as INT);
+
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "x = 1; return 99; x = 2;")?,
+        99
+    );
+    assert_eq!(scope.get_value::<INT>("x"), Some(1));
+
+    engine.consume("return 42; throw \"unreachable\";")?;
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 ///! This test simulates an external command object that is driven by a script.
-use rhai::{Engine, EvalAltResult, RegisterFn, Scope, INT};
+use rhai::{Engine, EvalAltResult, RegisterFn, RegisterFnMut, Scope, INT};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
 /// Simulate a command object.
@@ -79,3 +80,56 @@ fn test_side_effects_print() -> Result<(), Box<EvalAltResult>> {
     assert_eq!(*result.read().unwrap(), "42");
     Ok(())
 }
+
+#[test]
+fn test_side_effects_closure() -> Result<(), Box<EvalAltResult>> {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let mut engine = Engine::new();
+
+    // Register a closure capturing host state
+    let calls = counter.clone();
+    engine.register_fn("tick", move |x: INT| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        x + 1
+    });
+
+    assert_eq!(
+        engine.eval::<INT>("let x = 0; x = tick(x); x = tick(x); tick(x)")?,
+        3
+    );
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+    engine.consume("tick(41);")?;
+    assert_eq!(counter.load(Ordering::SeqCst), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_side_effects_closure_mut() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    // The closure owns and mutates its own state
+    let mut count: INT = 0;
+    engine.register_fn_mut("next_id", move || {
+        count += 1;
+        count
+    });
+
+    let mut history = Vec::new();
+    engine.register_fn_mut("record", move |x: &mut INT, delta: INT| {
+        history.push(delta);
+        *x += history.iter().sum::<INT>();
+    });
+
+    assert_eq!(engine.eval::<INT>("next_id(); next_id(); next_id()")?, 3);
+    assert_eq!(engine.eval::<INT>("next_id()")?, 4);
+
+    assert_eq!(
+        engine.eval::<INT>("let x = 0; x.record(1); x.record(2); x")?,
+        4
+    );
+
+    Ok(())
+}
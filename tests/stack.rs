@@ -1,6 +1,11 @@
 #![cfg(not(feature = "unchecked"))]
 use rhai::{Engine, EvalAltResult, ParseError, ParseErrorType};
 
+#[cfg(not(feature = "no_function"))]
+use rhai::{Dynamic, FnPtr, NativeCallContext, INT};
+#[cfg(not(feature = "no_function"))]
+use std::any::TypeId;
+
 #[test]
 #[cfg(not(feature = "no_function"))]
 fn test_stack_overflow_fn_calls() -> Result<(), Box<EvalAltResult>> {
@@ -29,6 +34,55 @@ fn test_stack_overflow_fn_calls() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+#[cfg(not(feature = "no_function"))]
+fn test_stack_overflow_native_reentry() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    // A native function that calls back into a script function
+    engine.register_raw_fn(
+        "call_back",
+        &[TypeId::of::<FnPtr>(), TypeId::of::<INT>()],
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| {
+            let fp = args[0].clone().cast::<FnPtr>();
+            let mut value = args[1].clone();
+            context.call_fn_ptr(&fp, &mut [&mut value])
+        },
+    );
+
+    let ast = engine.compile(
+        r#"
+            fn f(n) { if n >= 3 { return n; } call_back(Fn("f"), n + 1) }
+            f(0)
+        "#,
+    )?;
+    let ast2 = engine.compile(r#"fn g(n) { call_back(Fn("g"), n + 1) } g(0)"#)?;
+
+    engine.set_max_call_levels(10);
+    assert_eq!(engine.eval_ast::<INT>(&ast)?, 3);
+
+    // Each round trip through the native function counts towards the limit
+    engine.set_max_call_levels(4);
+
+    let mut err = engine.eval_ast::<INT>(&ast).expect_err("should error");
+    while let EvalAltResult::ErrorInFunctionCall(_, inner, _) = *err {
+        err = inner;
+    }
+    assert!(matches!(*err, EvalAltResult::ErrorStackOverflow(_)));
+
+    // Unbounded mutual recursion between Rust and script fails cleanly
+    engine.set_max_call_levels(50);
+
+    assert!(matches!(
+        *engine
+            .eval_ast::<INT>(&ast2)
+            .expect_err("should error"),
+        EvalAltResult::ErrorInFunctionCall(name, _, _) if name.starts_with("g > g > g")
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn test_stack_overflow_parsing() -> Result<(), Box<EvalAltResult>> {
     let mut engine = Engine::new();
@@ -79,3 +133,34 @@ fn test_stack_overflow_parsing() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_stack_overflow_parsing_nested_parens() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    let script = format!("{}1{}", "(".repeat(10000), ")".repeat(10000));
+
+    assert!(matches!(
+        engine.compile(&script).expect_err("should error"),
+        ParseError(x, _) if *x == ParseErrorType::ExprTooDeep
+    ));
+
+    engine.set_max_expr_depth(20);
+    assert_eq!(engine.max_expr_depth(), 20);
+
+    engine.compile("((((1))))")?;
+
+    let err = engine
+        .compile(&format!("{}1{}", "(".repeat(20), ")".repeat(20)))
+        .expect_err("should error");
+    assert_eq!(*err.0, ParseErrorType::ExprTooDeep);
+    assert!(!err.1.is_none());
+
+    // Statement blocks share the same limit
+    assert!(matches!(
+        engine.compile(&format!("{}1{}", "{".repeat(20), "}".repeat(20))).expect_err("should error"),
+        ParseError(x, _) if *x == ParseErrorType::ExprTooDeep
+    ));
+
+    Ok(())
+}
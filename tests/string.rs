@@ -1,5 +1,8 @@
 use rhai::{Engine, EvalAltResult, ImmutableString, RegisterFn, INT};
 
+#[cfg(not(feature = "no_index"))]
+use rhai::Array;
+
 #[test]
 fn test_string() -> Result<(), Box<EvalAltResult>> {
     let engine = Engine::new();
@@ -179,10 +182,288 @@ fn test_string_fn() -> Result<(), Box<EvalAltResult>> {
 
     assert_eq!(engine.eval::<INT>(r#"foo1("hello")"#)?, 5);
     assert_eq!(engine.eval::<INT>(r#"foo2("hello")"#)?, 5);
+    assert_eq!(engine.eval::<INT>(r#"foo3("hello")"#)?, 5);
+
+    // Strings built at runtime are accepted just like literals
+    assert_eq!(engine.eval::<INT>(r#"let s = "hel"; foo1(s + "lo")"#)?, 5);
+    assert_eq!(engine.eval::<INT>(r#"let s = "hel"; foo3(s + "lo")"#)?, 5);
+
+    engine.register_fn("join", |x: &str, y: String| format!("{}-{}", x, y));
+
+    assert_eq!(
+        engine.eval::<String>(r#"let s = "a"; join(s + "b", "c")"#)?,
+        "ab-c"
+    );
+    assert_eq!(engine.eval::<String>(r#""x".join("y")"#)?, "x-y");
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_string_bytes() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>(r#"let b = bytes("héllo"); b.len()"#)?, 6);
+    assert_eq!(engine.eval::<INT>(r#"let b = bytes("héllo"); b[1]"#)?, 0xc3);
+    assert_eq!(
+        engine.eval::<String>(r#"from_bytes(bytes("❤ hello!"))"#)?,
+        "\u{2764} hello!"
+    );
+    assert_eq!(engine.eval::<String>("from_bytes([104, 105])")?, "hi");
+    assert_eq!(engine.eval::<String>("from_bytes([])")?, "");
+
+    assert!(matches!(
+        *engine
+            .eval::<String>("from_bytes([0xc3, 0x28])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>("from_bytes([104, 256])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "Array element 1 is not a byte: 256"
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>("from_bytes([-1])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "Array element 0 is not a byte: -1"
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"from_bytes([104, "i"])"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == r#"Array element 1 is not a byte: "i""#
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_string_base64() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<String>(r#"base64_encode("hello world")"#)?,
+        "aGVsbG8gd29ybGQ="
+    );
+    assert_eq!(engine.eval::<String>(r#"base64_encode("")"#)?, "");
+    assert_eq!(engine.eval::<String>(r#"base64_encode("ab")"#)?, "YWI=");
+    assert_eq!(engine.eval::<String>(r#"base64_encode("abc")"#)?, "YWJj");
+    assert_eq!(engine.eval::<String>(r#"base64_encode("???")"#)?, "Pz8/");
+    assert_eq!(
+        engine.eval::<String>(r#"base64_encode("???", true)"#)?,
+        "Pz8_"
+    );
+
+    assert_eq!(
+        engine.eval::<String>(r#"base64_decode("aGVsbG8gd29ybGQ=")"#)?,
+        "hello world"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"base64_decode("aGVsbG8gd29ybGQ")"#)?,
+        "hello world"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"base64_decode("Pz8_", true)"#)?,
+        "???"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"base64_decode(base64_encode("❤ hello! ❤"))"#)?,
+        "\u{2764} hello! \u{2764}"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"base64_decode("Pz8_")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"base64_decode("YWJjZ")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"base64_decode("YW=I")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert_eq!(
+            engine.eval::<String>("base64_encode([0, 255, 128])")?,
+            "AP+A"
+        );
+        assert_eq!(
+            engine.eval::<INT>(r#"let b = base64_decode_bytes("AP+A"); b[1]"#)?,
+            255
+        );
+        assert!(matches!(
+            *engine
+                .eval::<String>(r#"base64_decode("AP+A")"#)
+                .expect_err("should error"),
+            EvalAltResult::ErrorRuntime(_, _)
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_string_hex() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<String>("to_hex([0, 15, 171, 255])")?,
+        "000fabff"
+    );
+    assert_eq!(
+        engine.eval::<String>("to_hex([0, 15, 171, 255], true)")?,
+        "000FABFF"
+    );
+    assert_eq!(engine.eval::<String>("to_hex([])")?, "");
+    assert_eq!(engine.eval::<String>(r#"to_hex(bytes("hi!"))"#)?, "686921");
+    assert_eq!(engine.eval::<INT>(r#"from_hex("00FfaB").len()"#)?, 3);
+    assert_eq!(engine.eval::<INT>(r#"from_hex("00FfaB")[2]"#)?, 0xab);
+    assert_eq!(
+        engine.eval::<String>(r#"from_bytes(from_hex(to_hex(bytes("❤ hex"))))"#)?,
+        "\u{2764} hex"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"from_hex("abc").len()"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"from_hex("0g").len()"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>("to_hex([256])")
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(ref msg, _) if msg == "Array element 0 is not a byte: 256"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_string_radix() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("to_hex(255)")?, "ff");
+    assert_eq!(engine.eval::<String>("to_binary(10)")?, "1010");
+    assert_eq!(engine.eval::<String>("to_octal(8)")?, "10");
+    assert_eq!(engine.eval::<String>("to_hex(0)")?, "0");
+    assert_eq!(engine.eval::<String>("let x = 255; x.to_hex()")?, "ff");
+
+    assert_eq!(engine.eval::<String>("to_hex(255, true)")?, "0xff");
+    assert_eq!(engine.eval::<String>("to_binary(10, true)")?, "0b1010");
+    assert_eq!(engine.eval::<String>("to_octal(8, true)")?, "0o10");
+
+    // Negative numbers are formatted in two's complement
+    #[cfg(not(feature = "only_i32"))]
+    assert_eq!(engine.eval::<String>("to_hex(-1)")?, "ffffffffffffffff");
+    #[cfg(feature = "only_i32")]
+    assert_eq!(engine.eval::<String>("to_hex(-1)")?, "ffffffff");
+
+    Ok(())
+}
+
+#[test]
+fn test_string_char_at() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // "é" takes two bytes in UTF-8
+    assert_eq!(engine.eval::<INT>(r#"char_count("café")"#)?, 4);
+    assert_eq!(
+        engine.eval::<INT>(r#"let s = "café"; s.char_count() - s.len()"#)?,
+        0
+    );
+    #[cfg(not(feature = "no_index"))]
+    assert_eq!(engine.eval::<INT>(r#"bytes("café").len()"#)?, 5);
+
+    assert_eq!(engine.eval::<String>(r#"char_at("café", 3)"#)?, "é");
+    assert_eq!(engine.eval::<String>(r#""naïve".char_at(2)"#)?, "ï");
+    assert_eq!(engine.eval::<String>(r#""naïve".char_at(4)"#)?, "e");
+    assert_eq!(engine.eval::<String>(r#"char_at("café", -1)"#)?, "é");
+    assert_eq!(engine.eval::<String>(r#"char_at("café", -4)"#)?, "c");
+
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"char_at("café", 4)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorStringBounds(4, 4, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"char_at("café", -5)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorStringBounds(4, -5, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(r#"char_at("", 0)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorStringBounds(0, 0, _)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_string_lines() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let lines = engine.eval::<Array>(r#"lines("a\r\nb\n")"#)?;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].clone().cast::<String>(), "a");
+    assert_eq!(lines[1].clone().cast::<String>(), "b");
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                let text = "first\n\nthird";
+                let lines = text.lines();
+                lines.len() * 10 + lines[1].len()
+            "#
+        )?,
+        30
+    );
+    assert_eq!(engine.eval::<INT>(r#"len(lines("single"))"#)?, 1);
+    assert_eq!(engine.eval::<INT>(r#"len(lines(""))"#)?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_string_repeat() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>(r#""ab" * 3 == "ababab""#)?);
+    assert_eq!(engine.eval::<String>(r#"3 * "ab""#)?, "ababab");
+    assert_eq!(engine.eval::<String>(r#""-" * 0"#)?, "");
+    assert_eq!(engine.eval::<String>(r#""é".repeat(2)"#)?, "éé");
+    assert_eq!(engine.eval::<String>(r#"let s = "="; s *= 4; s"#)?, "====");
 
     assert!(matches!(
-        *engine.eval::<INT>(r#"foo3("hello")"#).expect_err("should error"),
-        EvalAltResult::ErrorFunctionNotFound(err, _) if err == "foo3 (&str | ImmutableString)"
+        *engine
+            .eval::<String>(r#""ab" * -1"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, _)
     ));
 
     Ok(())
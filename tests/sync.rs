@@ -0,0 +1,50 @@
+#![cfg(feature = "sync")]
+
+use rhai::{Dynamic, Engine, EvalAltResult, Scope, AST, INT};
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_sync_types() {
+    assert_send_sync::<Engine>();
+    assert_send_sync::<Dynamic>();
+    assert_send_sync::<Scope>();
+    assert_send_sync::<AST>();
+    assert_send_sync::<EvalAltResult>();
+}
+
+#[test]
+fn test_sync_threads() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let ast = engine.compile("let x = 40; x + offset")?;
+
+    let results = thread::scope(|s| {
+        let handles: Vec<_> = (0..4 as INT)
+            .map(|offset| {
+                let engine = &engine;
+                let ast = &ast;
+
+                s.spawn(move || {
+                    let mut scope = Scope::new();
+                    scope.push("offset", offset);
+                    engine
+                        .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+                        .map_err(|err| err.to_string())
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    for (offset, result) in results.into_iter().enumerate() {
+        let value = result?.cast::<INT>();
+        assert_eq!(value, 40 + offset as INT);
+    }
+
+    Ok(())
+}
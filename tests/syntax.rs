@@ -0,0 +1,97 @@
+use rhai::{Engine, EvalAltResult, ParseErrorType, INT};
+
+#[test]
+fn test_custom_syntax() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine
+        .register_custom_syntax(&["double", "$expr$"], |engine, context, scope, inputs| {
+            let value = engine.eval_expression_tree(context, scope, &inputs[0])?;
+            Ok((value.as_int().unwrap() * 2).into())
+        })
+        .unwrap();
+
+    assert_eq!(engine.eval::<INT>("double 21")?, 42);
+    assert_eq!(engine.eval::<INT>("let x = 20; double x + 1")?, 42);
+    assert_eq!(engine.eval::<INT>("1 + double (double 5)")?, 21);
+
+    // Sub-expressions are only evaluated when needed
+    engine
+        .register_custom_syntax(
+            &["when", "$expr$", "do", "$block$"],
+            |engine, context, scope, inputs| {
+                if engine
+                    .eval_expression_tree(context, scope, &inputs[0])?
+                    .as_bool()
+                    .unwrap_or(false)
+                {
+                    engine.eval_expression_tree(context, scope, &inputs[1])
+                } else {
+                    Ok(().into())
+                }
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        engine.eval::<INT>("let x = 0; when x < 10 do { x = 42; }; x")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = 0; when x > 10 do { x = 42; }; x")?,
+        0
+    );
+
+    // Variables can be defined in the scope
+    engine
+        .register_custom_syntax(
+            &["define", "$ident$", "as", "$expr$"],
+            |engine, context, scope, inputs| {
+                let name = inputs[0].get_variable_name().unwrap().to_string();
+                let value = engine.eval_expression_tree(context, scope, &inputs[1])?;
+                scope.push(name, value);
+                Ok(().into())
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        engine.eval::<INT>("let y = 1; define x as y + 41; let z = 0; x + z")?,
+        42
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_syntax_errors() {
+    let mut engine = Engine::new();
+
+    assert!(engine
+        .register_custom_syntax(&["$expr$", "+"], |_, _, _, _| Ok(().into()))
+        .is_err());
+    assert!(engine
+        .register_custom_syntax(&["if", "$expr$"], |_, _, _, _| Ok(().into()))
+        .is_err());
+    assert!(engine
+        .register_custom_syntax(&["double", "a b"], |_, _, _, _| Ok(().into()))
+        .is_err());
+    assert!(engine
+        .register_custom_syntax::<&str>(&[], |_, _, _, _| Ok(().into()))
+        .is_err());
+
+    engine
+        .register_custom_syntax(&["swap", "$ident$", "with", "$ident$"], |_, _, _, _| {
+            Ok(().into())
+        })
+        .unwrap();
+
+    assert!(matches!(
+        *engine.compile("swap x and y").expect_err("should error").0,
+        ParseErrorType::MissingToken(token, _) if token == "with"
+    ));
+    assert!(matches!(
+        *engine.compile("swap 1 with y").expect_err("should error").0,
+        ParseErrorType::VariableExpected
+    ));
+}
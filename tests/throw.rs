@@ -1,4 +1,6 @@
-use rhai::{Engine, EvalAltResult};
+use rhai::{Engine, EvalAltResult, RegisterFn, Scope, INT};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[test]
 fn test_throw() {
@@ -14,3 +16,105 @@ fn test_throw() {
         EvalAltResult::ErrorRuntime(s, _) if s == ""
     ));
 }
+
+#[test]
+fn test_try_finally() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+    scope.push("cleanups", 0 as INT);
+
+    // Success path - the value of the try block is returned
+    assert_eq!(
+        engine.eval_with_scope::<INT>(
+            &mut scope,
+            "try { let x = 40; x + 2 } finally { cleanups += 1; }"
+        )?,
+        42
+    );
+    assert_eq!(scope.get_value::<INT>("cleanups").unwrap(), 1);
+
+    // Error path - the finally block runs before the error propagates
+    assert!(matches!(
+        *engine
+            .eval_with_scope::<INT>(
+                &mut scope,
+                r#"
+                    let resource = 1;
+                    try {
+                        let x = 1;
+                        throw "oops";
+                    } finally {
+                        cleanups += resource;
+                    }
+                    0
+                "#
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(s, _) if s == "oops"
+    ));
+    assert_eq!(scope.get_value::<INT>("cleanups").unwrap(), 2);
+
+    // Loop control flows through the finally block
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let count = 0;
+                for i in range(0, 10) {
+                    try {
+                        if i == 3 { break; }
+                    } finally {
+                        count += 1;
+                    }
+                }
+                count
+            "
+        )?,
+        4
+    );
+
+    // An error in the finally block takes precedence
+    assert!(matches!(
+        *engine
+            .eval::<()>(r#"try { throw "first"; } finally { throw "second"; }"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorRuntime(s, _) if s == "second"
+    ));
+
+    assert!(engine.compile("try { 42 }").is_err());
+    assert!(engine.compile("try 42 finally {}").is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_function"))]
+fn test_try_finally_return() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    let cleanups = Arc::new(AtomicUsize::new(0));
+    let counter = cleanups.clone();
+
+    engine.register_fn("cleanup", move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    // The finally block runs before the function returns
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                fn work(x) {
+                    try {
+                        return x * 2;
+                    } finally {
+                        cleanup();
+                    }
+                }
+                work(21)
+            "
+        )?,
+        42
+    );
+    assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
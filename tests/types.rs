@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, RegisterFn, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, RegisterFn, INT};
 
 #[test]
 fn test_type_of() -> Result<(), Box<EvalAltResult>> {
@@ -48,3 +48,173 @@ fn test_type_of() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_type_predicates() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>("is_int(42)")?);
+    assert!(!engine.eval::<bool>(r#"is_int("42")"#)?);
+    assert!(engine.eval::<bool>(r#"is_string("hello")"#)?);
+    assert!(engine.eval::<bool>("is_char('x')")?);
+    assert!(engine.eval::<bool>("is_bool(true)")?);
+    assert!(engine.eval::<bool>("is_unit(())")?);
+    assert!(engine.eval::<bool>(r#"is_fn_ptr(Fn("foo"))"#)?);
+
+    #[cfg(not(feature = "no_float"))]
+    assert!(engine.eval::<bool>("is_float(1.5)")?);
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert!(engine.eval::<bool>("let x = [1, 2]; x.is_array()")?);
+
+        #[cfg(not(feature = "no_function"))]
+        assert_eq!(
+            engine.eval::<INT>(
+                r#"
+                    fn total(x) {
+                        if is_array(x) {
+                            let sum = 0;
+                            for v in x { sum += v; }
+                            sum
+                        } else {
+                            x
+                        }
+                    }
+                    total([1, 2, 3]) * 10 + total(4)
+                "#
+            )?,
+            64
+        );
+    }
+
+    #[cfg(not(feature = "no_object"))]
+    assert!(engine.eval::<bool>("is_map(#{a: 1})")?);
+
+    // Script-defined functions override the built-in predicates
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(engine.eval::<INT>("fn is_int(x) { 42 } is_int(1)")?, 42);
+
+    // So do registered functions, for the types they are registered for
+    let mut engine = Engine::new();
+    engine.register_fn("is_int", |x: INT| x >= 0);
+    assert!(engine.eval::<bool>("is_int(42)")?);
+    assert!(!engine.eval::<bool>("is_int(-1)")?);
+    assert!(!engine.eval::<bool>("is_int(true)")?);
+
+    // The predicates are part of the core package
+    assert!(Engine::new_raw().eval::<bool>("is_int(42)").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_type_predicates_all_types() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // (type, value)
+    let mut values = vec![
+        ("unit", "()"),
+        ("bool", "true"),
+        ("int", "42"),
+        ("char", "'x'"),
+        ("string", r#""hello""#),
+        ("fn_ptr", r#"Fn("foo")"#),
+    ];
+    #[cfg(not(feature = "no_float"))]
+    values.push(("float", "1.5"));
+    #[cfg(not(feature = "no_index"))]
+    values.push(("array", "[1, 2]"));
+    #[cfg(not(feature = "no_object"))]
+    values.push(("map", "#{a: 1}"));
+
+    // (predicate, types for which it is true)
+    let predicates = [
+        ("is_unit", &["unit"][..]),
+        ("is_bool", &["bool"]),
+        ("is_int", &["int"]),
+        ("is_float", &["float"]),
+        ("is_number", &["int", "float"]),
+        ("is_char", &["char"]),
+        ("is_string", &["string"]),
+        ("is_array", &["array"]),
+        ("is_map", &["map"]),
+        ("is_fn_ptr", &["fn_ptr"]),
+    ];
+
+    for (predicate, types) in predicates.iter() {
+        #[cfg(feature = "no_float")]
+        if *predicate == "is_float" {
+            continue;
+        }
+        #[cfg(feature = "no_index")]
+        if *predicate == "is_array" {
+            continue;
+        }
+        #[cfg(feature = "no_object")]
+        if *predicate == "is_map" {
+            continue;
+        }
+
+        for (typ, value) in values.iter() {
+            assert_eq!(
+                engine.eval::<bool>(&format!("{}({})", predicate, value))?,
+                types.contains(typ),
+                "{}({})",
+                predicate,
+                value
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "tags"))]
+#[cfg(target_pointer_width = "64")]
+fn test_dynamic_size() {
+    assert_eq!(std::mem::size_of::<Dynamic>(), 16);
+}
+
+#[test]
+#[cfg(feature = "tags")]
+fn test_tag() -> Result<(), Box<EvalAltResult>> {
+    use rhai::Scope;
+
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>("tag(42)")?, 0);
+    assert_eq!(
+        engine.eval::<INT>("let x = 42; set_tag(x, 7); let y = x; tag(y)")?,
+        7
+    );
+    assert_eq!(engine.eval::<INT>("let x = 42; x.set_tag(7); x.tag()")?, 7);
+
+    // A new value gets a new tag
+    assert_eq!(
+        engine.eval::<INT>("let x = 42; set_tag(x, 7); x = x + 1; tag(x)")?,
+        0
+    );
+
+    // Tags survive being stored in and read back from the scope
+    let mut scope = Scope::new();
+    let mut value = Dynamic::from(42 as INT);
+    value.set_tag(123);
+    scope.push_dynamic("x", value);
+
+    engine.consume_with_scope(&mut scope, "let y = x; set_tag(y, tag(y) + 1); x = y;")?;
+
+    assert_eq!(scope.get_value::<Dynamic>("x").unwrap().tag(), 124);
+    assert_eq!(scope.get_value::<INT>("x").unwrap(), 42);
+
+    #[cfg(not(feature = "only_i32"))]
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = 1; set_tag(x, 9999999999)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, Scope, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, RegisterFn, Scope, INT};
 
 #[test]
 fn test_var_scope() -> Result<(), Box<EvalAltResult>> {
@@ -19,6 +19,26 @@ fn test_var_scope() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+fn test_var_scope_ast() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    // Compile once, evaluate many times against the same scope
+    let ast1 = engine.compile("let x = 40; let y = x + 1;")?;
+    let ast2 = engine.compile("x += 1; x + y")?;
+
+    engine.eval_ast_with_scope::<()>(&mut scope, &ast1)?;
+    assert_eq!(scope.len(), 2);
+    assert_eq!(scope.get_value::<INT>("x").expect("x should exist"), 40);
+
+    assert_eq!(engine.eval_ast_with_scope::<INT>(&mut scope, &ast2)?, 82);
+    assert_eq!(engine.eval_ast_with_scope::<INT>(&mut scope, &ast2)?, 83);
+    assert_eq!(scope.get_value::<INT>("x").expect("x should exist"), 42);
+
+    Ok(())
+}
+
 #[test]
 fn test_scope_eval() -> Result<(), Box<EvalAltResult>> {
     let engine = Engine::new();
@@ -52,3 +72,214 @@ fn test_scope_eval() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_scope_clear() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    assert!(scope.is_empty());
+
+    scope.push("x", 1 as INT);
+    scope.push("y", true);
+    scope.push_constant("LIMIT", 100 as INT);
+    engine.eval_with_scope::<()>(&mut scope, "let z = x + LIMIT;")?;
+
+    assert_eq!(scope.len(), 4);
+    assert!(!scope.is_empty());
+
+    scope.clear();
+
+    assert_eq!(scope.len(), 0);
+    assert!(scope.is_empty());
+    assert!(!scope.contains("LIMIT"));
+
+    // Constants are gone, so the name can be reused as a variable
+    engine.eval_with_scope::<()>(&mut scope, "let LIMIT = 1; LIMIT = 2;")?;
+    assert_eq!(scope.get_value::<INT>("LIMIT"), Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_rewind() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("x", 1 as INT);
+    engine.consume_with_scope(&mut scope, "let y = x + 1;")?;
+
+    // Enter a block
+    let block_start = scope.len();
+
+    engine.consume_with_scope(&mut scope, "let x = 40; let z = x + y; y = 3;")?;
+    assert_eq!(scope.len(), 4);
+    assert_eq!(scope.get_value::<INT>("x"), Some(40));
+    assert_eq!(scope.get_value::<INT>("z"), Some(42));
+
+    // Leave the block
+    scope.rewind(block_start);
+
+    assert_eq!(scope.len(), 2);
+    assert!(!scope.contains("z"));
+    assert_eq!(scope.get_value::<INT>("x"), Some(1));
+    assert_eq!(scope.get_value::<INT>("y"), Some(3));
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x + y")?, 4);
+
+    // Rewinding past the end leaves the scope unchanged
+    scope.rewind(10);
+    assert_eq!(scope.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_get_set_value() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    // Absent binding is pushed
+    scope.set_value("x", 40 as INT);
+    assert_eq!(scope.len(), 1);
+
+    engine.eval_with_scope::<()>(&mut scope, "x += 2;")?;
+    assert_eq!(scope.get_value::<INT>("x"), Some(42));
+
+    // Existing binding is overwritten in place
+    scope.set_value("x", 123 as INT);
+    assert_eq!(scope.len(), 1);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x")?, 123);
+
+    // Absent or wrong type
+    assert_eq!(scope.get_value::<INT>("y"), None);
+    assert_eq!(scope.get_value::<bool>("x"), None);
+
+    // The last (visible) binding is used
+    engine.eval_with_scope::<()>(&mut scope, "let x = true;")?;
+    assert_eq!(scope.get_value::<bool>("x"), Some(true));
+    assert_eq!(scope.get_value::<INT>("x"), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_lookup_by_name() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    // Names built at run-time look up the same as literals in the script
+    for i in 0..10 {
+        scope.push(format!("var_{}", i), i as INT);
+    }
+
+    assert_eq!(
+        engine.eval_with_scope::<INT>(
+            &mut scope,
+            r"
+                let sum = 0;
+                for i in range(0, 3) { sum += var_1 + var_9; }
+                sum
+            "
+        )?,
+        30
+    );
+
+    // Shadowed bindings - the last one wins, also when forced to search by eval
+    assert_eq!(
+        engine.eval_with_scope::<INT>(
+            &mut scope,
+            r#"
+                let var_3 = 100;
+                eval("let var_5 = 1000;");
+                var_3 + var_5 + var_7
+            "#
+        )?,
+        1107
+    );
+
+    assert!(matches!(
+        *engine.eval_with_scope::<INT>(&mut scope, "var_").expect_err("should error"),
+        EvalAltResult::ErrorVariableNotFound(name, _) if name == "var_"
+    ));
+
+    // Variables defined by one engine are found by another sharing the same scope
+    let engine2 = Engine::new();
+    engine2.consume_with_scope(&mut scope, "let from_engine2 = 40;")?;
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "from_engine2 + var_2")?,
+        42
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_run() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("count", 1 as INT);
+
+    // The value of the last statement is discarded, but side effects on the scope remain
+    engine.run_with_scope(&mut scope, "count *= 10; let total = count + 2; total")?;
+
+    assert_eq!(scope.get_value::<INT>("count"), Some(10));
+    assert_eq!(scope.get_value::<INT>("total"), Some(12));
+
+    engine.run("let x = 42; x")?;
+
+    assert!(matches!(
+        *engine.run("let x = 1; x.foo()").expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_push_types() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point {
+        x: INT,
+        y: INT,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Opaque(INT);
+
+    let mut engine = Engine::new();
+    engine.register_type_with_name::<Point>("Point");
+    engine.register_fn("sum", |p: &mut Point| p.x + p.y);
+
+    let mut scope = Scope::new();
+    scope.push("a", 40 as INT);
+    scope.push("p", Point { x: 1, y: 2 });
+    scope.push("o", Opaque(42));
+    scope.push_dynamic("d", Dynamic::from(true));
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "a + sum(p)")?, 43);
+    assert_eq!(
+        engine.eval_with_scope::<String>(&mut scope, "type_of(p)")?,
+        "Point"
+    );
+    assert!(engine.eval_with_scope::<bool>(&mut scope, "d")?);
+
+    // Unregistered types are stored as opaque values
+    assert_eq!(
+        engine.eval_with_scope::<Opaque>(&mut scope, "let o2 = o; o2")?,
+        Opaque(42)
+    );
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        scope.push("f", 1.5 as rhai::FLOAT);
+        assert_eq!(
+            engine.eval_with_scope::<rhai::FLOAT>(&mut scope, "f * 2.0")?,
+            3.0
+        );
+    }
+
+    assert_eq!(scope.get_value::<Point>("p"), Some(Point { x: 1, y: 2 }));
+
+    Ok(())
+}
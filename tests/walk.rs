@@ -0,0 +1,78 @@
+#![cfg(feature = "internals")]
+#![allow(deprecated)]
+use rhai::{ASTNode, Engine, EvalAltResult, Expr, Position, Stmt};
+
+#[test]
+fn test_ast_walk() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast = engine.compile(
+        r#"
+            let x = foo(1, bar());
+            if x > 0 {
+                print(x);
+            }
+        "#,
+    )?;
+
+    let mut calls = Vec::new();
+    let mut conditions = 0;
+
+    ast.walk(&mut |node| match node {
+        ASTNode::Expr(Expr::FnCall(x)) => calls.push(((x.0).0.to_string(), node.position())),
+        ASTNode::Stmt(Stmt::IfThenElse(_)) => conditions += 1,
+        _ => (),
+    });
+
+    assert_eq!(
+        calls,
+        vec![
+            ("foo".to_string(), Position::new(2, 21)),
+            ("bar".to_string(), Position::new(2, 28)),
+            (">".to_string(), Position::new(3, 18)),
+            ("print".to_string(), Position::new(4, 17)),
+        ]
+    );
+
+    assert_eq!(conditions, 1);
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_ast_walk_functions() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast = engine.compile(
+        r#"
+            fn forbidden(x) { x }
+            fn check(x) { forbidden(x) }
+            check(42)
+        "#,
+    )?;
+
+    let mut count = 0;
+
+    ast.walk(&mut |node| {
+        if let ASTNode::Expr(Expr::FnCall(x)) = node {
+            if (x.0).0 == "forbidden" {
+                count += 1;
+            }
+        }
+    });
+
+    assert_eq!(count, 1);
+
+    let mut returns = 0;
+
+    ast.walk(&mut |node| {
+        if let ASTNode::Stmt(Stmt::Expr(_)) = node {
+            returns += 1;
+        }
+    });
+
+    assert_eq!(returns, 3);
+
+    Ok(())
+}
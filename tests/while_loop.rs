@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, INT};
+use rhai::{Engine, EvalAltResult, ParseErrorType, INT};
 
 #[test]
 fn test_while() -> Result<(), Box<EvalAltResult>> {
@@ -24,3 +24,79 @@ fn test_while() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+fn test_while_let() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Drain an array until `pop` returns ()
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let queue = [1, 2, 3, 4];
+                let sum = 0;
+                let count = 0;
+
+                while let v = pop(queue) {
+                    sum = sum * 10 + v;
+                    count += 1;
+                }
+
+                assert_eq(queue.len(), 0);
+                sum + count * 10000
+            ",
+        )?,
+        44321
+    );
+
+    // `break` and `continue` work as in other loops; the variable is scoped to the body
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let queue = [1, 2, 3, 4, 5, 6];
+                let v = 100;
+                let sum = 0;
+
+                while let v = queue.shift() {
+                    if v == 2 { continue; }
+                    if v == 5 { break; }
+                    sum += v;
+                }
+
+                sum + v + queue.len()
+            ",
+        )?,
+        109
+    );
+
+    // Values other than () keep the loop going, even if they are falsy
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let items = [false, 0, true];
+                let count = 0;
+                while let x = items.pop() { count += 1; }
+                count
+            ",
+        )?,
+        3
+    );
+
+    assert!(matches!(
+        *engine
+            .compile("while let 1 = x {}")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::VariableExpected
+    ));
+    assert!(matches!(
+        *engine
+            .compile("while let x in y {}")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::MissingToken(token, _) if token == "="
+    ));
+
+    Ok(())
+}